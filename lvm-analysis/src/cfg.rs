@@ -0,0 +1,395 @@
+//! The control-flow graph of a program.
+//!
+//! The instruction set has no jumps or calls: the edges are the fallthroughs and
+//! the [`EdgeKind::Interrupt`] edges from the `IVEC` instructions to the handlers
+//! they install, which stand for the calls. A `SPAWN` entry is held by a register,
+//! so it gets no edge; the callers resolving the entries, like the linter, add
+//! them with [`Cfg::build_with_entries`], and `lvm-dis --cfg` shows no process.
+
+use std::{
+    fmt::{Display, UpperHex},
+    ops::Range,
+};
+
+use lvm_core::{Instruction, Program};
+
+/// The kind of a control-flow [`Edge`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EdgeKind {
+    /// The execution continues with the next instruction.
+    Fallthrough,
+    /// `IVEC` installs the handler of the vector; the handler can be entered
+    /// from any later instruction, so the edge is a call rather than a jump.
+    Interrupt(u8),
+}
+
+/// An edge between two basic blocks, by block index.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Edge {
+    from: usize,
+    to: usize,
+    kind: EdgeKind,
+}
+
+impl Edge {
+    /// Returns the index of the source block.
+    pub const fn from(&self) -> usize {
+        self.from
+    }
+
+    /// Returns the index of the target block.
+    pub const fn to(&self) -> usize {
+        self.to
+    }
+
+    /// Returns the kind of the edge.
+    pub const fn kind(&self) -> EdgeKind {
+        self.kind
+    }
+}
+
+/// A run of instructions always executed one after the other.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BasicBlock {
+    range: Range<usize>,
+}
+
+impl BasicBlock {
+    /// Returns the offset of the first instruction.
+    pub fn start(&self) -> usize {
+        self.range.start
+    }
+
+    /// Returns the offset after the last instruction.
+    pub fn end(&self) -> usize {
+        self.range.end
+    }
+
+    /// Returns the offsets of the instructions.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the offset of the last instruction.
+    pub fn last(&self) -> usize {
+        self.range.end - 1
+    }
+}
+
+/// The control-flow graph of a [`Program`].
+///
+/// A block ends before the handler addresses of the `IVEC` instructions and after
+/// the instructions leaving the flow, `HALT` and `IRET`. The entry block starts at
+/// offset 0 and the handler blocks are entered through [`EdgeKind::Interrupt`] edges
+/// from the blocks installing them.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_analysis::{Cfg, EdgeKind};
+/// use lvm_core::Program;
+/// use lvm_parser::ParseSource;
+///
+/// let program = Program::from_source("IVEC #8 #3\nLOAD $1 #1\nHALT\nIRET").unwrap();
+/// let cfg = Cfg::build(&program);
+///
+/// assert_eq!(2, cfg.blocks().len());
+/// assert_eq!(0..3, cfg.blocks()[0].range());
+/// assert_eq!(EdgeKind::Interrupt(8), cfg.edges()[0].kind());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Cfg {
+    blocks: Vec<BasicBlock>,
+    edges: Vec<Edge>,
+    block_of: Vec<usize>,
+    entries: Vec<usize>,
+}
+
+impl Cfg {
+    /// Splits the program into basic blocks and connects them.
+    pub fn build(program: &Program) -> Self {
+        Self::build_with_entries(program, &[])
+    }
+
+    /// Splits the program into basic blocks and connects them, starting a block
+    /// at each of the other entries, like the resolved `SPAWN` targets, which
+    /// have no edge.
+    pub fn build_with_entries(program: &Program, entries: &[usize]) -> Self {
+        let len = program.len();
+        let mut leaders = vec![false; len];
+        if len > 0 {
+            leaders[0] = true;
+        }
+        for entry in entries {
+            if let Some(leader) = leaders.get_mut(*entry) {
+                *leader = true;
+            }
+        }
+
+        for (offset, instruction) in program.iter().enumerate() {
+            match instruction {
+                Instruction::IvecI(ivec) => {
+                    let address: u16 = ivec.address().into();
+                    if let Some(leader) = leaders.get_mut(address as usize) {
+                        *leader = true;
+                    }
+                }
+                Instruction::HaltI(_) | Instruction::IretI(_) if offset + 1 < len => {
+                    leaders[offset + 1] = true;
+                }
+                _ => (),
+            }
+        }
+
+        let mut blocks: Vec<BasicBlock> = vec![];
+        let mut block_of = vec![0; len];
+        for offset in 0..len {
+            if leaders[offset] {
+                blocks.push(BasicBlock {
+                    range: offset..offset + 1,
+                });
+            } else if let Some(block) = blocks.last_mut() {
+                block.range.end = offset + 1;
+            }
+            block_of[offset] = blocks.len() - 1;
+        }
+
+        let mut edges = vec![];
+        for (index, block) in blocks.iter().enumerate() {
+            for offset in block.range() {
+                if let Some(Instruction::IvecI(ivec)) = program.get(offset) {
+                    let address: u16 = ivec.address().into();
+                    if let Some(&to) = block_of.get(address as usize) {
+                        edges.push(Edge {
+                            from: index,
+                            to,
+                            kind: EdgeKind::Interrupt(ivec.vector().value()),
+                        });
+                    }
+                }
+            }
+
+            let leaves = matches!(
+                program.get(block.last()),
+                Some(Instruction::HaltI(_) | Instruction::IretI(_))
+            );
+            if !leaves && index + 1 < blocks.len() {
+                edges.push(Edge {
+                    from: index,
+                    to: index + 1,
+                    kind: EdgeKind::Fallthrough,
+                });
+            }
+        }
+
+        let mut entries: Vec<usize> = entries
+            .iter()
+            .filter_map(|entry| block_of.get(*entry).copied())
+            .collect();
+        entries.sort();
+        entries.dedup();
+
+        Self {
+            blocks,
+            edges,
+            block_of,
+            entries,
+        }
+    }
+
+    /// Returns the blocks, ordered by offset.
+    pub fn blocks(&self) -> &[BasicBlock] {
+        &self.blocks
+    }
+
+    /// Returns the edges, ordered by source block.
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Returns the indexes of the blocks starting at the other entries,
+    /// see [`Cfg::build_with_entries`].
+    pub fn entries(&self) -> &[usize] {
+        &self.entries
+    }
+
+    /// Returns the index of the block holding the instruction at the offset.
+    pub fn block_of(&self, offset: usize) -> Option<usize> {
+        self.block_of.get(offset).copied()
+    }
+
+    /// Returns the edges leaving the block.
+    pub fn successors(&self, block: usize) -> impl Iterator<Item = &Edge> + '_ {
+        self.edges.iter().filter(move |e| e.from == block)
+    }
+
+    /// Returns the edges entering the block.
+    pub fn predecessors(&self, block: usize) -> impl Iterator<Item = &Edge> + '_ {
+        self.edges.iter().filter(move |e| e.to == block)
+    }
+
+    /// Returns the graph in the Graphviz DOT language, labelling the blocks with
+    /// their instructions. It displays the operands in decimal, or in hex with `{:X}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_analysis::Cfg;
+    /// use lvm_core::Program;
+    /// use lvm_parser::ParseSource;
+    ///
+    /// let program = Program::from_source("LOAD $1 #10\nHALT\nPRTI $1").unwrap();
+    /// let cfg = Cfg::build(&program);
+    ///
+    /// let dot = cfg.dot(&program).to_string();
+    /// assert!(dot.starts_with("digraph cfg {"));
+    /// assert!(dot.contains("b0 [label=\"0000: LOAD $1 #10\\l0001: HALT\\l\"];"));
+    ///
+    /// let dot = format!("{:X}", cfg.dot(&program));
+    /// assert!(dot.contains("LOAD 01 000A"));
+    /// ```
+    pub fn dot<'a>(&'a self, program: &'a Program) -> Dot<'a> {
+        Dot { cfg: self, program }
+    }
+}
+
+/// The DOT rendering of a [`Cfg`], see [`Cfg::dot`].
+pub struct Dot<'a> {
+    cfg: &'a Cfg,
+    program: &'a Program,
+}
+
+impl Dot<'_> {
+    fn write<F>(&self, f: &mut std::fmt::Formatter<'_>, label: F) -> std::fmt::Result
+    where
+        F: Fn(&Instruction) -> String,
+    {
+        writeln!(f, "digraph cfg {{")?;
+        writeln!(f, "    node [shape=box, fontname=\"monospace\"];")?;
+
+        for (index, block) in self.cfg.blocks.iter().enumerate() {
+            write!(f, "    b{} [label=\"", index)?;
+            for offset in block.range() {
+                if let Some(instruction) = self.program.get(offset) {
+                    write!(f, "{:04}: {}\\l", offset, label(instruction))?;
+                }
+            }
+            writeln!(f, "\"];")?;
+        }
+
+        for edge in &self.cfg.edges {
+            match edge.kind {
+                EdgeKind::Fallthrough => writeln!(f, "    b{} -> b{};", edge.from, edge.to)?,
+                EdgeKind::Interrupt(vector) => writeln!(
+                    f,
+                    "    b{} -> b{} [style=dashed, label=\"#{}\"];",
+                    edge.from, edge.to, vector
+                )?,
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl Display for Dot<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, |i| format!("{}", i))
+    }
+}
+
+impl UpperHex for Dot<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, |i| format!("{:X}", i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_parser::ParseSource;
+
+    use super::*;
+
+    fn build(source: &str) -> (Program, Cfg) {
+        let program = Program::from_source(source).unwrap();
+        let cfg = Cfg::build(&program);
+        (program, cfg)
+    }
+
+    #[test]
+    fn empty() {
+        let (_, cfg) = build("");
+        assert!(cfg.blocks().is_empty());
+        assert!(cfg.edges().is_empty());
+        assert_eq!(None, cfg.block_of(0));
+    }
+
+    #[test]
+    fn blocks() {
+        let source = "IVEC #8 #4\nLOAD $0 #1\nLOAD $1 #2\nHALT\nPRTI $0\nIVEC #2 #4\nIRET\nPRTI $1";
+        let (_, cfg) = build(source);
+
+        let ranges: Vec<_> = cfg.blocks().iter().map(BasicBlock::range).collect();
+        assert_eq!(vec![0..4, 4..7, 7..8], ranges);
+        assert_eq!(Some(1), cfg.block_of(5));
+
+        let edges: Vec<_> = cfg
+            .edges()
+            .iter()
+            .map(|e| (e.from(), e.to(), e.kind()))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, 1, EdgeKind::Interrupt(8)),
+                (1, 1, EdgeKind::Interrupt(2)),
+            ],
+            edges
+        );
+        assert_eq!(2, cfg.predecessors(1).count());
+        assert_eq!(0, cfg.successors(2).count());
+    }
+
+    #[test]
+    fn fallthrough() {
+        // The handler address splits the main code.
+        let (_, cfg) = build("IVEC #8 #2\nLOAD $0 #1\nLOAD $1 #2\nIRET");
+        let edges: Vec<_> = cfg
+            .edges()
+            .iter()
+            .map(|e| (e.from(), e.to(), e.kind()))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, 1, EdgeKind::Interrupt(8)),
+                (0, 1, EdgeKind::Fallthrough)
+            ],
+            edges
+        );
+    }
+
+    #[test]
+    fn entries() {
+        // The entry splits its block, without an edge.
+        let source = "LOAD $1 #2\nSPAWN $1 $2\nLOAD $3 #1\nHALT";
+        let program = Program::from_source(source).unwrap();
+        let cfg = Cfg::build_with_entries(&program, &[2, 2, 9]);
+
+        let ranges: Vec<_> = cfg.blocks().iter().map(BasicBlock::range).collect();
+        assert_eq!(vec![0..2, 2..4], ranges);
+        assert_eq!(&[1], cfg.entries());
+        assert_eq!(1, cfg.edges().len());
+        assert!(Cfg::build(&program).entries().is_empty());
+    }
+
+    #[test]
+    fn dot() {
+        let (program, cfg) = build("IVEC #8 #2\nHALT\nIRET");
+        let expected = "digraph cfg {
+    node [shape=box, fontname=\"monospace\"];
+    b0 [label=\"0000: IVEC #8 #2\\l0001: HALT\\l\"];
+    b1 [label=\"0002: IRET\\l\"];
+    b0 -> b1 [style=dashed, label=\"#8\"];
+}";
+        assert_eq!(expected, cfg.dot(&program).to_string());
+    }
+}
@@ -0,0 +1,337 @@
+use lvm_core::{Instruction, Program, RIndex};
+
+use crate::{solve, Analysis, Cfg, Direction, RegisterSet};
+
+/// The value of a register found by the [`ConstantPropagation`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Value {
+    /// No path reaches the instruction.
+    Unreached,
+    /// The register holds the constant on all the paths.
+    Constant(u16),
+    /// The register may hold different values.
+    Varying,
+}
+
+/// The values of the registers at an instruction.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Constants(Option<Vec<Value>>);
+
+impl Constants {
+    fn varying() -> Self {
+        Self(Some(vec![Value::Varying; u8::MAX as usize + 1]))
+    }
+
+    /// Returns the value of the register.
+    pub fn get(&self, rindx: RIndex) -> Value {
+        let idx: u8 = rindx.into();
+        match &self.0 {
+            Some(values) => values[idx as usize],
+            None => Value::Unreached,
+        }
+    }
+
+    /// Returns the constant held by the register.
+    pub fn constant(&self, rindx: RIndex) -> Option<u16> {
+        match self.get(rindx) {
+            Value::Constant(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, rindx: RIndex, value: Value) {
+        let idx: u8 = rindx.into();
+        if let Some(values) = &mut self.0 {
+            values[idx as usize] = value;
+        }
+    }
+}
+
+/// The constant propagation: the registers holding the same constant on all the
+/// paths reaching an instruction.
+///
+/// The registers are set outside the code at the entries, so they are not constant.
+/// Once an `IVEC` installs a reachable handler, the handler may run after any later
+/// instruction, so the registers it writes vary after each of them.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_analysis::{solve, Cfg, ConstantPropagation, Value};
+/// use lvm_core::{Program, RIndex};
+/// use lvm_parser::ParseSource;
+///
+/// let program = Program::from_source("LOAD $1 #2\nADD $1 $1 $2\nREAD $1").unwrap();
+/// let cfg = Cfg::build(&program);
+/// let constants = solve(&ConstantPropagation::make(&program, &cfg), &program, &cfg);
+///
+/// let after = constants.after(2).unwrap();
+/// assert_eq!(Value::Varying, after.get(RIndex::make(1)));
+/// assert_eq!(Some(4), after.constant(RIndex::make(2)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConstantPropagation {
+    clobbered: Vec<RegisterSet>,
+}
+
+impl ConstantPropagation {
+    /// Creates a [`ConstantPropagation`] instance for the program and its graph,
+    /// finding the registers the handlers may write after each instruction.
+    pub fn make(program: &Program, cfg: &Cfg) -> Self {
+        let mut clobbered = vec![RegisterSet::default(); program.len()];
+        let reached = reachable(cfg, std::iter::once(0).chain(cfg.entries().iter().copied()));
+
+        for (index, block) in cfg.blocks().iter().enumerate() {
+            if !reached[index] {
+                continue;
+            }
+
+            for offset in block.range() {
+                let Some(Instruction::IvecI(ivec)) = program.get(offset) else {
+                    continue;
+                };
+                let address: u16 = ivec.address().into();
+                let Some(handler) = cfg.block_of(address as usize) else {
+                    continue;
+                };
+
+                // The handler may run after the IVEC and after anything reached from it.
+                let written = writes(program, cfg, &reachable(cfg, [handler]));
+                let later = reachable(cfg, cfg.successors(index).map(|e| e.to()));
+                let offsets = cfg
+                    .blocks()
+                    .iter()
+                    .zip(&later)
+                    .filter(|(_, later)| **later)
+                    .flat_map(|(block, _)| block.range())
+                    .chain(offset..block.end());
+                for offset in offsets {
+                    clobbered[offset].union(&written);
+                }
+            }
+        }
+
+        Self { clobbered }
+    }
+}
+
+/// Builds the graph with the targets of the `SPAWN` instructions as entries, found
+/// by the constant propagation until no new target is reached. Returns false when
+/// the target of a reached `SPAWN` is not a constant, so a process may start at
+/// any instruction.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_analysis::resolve_spawns;
+/// use lvm_core::Program;
+/// use lvm_parser::ParseSource;
+///
+/// let program = Program::from_source("LOAD $1 #3\nSPAWN $1 $2\nHALT\nHALT").unwrap();
+/// let (cfg, resolved) = resolve_spawns(&program);
+/// assert!(resolved);
+/// assert_eq!(3, cfg.blocks()[cfg.entries()[0]].start());
+///
+/// let program = Program::from_source("READ $1\nSPAWN $1 $2\nHALT").unwrap();
+/// assert!(!resolve_spawns(&program).1);
+/// ```
+pub fn resolve_spawns(program: &Program) -> (Cfg, bool) {
+    let mut entries = vec![];
+    loop {
+        let cfg = Cfg::build_with_entries(program, &entries);
+        let constants = solve(&ConstantPropagation::make(program, &cfg), program, &cfg);
+        let mut targets = entries.clone();
+        let mut resolved = true;
+
+        for (offset, instruction) in program.iter().enumerate() {
+            let Instruction::SpawnI(spawn) = instruction else {
+                continue;
+            };
+            match constants.before(offset).map(|c| c.get(spawn.entry())) {
+                Some(Value::Constant(target)) if !targets.contains(&(target as usize)) => {
+                    targets.push(target as usize)
+                }
+                Some(Value::Varying) => resolved = false,
+                _ => (),
+            }
+        }
+
+        if targets.len() == entries.len() {
+            return (cfg, resolved);
+        }
+        entries = targets;
+    }
+}
+
+/// Returns the blocks reached from the blocks, through all the edges.
+fn reachable(cfg: &Cfg, from: impl IntoIterator<Item = usize>) -> Vec<bool> {
+    let mut reached = vec![false; cfg.blocks().len()];
+    let mut work: Vec<usize> = from.into_iter().filter(|b| *b < reached.len()).collect();
+
+    while let Some(block) = work.pop() {
+        if !std::mem::replace(&mut reached[block], true) {
+            work.extend(cfg.successors(block).map(|e| e.to()));
+        }
+    }
+
+    reached
+}
+
+/// Returns the registers written by the instructions of the blocks.
+fn writes(program: &Program, cfg: &Cfg, blocks: &[bool]) -> RegisterSet {
+    let mut written = RegisterSet::default();
+    let offsets = cfg
+        .blocks()
+        .iter()
+        .zip(blocks)
+        .filter(|(_, reached)| **reached)
+        .flat_map(|(block, _)| block.range());
+
+    for instruction in offsets.filter_map(|offset| program.get(offset)) {
+        match instruction {
+            Instruction::SyscallI(_) => return RegisterSet::full(),
+            _ => instruction
+                .writes()
+                .into_iter()
+                .for_each(|r| written.insert(r)),
+        }
+    }
+
+    written
+}
+
+impl Analysis for ConstantPropagation {
+    type Fact = Constants;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom(&self) -> Self::Fact {
+        Constants(None)
+    }
+
+    fn boundary(&self, _instruction: &Instruction) -> Self::Fact {
+        Constants::varying()
+    }
+
+    fn join(&self, fact: &mut Self::Fact, other: &Self::Fact) {
+        match (&mut fact.0, &other.0) {
+            (_, None) => (),
+            (None, Some(_)) => *fact = other.clone(),
+            (Some(values), Some(others)) => {
+                for (value, other) in values.iter_mut().zip(others) {
+                    if value != other {
+                        *value = Value::Varying;
+                    }
+                }
+            }
+        }
+    }
+
+    fn transfer(&self, offset: usize, instruction: &Instruction, fact: &mut Self::Fact) {
+        if fact.0.is_none() {
+            return;
+        }
+
+        self.effect(instruction, fact);
+        if let Some(clobbered) = self.clobbered.get(offset) {
+            for register in clobbered.iter() {
+                fact.set(register, Value::Varying);
+            }
+        }
+    }
+}
+
+impl ConstantPropagation {
+    fn effect(&self, instruction: &Instruction, fact: &mut Constants) {
+        let binary = |a: RIndex, b: RIndex, op: fn(u16, u16) -> Option<u16>| match (
+            fact.get(a),
+            fact.get(b),
+        ) {
+            (Value::Constant(a), Value::Constant(b)) => {
+                op(a, b).map_or(Value::Varying, Value::Constant)
+            }
+            _ => Value::Varying,
+        };
+
+        match instruction {
+            Instruction::LoadI(load) => {
+                fact.set(load.index(), Value::Constant(load.operand().into()))
+            }
+            Instruction::AddI(add) => {
+                let value = binary(add.index1(), add.index2(), |a, b| Some(a.wrapping_add(b)));
+                fact.set(add.index3(), value);
+            }
+            Instruction::DivI(div) => {
+                let value = binary(div.index1(), div.index2(), u16::checked_div);
+                fact.set(div.index3(), value);
+            }
+            Instruction::SyscallI(_) => *fact = Constants::varying(),
+            _ => {
+                for register in instruction.writes() {
+                    fact.set(register, Value::Varying);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::Program;
+    use lvm_parser::ParseSource;
+
+    use super::*;
+    use crate::{solve, Cfg};
+
+    #[test]
+    fn propagation() {
+        let source = "LOAD $1 #7\nLOAD $2 #2\nDIV $1 $2 $3\nDIV $1 $0 $4\nSYSCALL #1\nPRTI $1";
+        let program = Program::from_source(source).unwrap();
+        let cfg = Cfg::build(&program);
+        let constants = solve(&ConstantPropagation::make(&program, &cfg), &program, &cfg);
+
+        let after = constants.after(3).unwrap();
+        assert_eq!(Some(3), after.constant(RIndex::make(3)));
+        assert_eq!(Value::Varying, after.get(RIndex::make(4)));
+        assert_eq!(None, constants.after(4).unwrap().constant(RIndex::make(1)));
+    }
+
+    #[test]
+    fn unreached() {
+        // The handler may interrupt any instruction, so its entry varies.
+        let source = "IVEC #8 #4\nLOAD $1 #2\nPRTI $1\nHALT\nPRTI $1\nIRET\nLOAD $1 #3";
+        let program = Program::from_source(source).unwrap();
+        let cfg = Cfg::build(&program);
+        let constants = solve(&ConstantPropagation::make(&program, &cfg), &program, &cfg);
+
+        assert_eq!(
+            Some(2),
+            constants.before(2).unwrap().constant(RIndex::make(1))
+        );
+        assert_eq!(
+            Value::Varying,
+            constants.before(4).unwrap().get(RIndex::make(1))
+        );
+        assert_eq!(
+            Value::Unreached,
+            constants.before(6).unwrap().get(RIndex::make(1))
+        );
+    }
+
+    #[test]
+    fn handlers() {
+        // The handler may overwrite $1 after any instruction following the IVEC.
+        let source =
+            "LOAD $1 #1\nIVEC #8 #6\nLOAD $1 #2\nLOAD $2 #3\nADD $1 $2 $3\nHALT\nLOAD $1 #9\nIRET";
+        let program = Program::from_source(source).unwrap();
+        let cfg = Cfg::build(&program);
+        let constants = solve(&ConstantPropagation::make(&program, &cfg), &program, &cfg);
+
+        let r = RIndex::make;
+        assert_eq!(Some(1), constants.after(0).unwrap().constant(r(1)));
+        assert_eq!(Value::Varying, constants.after(1).unwrap().get(r(1)));
+        assert_eq!(Value::Varying, constants.after(2).unwrap().get(r(1)));
+        assert_eq!(Some(3), constants.after(3).unwrap().constant(r(2)));
+        assert_eq!(Value::Varying, constants.after(4).unwrap().get(r(3)));
+    }
+}
@@ -0,0 +1,174 @@
+use lvm_core::{Instruction, Program};
+
+use crate::{Cfg, EdgeKind};
+
+/// The direction in which the facts of an [`Analysis`] flow.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    /// From the entry to the exits, e.g. the reaching definitions.
+    Forward,
+    /// From the exits to the entry, e.g. the liveness.
+    Backward,
+}
+
+/// A data-flow analysis over the control-flow graph of a program, see [`solve`].
+///
+/// The facts form a lattice: [`Analysis::bottom`] is the fact of the code not reached
+/// yet, and [`Analysis::join`] merges the facts of the paths meeting at a block.
+pub trait Analysis {
+    type Fact: Clone + PartialEq;
+
+    /// The direction of the analysis.
+    const DIRECTION: Direction;
+
+    /// Returns the fact of the code no path reaches.
+    fn bottom(&self) -> Self::Fact;
+
+    /// Returns the fact at a boundary of the graph: before the first instruction of
+    /// an entry, for a forward analysis, or after the last instruction of an exit,
+    /// for a backward analysis.
+    fn boundary(&self, instruction: &Instruction) -> Self::Fact;
+
+    /// Merges the other fact into the fact.
+    fn join(&self, fact: &mut Self::Fact, other: &Self::Fact);
+
+    /// Applies the effect of the instruction to the fact, in the direction of the analysis.
+    fn transfer(&self, offset: usize, instruction: &Instruction, fact: &mut Self::Fact);
+}
+
+/// The facts computed by [`solve`] for every instruction of a program.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Solution<F> {
+    before: Vec<F>,
+    after: Vec<F>,
+}
+
+impl<F> Solution<F> {
+    /// Returns the fact holding before the instruction runs.
+    pub fn before(&self, offset: usize) -> Option<&F> {
+        self.before.get(offset)
+    }
+
+    /// Returns the fact holding after the instruction runs.
+    pub fn after(&self, offset: usize) -> Option<&F> {
+        self.after.get(offset)
+    }
+}
+
+/// Solves the analysis over the graph of the program, iterating until the facts
+/// of the blocks stop changing.
+///
+/// The entries are the first block, the handler blocks and the other entries of the
+/// graph; the exits are the blocks ending with `HALT` or `IRET` and the last block.
+/// A handler is analysed as a separate entry: its effects on the code it interrupts
+/// are left to the analysis, as in [`crate::ConstantPropagation::make`].
+///
+/// # Examples
+///
+/// ```
+/// use lvm_analysis::{solve, Cfg, Liveness, RegisterSet};
+/// use lvm_core::{Program, RIndex};
+/// use lvm_parser::ParseSource;
+///
+/// let program = Program::from_source("LOAD $1 #2\nLOAD $2 #3\nPRTI $1").unwrap();
+/// let liveness = solve(&Liveness::default(), &program, &Cfg::build(&program));
+///
+/// let live = liveness.after(0).unwrap();
+/// assert!(live.contains(RIndex::make(1)));
+/// assert!(!live.contains(RIndex::make(2)));
+/// ```
+pub fn solve<A: Analysis>(analysis: &A, program: &Program, cfg: &Cfg) -> Solution<A::Fact> {
+    let blocks = cfg.blocks();
+    let forward = A::DIRECTION == Direction::Forward;
+
+    // The facts at the start and the end of each block, in the direction of the analysis.
+    let mut inputs: Vec<A::Fact> = vec![analysis.bottom(); blocks.len()];
+    let mut outputs: Vec<A::Fact> = vec![analysis.bottom(); blocks.len()];
+
+    let mut flows: Vec<Vec<usize>> = vec![vec![]; blocks.len()];
+    let mut entries: Vec<bool> = vec![false; blocks.len()];
+    for &entry in cfg.entries() {
+        entries[entry] = true;
+    }
+    for edge in cfg.edges() {
+        match (edge.kind(), forward) {
+            (EdgeKind::Fallthrough, true) => flows[edge.to()].push(edge.from()),
+            (EdgeKind::Fallthrough, false) => flows[edge.from()].push(edge.to()),
+            (EdgeKind::Interrupt(_), true) => entries[edge.to()] = true,
+            (EdgeKind::Interrupt(_), false) => (),
+        }
+    }
+
+    let boundaries: Vec<Option<A::Fact>> = blocks
+        .iter()
+        .enumerate()
+        .map(|(index, block)| {
+            let (boundary, offset) = match forward {
+                true => (index == 0 || entries[index], block.start()),
+                false => (flows[index].is_empty(), block.last()),
+            };
+            match boundary {
+                true => program.get(offset).map(|i| analysis.boundary(i)),
+                false => None,
+            }
+        })
+        .collect();
+
+    let order: Vec<usize> = match forward {
+        true => (0..blocks.len()).collect(),
+        false => (0..blocks.len()).rev().collect(),
+    };
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &index in &order {
+            let mut input = match &boundaries[index] {
+                Some(boundary) => boundary.clone(),
+                None => analysis.bottom(),
+            };
+            for &other in &flows[index] {
+                analysis.join(&mut input, &outputs[other]);
+            }
+
+            let mut output = input.clone();
+            for offset in offsets(blocks[index].range(), forward) {
+                if let Some(instruction) = program.get(offset) {
+                    analysis.transfer(offset, instruction, &mut output);
+                }
+            }
+
+            inputs[index] = input;
+            if output != outputs[index] {
+                outputs[index] = output;
+                changed = true;
+            }
+        }
+    }
+
+    let mut before = vec![analysis.bottom(); program.len()];
+    let mut after = vec![analysis.bottom(); program.len()];
+    for (index, block) in blocks.iter().enumerate() {
+        let mut fact = inputs[index].clone();
+        for offset in offsets(block.range(), forward) {
+            if let Some(instruction) = program.get(offset) {
+                let (first, second) = match forward {
+                    true => (&mut before, &mut after),
+                    false => (&mut after, &mut before),
+                };
+                first[offset] = fact.clone();
+                analysis.transfer(offset, instruction, &mut fact);
+                second[offset] = fact.clone();
+            }
+        }
+    }
+
+    Solution { before, after }
+}
+
+fn offsets(range: std::ops::Range<usize>, forward: bool) -> Box<dyn Iterator<Item = usize>> {
+    match forward {
+        true => Box::new(range),
+        false => Box::new(range.rev()),
+    }
+}
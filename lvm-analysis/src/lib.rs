@@ -0,0 +1,13 @@
+mod cfg;
+mod constants;
+mod dataflow;
+mod liveness;
+mod reaching;
+mod registers;
+
+pub use cfg::*;
+pub use constants::*;
+pub use dataflow::*;
+pub use liveness::*;
+pub use reaching::*;
+pub use registers::*;
@@ -0,0 +1,104 @@
+use lvm_core::Instruction;
+
+use crate::{Analysis, Direction, RegisterSet};
+
+/// The liveness of the registers: the registers whose value may be read later.
+///
+/// The registers read by the host when the program ends are live at its end, none
+/// by default. The interrupted code resumes after `IRET`, so all the registers are
+/// live there, and a syscall may read any register.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_analysis::{solve, Cfg, Liveness, RegisterSet};
+/// use lvm_core::{Program, RIndex};
+/// use lvm_parser::ParseSource;
+///
+/// let program = Program::from_source("LOAD $1 #2\nLOAD $2 #3").unwrap();
+/// let exit: RegisterSet = [RIndex::make(2)].into_iter().collect();
+/// let liveness = solve(&Liveness::default().with_exit(exit), &program, &Cfg::build(&program));
+///
+/// assert_eq!("{}", liveness.after(0).unwrap().to_string());
+/// assert_eq!("{$2}", liveness.after(1).unwrap().to_string());
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Liveness {
+    exit: RegisterSet,
+}
+
+impl Liveness {
+    /// Sets the registers live when the program ends.
+    pub fn with_exit(mut self, exit: RegisterSet) -> Self {
+        self.exit = exit;
+        self
+    }
+}
+
+impl Analysis for Liveness {
+    type Fact = RegisterSet;
+
+    const DIRECTION: Direction = Direction::Backward;
+
+    fn bottom(&self) -> Self::Fact {
+        RegisterSet::default()
+    }
+
+    fn boundary(&self, instruction: &Instruction) -> Self::Fact {
+        match instruction {
+            Instruction::IretI(_) => RegisterSet::full(),
+            _ => self.exit,
+        }
+    }
+
+    fn join(&self, fact: &mut Self::Fact, other: &Self::Fact) {
+        fact.union(other);
+    }
+
+    fn transfer(&self, _offset: usize, instruction: &Instruction, fact: &mut Self::Fact) {
+        if let Instruction::SyscallI(_) = instruction {
+            *fact = RegisterSet::full();
+            return;
+        }
+
+        for register in instruction.writes() {
+            fact.remove(register);
+        }
+        for register in instruction.reads() {
+            fact.insert(register);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::{Program, RIndex};
+    use lvm_parser::ParseSource;
+
+    use super::*;
+    use crate::{solve, Cfg};
+
+    #[test]
+    fn liveness() {
+        let source = "LOAD $1 #2\nLOAD $2 #3\nADD $1 $2 $1\nLOAD $2 #0\nPRTI $1";
+        let program = Program::from_source(source).unwrap();
+        let liveness = solve(&Liveness::default(), &program, &Cfg::build(&program));
+
+        let live: Vec<String> = (0..program.len())
+            .map(|offset| liveness.before(offset).unwrap().to_string())
+            .collect();
+        assert_eq!(vec!["{}", "{$1}", "{$1, $2}", "{$1}", "{$1}"], live);
+        assert!(!liveness.after(3).unwrap().contains(RIndex::make(2)));
+    }
+
+    #[test]
+    fn handlers() {
+        let source = "IVEC #8 #3\nLOAD $1 #2\nHALT\nLOAD $3 #1\nIRET";
+        let program = Program::from_source(source).unwrap();
+        let liveness = solve(&Liveness::default(), &program, &Cfg::build(&program));
+
+        assert!(liveness.after(1).unwrap().is_empty());
+        assert!(liveness.after(3).unwrap().contains(RIndex::make(3)));
+        assert!(!liveness.before(3).unwrap().contains(RIndex::make(3)));
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeSet;
+
+use lvm_core::{Instruction, RIndex};
+
+use crate::{Analysis, Direction};
+
+/// A definition of a register: the instruction writing it, or the entry of the
+/// program or of a handler, where the register holds a value set outside the code.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Definition {
+    register: RIndex,
+    offset: Option<usize>,
+}
+
+impl Definition {
+    /// Creates a [`Definition`] instance, `None` standing for the entry.
+    pub fn make(register: RIndex, offset: Option<usize>) -> Self {
+        Self { register, offset }
+    }
+
+    /// Returns the defined register.
+    pub const fn register(&self) -> RIndex {
+        self.register
+    }
+
+    /// Returns the offset of the defining instruction, or `None` for the entry.
+    pub const fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+/// The reaching definitions: the definitions whose value a register may hold.
+///
+/// A syscall may write any register, so it defines all of them without removing
+/// the previous definitions.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_analysis::{solve, Cfg, Definition, ReachingDefinitions};
+/// use lvm_core::{Program, RIndex};
+/// use lvm_parser::ParseSource;
+///
+/// let program = Program::from_source("LOAD $1 #2\nLOAD $1 #3\nPRTI $1").unwrap();
+/// let reaching = solve(&ReachingDefinitions::make(8), &program, &Cfg::build(&program));
+///
+/// let defs = reaching.before(2).unwrap();
+/// assert!(defs.contains(&Definition::make(RIndex::make(1), Some(1))));
+/// assert!(!defs.contains(&Definition::make(RIndex::make(1), Some(0))));
+/// assert!(defs.contains(&Definition::make(RIndex::make(2), None)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ReachingDefinitions {
+    registers: usize,
+}
+
+impl ReachingDefinitions {
+    /// Creates a [`ReachingDefinitions`] instance for machines with the number of registers.
+    pub fn make(registers: usize) -> Self {
+        Self {
+            registers: registers.min(u8::MAX as usize + 1),
+        }
+    }
+
+    /// Returns the definitions of the register among the reaching definitions.
+    pub fn of(
+        facts: &BTreeSet<Definition>,
+        register: RIndex,
+    ) -> impl Iterator<Item = &Definition> + '_ {
+        let first = Definition::make(register, None);
+        facts
+            .range(first..)
+            .take_while(move |d| d.register == register)
+    }
+
+    fn registers(&self) -> impl Iterator<Item = RIndex> {
+        (0..self.registers).map(|r| RIndex::make(r as u8))
+    }
+}
+
+impl Analysis for ReachingDefinitions {
+    type Fact = BTreeSet<Definition>;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom(&self) -> Self::Fact {
+        BTreeSet::new()
+    }
+
+    fn boundary(&self, _instruction: &Instruction) -> Self::Fact {
+        self.registers()
+            .map(|r| Definition::make(r, None))
+            .collect()
+    }
+
+    fn join(&self, fact: &mut Self::Fact, other: &Self::Fact) {
+        fact.extend(other.iter().copied());
+    }
+
+    fn transfer(&self, offset: usize, instruction: &Instruction, fact: &mut Self::Fact) {
+        if let Instruction::SyscallI(_) = instruction {
+            fact.extend(self.registers().map(|r| Definition::make(r, Some(offset))));
+            return;
+        }
+
+        for register in instruction.writes() {
+            fact.retain(|d| d.register != register);
+            fact.insert(Definition::make(register, Some(offset)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::Program;
+    use lvm_parser::ParseSource;
+
+    use super::*;
+    use crate::{solve, Cfg};
+
+    fn offsets(facts: &BTreeSet<Definition>, register: u8) -> Vec<Option<usize>> {
+        ReachingDefinitions::of(facts, RIndex::make(register))
+            .map(Definition::offset)
+            .collect()
+    }
+
+    #[test]
+    fn straight() {
+        let program =
+            Program::from_source("LOAD $1 #2\nSYSCALL #1\nADD $1 $2 $3\nPRTI $3").unwrap();
+        let reaching = solve(
+            &ReachingDefinitions::make(4),
+            &program,
+            &Cfg::build(&program),
+        );
+
+        let before = reaching.before(2).unwrap();
+        assert_eq!(vec![Some(0), Some(1)], offsets(before, 1));
+        assert_eq!(vec![None, Some(1)], offsets(before, 2));
+        assert_eq!(vec![Some(2)], offsets(reaching.after(2).unwrap(), 3));
+        assert!(offsets(before, 4).is_empty());
+    }
+
+    #[test]
+    fn handlers() {
+        // The handler is an entry of its own.
+        let source = "IVEC #8 #3\nLOAD $1 #2\nHALT\nPRTI $1\nIRET";
+        let program = Program::from_source(source).unwrap();
+        let reaching = solve(
+            &ReachingDefinitions::make(8),
+            &program,
+            &Cfg::build(&program),
+        );
+
+        assert_eq!(vec![Some(1)], offsets(reaching.before(2).unwrap(), 1));
+        assert_eq!(vec![None], offsets(reaching.before(3).unwrap(), 1));
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt::Display;
+
+use lvm_core::RIndex;
+
+/// A set of registers.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_analysis::RegisterSet;
+/// use lvm_core::RIndex;
+///
+/// let mut set = RegisterSet::default();
+/// set.insert(RIndex::make(3));
+/// set.insert(RIndex::make(1));
+///
+/// assert!(set.contains(RIndex::make(1)));
+/// assert_eq!("{$1, $3}", set.to_string());
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct RegisterSet([u64; 4]);
+
+impl RegisterSet {
+    /// Returns the set of all the registers.
+    pub fn full() -> Self {
+        Self([u64::MAX; 4])
+    }
+
+    /// Returns true when the set has the register.
+    pub fn contains(&self, rindx: RIndex) -> bool {
+        let (word, bit) = Self::position(rindx);
+        self.0[word] & bit != 0
+    }
+
+    /// Adds the register to the set.
+    pub fn insert(&mut self, rindx: RIndex) {
+        let (word, bit) = Self::position(rindx);
+        self.0[word] |= bit;
+    }
+
+    /// Removes the register from the set.
+    pub fn remove(&mut self, rindx: RIndex) {
+        let (word, bit) = Self::position(rindx);
+        self.0[word] &= !bit;
+    }
+
+    /// Adds the registers of the other set.
+    pub fn union(&mut self, other: &Self) {
+        for (word, other) in self.0.iter_mut().zip(other.0) {
+            *word |= other;
+        }
+    }
+
+    /// Returns true when the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|word| *word == 0)
+    }
+
+    /// Returns the registers, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RIndex> + '_ {
+        (0..=u8::MAX)
+            .map(RIndex::make)
+            .filter(|rindx| self.contains(*rindx))
+    }
+
+    fn position(rindx: RIndex) -> (usize, u64) {
+        let idx: u8 = rindx.into();
+        (idx as usize / 64, 1 << (idx % 64))
+    }
+}
+
+impl FromIterator<RIndex> for RegisterSet {
+    fn from_iter<T: IntoIterator<Item = RIndex>>(iter: T) -> Self {
+        let mut set = Self::default();
+        for rindx in iter {
+            set.insert(rindx);
+        }
+        set
+    }
+}
+
+impl Display for RegisterSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let registers: Vec<String> = self.iter().map(|r| r.to_string()).collect();
+        write!(f, "{{{}}}", registers.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set() {
+        let mut set: RegisterSet = [0, 64, 255].map(RIndex::make).into_iter().collect();
+        assert_eq!(3, set.iter().count());
+
+        set.remove(RIndex::make(64));
+        assert!(!set.contains(RIndex::make(64)));
+        assert!(set.contains(RIndex::make(255)));
+
+        let mut other = RegisterSet::default();
+        assert!(other.is_empty());
+        other.union(&set);
+        assert_eq!(set, other);
+        assert_eq!(256, RegisterSet::full().iter().count());
+    }
+}
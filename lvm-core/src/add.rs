@@ -4,7 +4,7 @@ use crate::RIndex;
 
 /// Structure that represents the load instruction.
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Add {
     rindx1: RIndex,
     rindx2: RIndex,
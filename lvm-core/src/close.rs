@@ -0,0 +1,115 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the close instruction, which closes the file handle held by a register.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Close {
+    rindx: RIndex,
+}
+
+impl Close {
+    pub const PREFIX: &str = "CLOSE";
+    pub const ID: u8 = 10;
+
+    /// Creates a [`Close`] instance.
+    pub fn make(rindx: RIndex) -> Self {
+        Self { rindx }
+    }
+
+    /// Returns the register index.
+    pub const fn index(&self) -> RIndex {
+        self.rindx
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Close`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{Close, RIndex};
+///
+/// let close = Close::make(RIndex::make(10u8));
+/// assert_eq!("CLOSE $10", close.to_string())
+/// ```
+impl Display for Close {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", Self::PREFIX, self.rindx)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Close`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{Close, RIndex};
+///
+/// let close = Close::make(RIndex::make(10u8));
+/// assert_eq!("CLOSE 0A", format!("{:#X}", close))
+/// ```
+impl UpperHex for Close {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:X}", Self::PREFIX, self.rindx)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Close`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{Close, RIndex};
+///
+/// let close = Close::make(RIndex::make(10u8));
+/// assert_eq!("CLOSE 0a", format!("{:#x}", close))
+/// ```
+impl LowerHex for Close {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:x}", Self::PREFIX, self.rindx)
+    }
+}
+
+impl From<Close> for [u8; 4] {
+    fn from(close: Close) -> Self {
+        [Close::ID, close.index().into(), 0, 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string() {
+        let close = Close::make(RIndex::make(10u8));
+        assert_eq!("CLOSE $10", close.to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        let close = Close::make(RIndex::make(10u8));
+        assert_eq!("CLOSE 0A", format!("{:#X}", close))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        let close = Close::make(RIndex::make(10u8));
+        assert_eq!("CLOSE 0a", format!("{:#x}", close))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let close = Close::make(RIndex::make(10u8));
+        let bytes: [u8; 4] = close.into();
+
+        assert_eq!([10, 10, 0, 0], bytes);
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the division instruction, which stores the
+/// quotient of the first two registers in the third one.
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Div {
+    rindx1: RIndex,
+    rindx2: RIndex,
+    rindx3: RIndex,
+}
+
+impl Div {
+    pub const PREFIX: &str = "DIV";
+    pub const ID: u8 = 11;
+
+    /// Creates a [`Div`] instance.
+    pub fn make(rindx1: RIndex, rindx2: RIndex, rindx3: RIndex) -> Self {
+        Self {
+            rindx1,
+            rindx2,
+            rindx3,
+        }
+    }
+
+    /// Returns the register index.
+    pub const fn index1(&self) -> RIndex {
+        self.rindx1
+    }
+
+    /// Returns the register index.
+    pub const fn index2(&self) -> RIndex {
+        self.rindx2
+    }
+
+    /// Returns the register index.
+    pub const fn index3(&self) -> RIndex {
+        self.rindx3
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Div`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{Div, RIndex};
+///
+/// let rindx1 = RIndex::make(10u8);
+/// let rindx2 = RIndex::make(20u8);
+/// let rindx3 = RIndex::make(30u8);
+/// let div = Div::make(rindx1, rindx2, rindx3);
+/// assert_eq!("DIV $10 $20 $30", div.to_string())
+/// ```
+impl Display for Div {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            Self::PREFIX,
+            self.rindx1,
+            self.rindx2,
+            self.rindx3
+        )
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Div`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{Div, RIndex};
+///
+/// let rindx1 = RIndex::make(10u8);
+/// let rindx2 = RIndex::make(20u8);
+/// let rindx3 = RIndex::make(30u8);
+/// let div = Div::make(rindx1, rindx2, rindx3);
+/// assert_eq!("DIV 0A 14 1E", format!("{:#X}", div))
+/// ```
+impl UpperHex for Div {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:X} {:X} {:X}",
+            Self::PREFIX,
+            self.rindx1,
+            self.rindx2,
+            self.rindx3
+        )
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Div`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{Div, RIndex};
+///
+/// let rindx1 = RIndex::make(10u8);
+/// let rindx2 = RIndex::make(20u8);
+/// let rindx3 = RIndex::make(30u8);
+/// let div = Div::make(rindx1, rindx2, rindx3);
+/// assert_eq!("DIV 0a 14 1e", format!("{:#x}", div))
+/// ```
+impl LowerHex for Div {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:x} {:x} {:x}",
+            Self::PREFIX,
+            self.rindx1,
+            self.rindx2,
+            self.rindx3
+        )
+    }
+}
+
+impl From<Div> for [u8; 4] {
+    fn from(div: Div) -> Self {
+        [
+            Div::ID,
+            div.index1().into(),
+            div.index2().into(),
+            div.index3().into(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string() {
+        let rindx1 = RIndex::make(10u8);
+        let rindx2 = RIndex::make(20u8);
+        let rindx3 = RIndex::make(30u8);
+        let div = Div::make(rindx1, rindx2, rindx3);
+        assert_eq!("DIV $10 $20 $30", div.to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        let rindx1 = RIndex::make(10u8);
+        let rindx2 = RIndex::make(20u8);
+        let rindx3 = RIndex::make(30u8);
+        let div = Div::make(rindx1, rindx2, rindx3);
+        assert_eq!("DIV 0A 14 1E", format!("{:#X}", div))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        let rindx1 = RIndex::make(10u8);
+        let rindx2 = RIndex::make(20u8);
+        let rindx3 = RIndex::make(30u8);
+        let div = Div::make(rindx1, rindx2, rindx3);
+        assert_eq!("DIV 0a 14 1e", format!("{:#x}", div))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let rindx1 = RIndex::make(10u8);
+        let rindx2 = RIndex::make(20u8);
+        let rindx3 = RIndex::make(30u8);
+        let div = Div::make(rindx1, rindx2, rindx3);
+        let bytes: [u8; 4] = div.into();
+
+        assert_eq!(11, bytes[0]);
+        assert_eq!(10, bytes[1]);
+        assert_eq!(20, bytes[2]);
+        assert_eq!(30, bytes[3]);
+    }
+}
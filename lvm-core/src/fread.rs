@@ -0,0 +1,156 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the file read instruction, which reads from a file handle into memory.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FRead {
+    handle: RIndex,
+    buffer: RIndex,
+    len: RIndex,
+}
+
+impl FRead {
+    pub const PREFIX: &str = "FREAD";
+    pub const ID: u8 = 8;
+
+    /// Creates a [`FRead`] instance.
+    pub fn make(handle: RIndex, buffer: RIndex, len: RIndex) -> Self {
+        Self {
+            handle,
+            buffer,
+            len,
+        }
+    }
+
+    /// Returns the register holding the file handle.
+    pub const fn handle(&self) -> RIndex {
+        self.handle
+    }
+
+    /// Returns the register holding the address of the buffer.
+    pub const fn buffer(&self) -> RIndex {
+        self.buffer
+    }
+
+    /// Returns the register holding the number of bytes to read, then the number of bytes read.
+    pub const fn len(&self) -> RIndex {
+        self.len
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`FRead`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{FRead, RIndex};
+///
+/// let fread = FRead::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8));
+/// assert_eq!("FREAD $1 $2 $10", fread.to_string())
+/// ```
+impl Display for FRead {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            Self::PREFIX,
+            self.handle,
+            self.buffer,
+            self.len
+        )
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`FRead`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{FRead, RIndex};
+///
+/// let fread = FRead::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8));
+/// assert_eq!("FREAD 01 02 0A", format!("{:#X}", fread))
+/// ```
+impl UpperHex for FRead {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:X} {:X} {:X}",
+            Self::PREFIX,
+            self.handle,
+            self.buffer,
+            self.len
+        )
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`FRead`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{FRead, RIndex};
+///
+/// let fread = FRead::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8));
+/// assert_eq!("FREAD 01 02 0a", format!("{:#x}", fread))
+/// ```
+impl LowerHex for FRead {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:x} {:x} {:x}",
+            Self::PREFIX,
+            self.handle,
+            self.buffer,
+            self.len
+        )
+    }
+}
+
+impl From<FRead> for [u8; 4] {
+    fn from(fread: FRead) -> Self {
+        [
+            FRead::ID,
+            fread.handle().into(),
+            fread.buffer().into(),
+            fread.len().into(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_fread() -> FRead {
+        FRead::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8))
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!("FREAD $1 $2 $10", make_fread().to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        assert_eq!("FREAD 01 02 0A", format!("{:#X}", make_fread()))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        assert_eq!("FREAD 01 02 0a", format!("{:#x}", make_fread()))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let bytes: [u8; 4] = make_fread().into();
+        assert_eq!([8, 1, 2, 10], bytes);
+    }
+}
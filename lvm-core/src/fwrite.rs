@@ -0,0 +1,156 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the file write instruction, which writes memory to a file handle.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FWrite {
+    handle: RIndex,
+    buffer: RIndex,
+    len: RIndex,
+}
+
+impl FWrite {
+    pub const PREFIX: &str = "FWRITE";
+    pub const ID: u8 = 9;
+
+    /// Creates a [`FWrite`] instance.
+    pub fn make(handle: RIndex, buffer: RIndex, len: RIndex) -> Self {
+        Self {
+            handle,
+            buffer,
+            len,
+        }
+    }
+
+    /// Returns the register holding the file handle.
+    pub const fn handle(&self) -> RIndex {
+        self.handle
+    }
+
+    /// Returns the register holding the address of the buffer.
+    pub const fn buffer(&self) -> RIndex {
+        self.buffer
+    }
+
+    /// Returns the register holding the number of bytes to write, then the number of bytes written.
+    pub const fn len(&self) -> RIndex {
+        self.len
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`FWrite`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{FWrite, RIndex};
+///
+/// let fwrite = FWrite::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8));
+/// assert_eq!("FWRITE $1 $2 $10", fwrite.to_string())
+/// ```
+impl Display for FWrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            Self::PREFIX,
+            self.handle,
+            self.buffer,
+            self.len
+        )
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`FWrite`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{FWrite, RIndex};
+///
+/// let fwrite = FWrite::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8));
+/// assert_eq!("FWRITE 01 02 0A", format!("{:#X}", fwrite))
+/// ```
+impl UpperHex for FWrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:X} {:X} {:X}",
+            Self::PREFIX,
+            self.handle,
+            self.buffer,
+            self.len
+        )
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`FWrite`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{FWrite, RIndex};
+///
+/// let fwrite = FWrite::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8));
+/// assert_eq!("FWRITE 01 02 0a", format!("{:#x}", fwrite))
+/// ```
+impl LowerHex for FWrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:x} {:x} {:x}",
+            Self::PREFIX,
+            self.handle,
+            self.buffer,
+            self.len
+        )
+    }
+}
+
+impl From<FWrite> for [u8; 4] {
+    fn from(fwrite: FWrite) -> Self {
+        [
+            FWrite::ID,
+            fwrite.handle().into(),
+            fwrite.buffer().into(),
+            fwrite.len().into(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_fwrite() -> FWrite {
+        FWrite::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8))
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!("FWRITE $1 $2 $10", make_fwrite().to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        assert_eq!("FWRITE 01 02 0A", format!("{:#X}", make_fwrite()))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        assert_eq!("FWRITE 01 02 0a", format!("{:#x}", make_fwrite()))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let bytes: [u8; 4] = make_fwrite().into();
+        assert_eq!([9, 1, 2, 10], bytes);
+    }
+}
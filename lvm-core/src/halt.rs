@@ -0,0 +1,70 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+/// Structure that represents the halt instruction, which stops the program.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Halt;
+
+impl Halt {
+    pub const PREFIX: &str = "HALT";
+    pub const ID: u8 = 14;
+
+    /// Creates a [`Halt`] instance.
+    pub fn make() -> Self {
+        Self
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Halt`] implements `Display`.
+///
+/// ```
+/// use lvm_core::Halt;
+///
+/// assert_eq!("HALT", Halt::make().to_string())
+/// ```
+impl Display for Halt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Self::PREFIX)
+    }
+}
+
+/// Used for a hex representation
+impl UpperHex for Halt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Self::PREFIX)
+    }
+}
+
+/// Used for a hex representation
+impl LowerHex for Halt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Self::PREFIX)
+    }
+}
+
+impl From<Halt> for [u8; 4] {
+    fn from(_: Halt) -> Self {
+        [Halt::ID, 0, 0, 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string() {
+        assert_eq!("HALT", Halt::make().to_string());
+        assert_eq!("HALT", format!("{:#X}", Halt::make()));
+        assert_eq!("HALT", format!("{:#x}", Halt::make()));
+    }
+
+    #[test]
+    fn to_bytes() {
+        let bytes: [u8; 4] = Halt::make().into();
+        assert_eq!([14, 0, 0, 0], bytes);
+    }
+}
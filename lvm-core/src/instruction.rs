@@ -1,11 +1,99 @@
 use std::fmt::{Debug, Display, LowerHex, UpperHex};
 
-use crate::{Add, Load};
+use crate::{
+    Add, Close, Div, FRead, FWrite, Halt, Iret, Ivec, Load, Open, Prtc, Prti, RIndex, Read,
+    RecvMsg, SendMsg, Spawn, Syscall,
+};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Instruction {
     LoadI(Load),
     AddI(Add),
+    SyscallI(Syscall),
+    PrtiI(Prti),
+    PrtcI(Prtc),
+    ReadI(Read),
+    OpenI(Open),
+    FReadI(FRead),
+    FWriteI(FWrite),
+    CloseI(Close),
+    DivI(Div),
+    IvecI(Ivec),
+    IretI(Iret),
+    HaltI(Halt),
+    SpawnI(Spawn),
+    SendI(SendMsg),
+    RecvI(RecvMsg),
+}
+
+impl Instruction {
+    /// Returns the registers whose values the instruction reads.
+    /// The registers a syscall handler uses are not known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Add, Instruction, RIndex};
+    ///
+    /// let r = RIndex::make;
+    /// let add = Instruction::AddI(Add::make(r(1), r(2), r(3)));
+    /// assert_eq!(vec![r(1), r(2)], add.reads());
+    /// ```
+    pub fn reads(&self) -> Vec<RIndex> {
+        match self {
+            Self::AddI(add) => vec![add.index1(), add.index2()],
+            Self::PrtiI(prti) => vec![prti.index()],
+            Self::PrtcI(prtc) => vec![prtc.index()],
+            Self::OpenI(open) => vec![open.path(), open.mode()],
+            Self::FReadI(fread) => vec![fread.handle(), fread.buffer(), fread.len()],
+            Self::FWriteI(fwrite) => vec![fwrite.handle(), fwrite.buffer(), fwrite.len()],
+            Self::CloseI(close) => vec![close.index()],
+            Self::DivI(div) => vec![div.index1(), div.index2()],
+            Self::SpawnI(spawn) => vec![spawn.entry()],
+            Self::SendI(send) => vec![send.target(), send.value()],
+            Self::LoadI(_)
+            | Self::SyscallI(_)
+            | Self::ReadI(_)
+            | Self::IvecI(_)
+            | Self::IretI(_)
+            | Self::HaltI(_)
+            | Self::RecvI(_) => vec![],
+        }
+    }
+
+    /// Returns the registers the instruction writes.
+    /// The registers a syscall handler uses are not known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Add, Instruction, RIndex};
+    ///
+    /// let r = RIndex::make;
+    /// let add = Instruction::AddI(Add::make(r(1), r(2), r(3)));
+    /// assert_eq!(vec![r(3)], add.writes());
+    /// ```
+    pub fn writes(&self) -> Vec<RIndex> {
+        match self {
+            Self::LoadI(load) => vec![load.index()],
+            Self::AddI(add) => vec![add.index3()],
+            Self::ReadI(read) => vec![read.index()],
+            Self::OpenI(open) => vec![open.handle()],
+            Self::FReadI(fread) => vec![fread.len()],
+            Self::FWriteI(fwrite) => vec![fwrite.len()],
+            Self::DivI(div) => vec![div.index3()],
+            Self::SpawnI(spawn) => vec![spawn.pid()],
+            Self::RecvI(recv) => vec![recv.sender(), recv.value()],
+            Self::SyscallI(_)
+            | Self::PrtiI(_)
+            | Self::PrtcI(_)
+            | Self::CloseI(_)
+            | Self::IvecI(_)
+            | Self::IretI(_)
+            | Self::HaltI(_)
+            | Self::SendI(_) => vec![],
+        }
+    }
 }
 
 impl Display for Instruction {
@@ -13,6 +101,21 @@ impl Display for Instruction {
         match self {
             Self::LoadI(load) => write!(f, "{}", load),
             Self::AddI(add) => write!(f, "{}", add),
+            Self::SyscallI(syscall) => write!(f, "{}", syscall),
+            Self::PrtiI(prti) => write!(f, "{}", prti),
+            Self::PrtcI(prtc) => write!(f, "{}", prtc),
+            Self::ReadI(read) => write!(f, "{}", read),
+            Self::OpenI(open) => write!(f, "{}", open),
+            Self::FReadI(fread) => write!(f, "{}", fread),
+            Self::FWriteI(fwrite) => write!(f, "{}", fwrite),
+            Self::CloseI(close) => write!(f, "{}", close),
+            Self::DivI(div) => write!(f, "{}", div),
+            Self::IvecI(ivec) => write!(f, "{}", ivec),
+            Self::IretI(iret) => write!(f, "{}", iret),
+            Self::HaltI(halt) => write!(f, "{}", halt),
+            Self::SpawnI(spawn) => write!(f, "{}", spawn),
+            Self::SendI(send) => write!(f, "{}", send),
+            Self::RecvI(recv) => write!(f, "{}", recv),
         }
     }
 }
@@ -37,6 +140,21 @@ impl UpperHex for Instruction {
         match self {
             Self::LoadI(load) => write!(f, "{:X}", load),
             Self::AddI(add) => write!(f, "{:X}", add),
+            Self::SyscallI(syscall) => write!(f, "{:X}", syscall),
+            Self::PrtiI(prti) => write!(f, "{:X}", prti),
+            Self::PrtcI(prtc) => write!(f, "{:X}", prtc),
+            Self::ReadI(read) => write!(f, "{:X}", read),
+            Self::OpenI(open) => write!(f, "{:X}", open),
+            Self::FReadI(fread) => write!(f, "{:X}", fread),
+            Self::FWriteI(fwrite) => write!(f, "{:X}", fwrite),
+            Self::CloseI(close) => write!(f, "{:X}", close),
+            Self::DivI(div) => write!(f, "{:X}", div),
+            Self::IvecI(ivec) => write!(f, "{:X}", ivec),
+            Self::IretI(iret) => write!(f, "{:X}", iret),
+            Self::HaltI(halt) => write!(f, "{:X}", halt),
+            Self::SpawnI(spawn) => write!(f, "{:X}", spawn),
+            Self::SendI(send) => write!(f, "{:X}", send),
+            Self::RecvI(recv) => write!(f, "{:X}", recv),
         }
     }
 }
@@ -61,6 +179,21 @@ impl LowerHex for Instruction {
         match self {
             Self::LoadI(load) => write!(f, "{:x}", load),
             Self::AddI(add) => write!(f, "{:x}", add),
+            Self::SyscallI(syscall) => write!(f, "{:x}", syscall),
+            Self::PrtiI(prti) => write!(f, "{:x}", prti),
+            Self::PrtcI(prtc) => write!(f, "{:x}", prtc),
+            Self::ReadI(read) => write!(f, "{:x}", read),
+            Self::OpenI(open) => write!(f, "{:x}", open),
+            Self::FReadI(fread) => write!(f, "{:x}", fread),
+            Self::FWriteI(fwrite) => write!(f, "{:x}", fwrite),
+            Self::CloseI(close) => write!(f, "{:x}", close),
+            Self::DivI(div) => write!(f, "{:x}", div),
+            Self::IvecI(ivec) => write!(f, "{:x}", ivec),
+            Self::IretI(iret) => write!(f, "{:x}", iret),
+            Self::HaltI(halt) => write!(f, "{:x}", halt),
+            Self::SpawnI(spawn) => write!(f, "{:x}", spawn),
+            Self::SendI(send) => write!(f, "{:x}", send),
+            Self::RecvI(recv) => write!(f, "{:x}", recv),
         }
     }
 }
@@ -70,13 +203,28 @@ impl From<Instruction> for [u8; 4] {
         match instruction {
             Instruction::LoadI(load) => load.into(),
             Instruction::AddI(add) => add.into(),
+            Instruction::SyscallI(syscall) => syscall.into(),
+            Instruction::PrtiI(prti) => prti.into(),
+            Instruction::PrtcI(prtc) => prtc.into(),
+            Instruction::ReadI(read) => read.into(),
+            Instruction::OpenI(open) => open.into(),
+            Instruction::FReadI(fread) => fread.into(),
+            Instruction::FWriteI(fwrite) => fwrite.into(),
+            Instruction::CloseI(close) => close.into(),
+            Instruction::DivI(div) => div.into(),
+            Instruction::IvecI(ivec) => ivec.into(),
+            Instruction::IretI(iret) => iret.into(),
+            Instruction::HaltI(halt) => halt.into(),
+            Instruction::SpawnI(spawn) => spawn.into(),
+            Instruction::SendI(send) => send.into(),
+            Instruction::RecvI(recv) => recv.into(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Operand16, RIndex};
+    use crate::Operand16;
 
     use super::*;
 
@@ -93,7 +241,11 @@ mod tests {
         let rindx3 = RIndex::make(30u8);
         let add = Add::make(rindx1, rindx2, rindx3);
         let instruction = Instruction::AddI(add);
-        assert_eq!("ADD $10 $20 $30", instruction.to_string())
+        assert_eq!("ADD $10 $20 $30", instruction.to_string());
+
+        let syscall = Syscall::make(Operand16::make(7u16));
+        let instruction = Instruction::SyscallI(syscall);
+        assert_eq!("SYSCALL #7", instruction.to_string())
     }
 
     #[test]
@@ -127,4 +279,19 @@ mod tests {
         let instruction = Instruction::AddI(add);
         assert_eq!("ADD 0a 14 1e", format!("{:#x}", instruction))
     }
+
+    #[test]
+    fn registers() {
+        let r = RIndex::make;
+        let fread = Instruction::FReadI(FRead::make(r(1), r(2), r(3)));
+        assert_eq!(vec![r(1), r(2), r(3)], fread.reads());
+        assert_eq!(vec![r(3)], fread.writes());
+
+        let recv = Instruction::RecvI(RecvMsg::make(r(4), r(5)));
+        assert!(recv.reads().is_empty());
+        assert_eq!(vec![r(4), r(5)], recv.writes());
+
+        let halt = Instruction::HaltI(Halt::make());
+        assert!(halt.reads().is_empty() && halt.writes().is_empty());
+    }
 }
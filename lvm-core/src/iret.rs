@@ -0,0 +1,70 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+/// Structure that represents the return from interrupt instruction, which resumes the interrupted code.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Iret;
+
+impl Iret {
+    pub const PREFIX: &str = "IRET";
+    pub const ID: u8 = 13;
+
+    /// Creates a [`Iret`] instance.
+    pub fn make() -> Self {
+        Self
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Iret`] implements `Display`.
+///
+/// ```
+/// use lvm_core::Iret;
+///
+/// assert_eq!("IRET", Iret::make().to_string())
+/// ```
+impl Display for Iret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Self::PREFIX)
+    }
+}
+
+/// Used for a hex representation
+impl UpperHex for Iret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Self::PREFIX)
+    }
+}
+
+/// Used for a hex representation
+impl LowerHex for Iret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Self::PREFIX)
+    }
+}
+
+impl From<Iret> for [u8; 4] {
+    fn from(_: Iret) -> Self {
+        [Iret::ID, 0, 0, 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string() {
+        assert_eq!("IRET", Iret::make().to_string());
+        assert_eq!("IRET", format!("{:#X}", Iret::make()));
+        assert_eq!("IRET", format!("{:#x}", Iret::make()));
+    }
+
+    #[test]
+    fn to_bytes() {
+        let bytes: [u8; 4] = Iret::make().into();
+        assert_eq!([13, 0, 0, 0], bytes);
+    }
+}
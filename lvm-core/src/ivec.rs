@@ -0,0 +1,140 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::{Operand16, Operand8};
+
+/// Structure that represents the interrupt vector instruction, which installs
+/// the handler at an instruction address for an interrupt vector.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Ivec {
+    vector: Operand8,
+    address: Operand16,
+}
+
+impl Ivec {
+    pub const PREFIX: &str = "IVEC";
+    pub const ID: u8 = 12;
+
+    /// Creates a [`Ivec`] instance.
+    pub fn make(vector: Operand8, address: Operand16) -> Self {
+        Self { vector, address }
+    }
+
+    /// Returns the interrupt vector.
+    pub const fn vector(&self) -> Operand8 {
+        self.vector
+    }
+
+    /// Returns the instruction address of the handler.
+    pub const fn address(&self) -> Operand16 {
+        self.address
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Ivec`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{Ivec, Operand16, Operand8};
+///
+/// let vector = Operand8::make(10u8);
+/// let address = Operand16::make(500u16);
+/// let ivec = Ivec::make(vector, address);
+/// assert_eq!("IVEC #10 #500", ivec.to_string())
+/// ```
+impl Display for Ivec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", Self::PREFIX, self.vector, self.address)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Ivec`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{Ivec, Operand16, Operand8};
+///
+/// let vector = Operand8::make(10u8);
+/// let address = Operand16::make(500u16);
+/// let ivec = Ivec::make(vector, address);
+/// assert_eq!("IVEC 0A 01F4", format!("{:#X}", ivec))
+/// ```
+impl UpperHex for Ivec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:X} {:X}", Self::PREFIX, self.vector, self.address)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Ivec`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{Ivec, Operand16, Operand8};
+///
+/// let vector = Operand8::make(10u8);
+/// let address = Operand16::make(500u16);
+/// let ivec = Ivec::make(vector, address);
+/// assert_eq!("IVEC 0a 01f4", format!("{:#x}", ivec))
+/// ```
+impl LowerHex for Ivec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:x} {:x}", Self::PREFIX, self.vector, self.address)
+    }
+}
+
+impl From<Ivec> for [u8; 4] {
+    fn from(ivec: Ivec) -> Self {
+        let address: [u8; 2] = ivec.address().into();
+        [Ivec::ID, ivec.vector().into(), address[0], address[1]]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string() {
+        let vector = Operand8::make(10u8);
+        let address = Operand16::make(500u16);
+        let ivec = Ivec::make(vector, address);
+        assert_eq!("IVEC #10 #500", ivec.to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        let vector = Operand8::make(10u8);
+        let address = Operand16::make(500u16);
+        let ivec = Ivec::make(vector, address);
+        assert_eq!("IVEC 0A 01F4", format!("{:#X}", ivec))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        let vector = Operand8::make(10u8);
+        let address = Operand16::make(500u16);
+        let ivec = Ivec::make(vector, address);
+        assert_eq!("IVEC 0a 01f4", format!("{:#x}", ivec))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let vector = Operand8::make(10u8);
+        let address = Operand16::make(500u16);
+        let ivec = Ivec::make(vector, address);
+        let bytes: [u8; 4] = ivec.into();
+
+        assert_eq!(12, bytes[0]);
+        assert_eq!(10, bytes[1]);
+        assert_eq!(1, bytes[2]);
+        assert_eq!(0xF4u8, bytes[3]);
+    }
+}
@@ -1,15 +1,45 @@
 mod add;
+mod close;
+mod div;
+mod fread;
+mod fwrite;
+mod halt;
 mod instruction;
+mod iret;
+mod ivec;
 mod load;
+mod open;
 mod operand16;
 mod operand8;
 mod program;
+mod prtc;
+mod prti;
+mod read;
+mod recv;
 mod rindex;
+mod send;
+mod spawn;
+mod syscall;
 
 pub use add::*;
+pub use close::*;
+pub use div::*;
+pub use fread::*;
+pub use fwrite::*;
+pub use halt::*;
 pub use instruction::*;
+pub use iret::*;
+pub use ivec::*;
 pub use load::*;
+pub use open::*;
 pub use operand16::*;
 pub use operand8::*;
 pub use program::*;
+pub use prtc::*;
+pub use prti::*;
+pub use read::*;
+pub use recv::*;
 pub use rindex::*;
+pub use send::*;
+pub use spawn::*;
+pub use syscall::*;
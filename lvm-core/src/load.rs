@@ -4,7 +4,7 @@ use crate::{Operand16, RIndex};
 
 /// Structure that represents the load instruction.
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Load {
     rindx: RIndex,
     oprnd: Operand16,
@@ -0,0 +1,152 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the open instruction, which opens the file whose path is at the address held by the first register, in the mode held by the second register, and stores the handle in the third register.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Open {
+    path: RIndex,
+    mode: RIndex,
+    handle: RIndex,
+}
+
+impl Open {
+    pub const PREFIX: &str = "OPEN";
+    pub const ID: u8 = 7;
+
+    /// Creates a [`Open`] instance.
+    pub fn make(path: RIndex, mode: RIndex, handle: RIndex) -> Self {
+        Self { path, mode, handle }
+    }
+
+    /// Returns the register holding the address of the path.
+    pub const fn path(&self) -> RIndex {
+        self.path
+    }
+
+    /// Returns the register holding the open mode.
+    pub const fn mode(&self) -> RIndex {
+        self.mode
+    }
+
+    /// Returns the register receiving the handle.
+    pub const fn handle(&self) -> RIndex {
+        self.handle
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Open`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{Open, RIndex};
+///
+/// let open = Open::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8));
+/// assert_eq!("OPEN $1 $2 $10", open.to_string())
+/// ```
+impl Display for Open {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            Self::PREFIX,
+            self.path,
+            self.mode,
+            self.handle
+        )
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Open`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{Open, RIndex};
+///
+/// let open = Open::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8));
+/// assert_eq!("OPEN 01 02 0A", format!("{:#X}", open))
+/// ```
+impl UpperHex for Open {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:X} {:X} {:X}",
+            Self::PREFIX,
+            self.path,
+            self.mode,
+            self.handle
+        )
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Open`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{Open, RIndex};
+///
+/// let open = Open::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8));
+/// assert_eq!("OPEN 01 02 0a", format!("{:#x}", open))
+/// ```
+impl LowerHex for Open {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:x} {:x} {:x}",
+            Self::PREFIX,
+            self.path,
+            self.mode,
+            self.handle
+        )
+    }
+}
+
+impl From<Open> for [u8; 4] {
+    fn from(open: Open) -> Self {
+        [
+            Open::ID,
+            open.path().into(),
+            open.mode().into(),
+            open.handle().into(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_open() -> Open {
+        Open::make(RIndex::make(1u8), RIndex::make(2u8), RIndex::make(10u8))
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!("OPEN $1 $2 $10", make_open().to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        assert_eq!("OPEN 01 02 0A", format!("{:#X}", make_open()))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        assert_eq!("OPEN 01 02 0a", format!("{:#x}", make_open()))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let bytes: [u8; 4] = make_open().into();
+        assert_eq!([7, 1, 2, 10], bytes);
+    }
+}
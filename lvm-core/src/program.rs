@@ -2,13 +2,44 @@ use std::fmt::{Debug, Display, LowerHex, UpperHex};
 
 use crate::Instruction;
 
-#[derive(Debug)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Program(Vec<Instruction>);
 
 impl Program {
     pub fn make(xs: Vec<Instruction>) -> Self {
         Self(xs)
     }
+
+    /// Returns the number of instructions.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the program has no instructions.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the instruction at the given position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Instruction, Load, Operand16, Program, RIndex};
+    ///
+    /// let load = Load::make(RIndex::make(1u8), Operand16::make(10u16));
+    /// let program = Program::make(vec![Instruction::LoadI(load)]);
+    /// assert_eq!(Some(&Instruction::LoadI(load)), program.get(0));
+    /// assert_eq!(None, program.get(1));
+    /// ```
+    pub fn get(&self, pos: usize) -> Option<&Instruction> {
+        self.0.get(pos)
+    }
+
+    /// Returns an iterator over the instructions.
+    pub fn iter(&self) -> std::slice::Iter<'_, Instruction> {
+        self.0.iter()
+    }
 }
 
 impl Display for Program {
@@ -139,6 +170,15 @@ mod tests {
         assert_eq!(e, s)
     }
 
+    #[test]
+    fn get() {
+        let program = Program::make(vec![create_load(), create_add()]);
+
+        assert_eq!(2, program.len());
+        assert_eq!(Some(&create_add()), program.get(1));
+        assert!(program.get(2).is_none());
+    }
+
     #[test]
     fn to_bytes() {
         let i1 = create_load();
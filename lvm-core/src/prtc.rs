@@ -0,0 +1,115 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the print char instruction, which prints the character of a register.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Prtc {
+    rindx: RIndex,
+}
+
+impl Prtc {
+    pub const PREFIX: &str = "PRTC";
+    pub const ID: u8 = 5;
+
+    /// Creates a [`Prtc`] instance.
+    pub fn make(rindx: RIndex) -> Self {
+        Self { rindx }
+    }
+
+    /// Returns the register index.
+    pub const fn index(&self) -> RIndex {
+        self.rindx
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Prtc`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{Prtc, RIndex};
+///
+/// let prtc = Prtc::make(RIndex::make(10u8));
+/// assert_eq!("PRTC $10", prtc.to_string())
+/// ```
+impl Display for Prtc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", Self::PREFIX, self.rindx)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Prtc`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{Prtc, RIndex};
+///
+/// let prtc = Prtc::make(RIndex::make(10u8));
+/// assert_eq!("PRTC 0A", format!("{:#X}", prtc))
+/// ```
+impl UpperHex for Prtc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:X}", Self::PREFIX, self.rindx)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Prtc`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{Prtc, RIndex};
+///
+/// let prtc = Prtc::make(RIndex::make(10u8));
+/// assert_eq!("PRTC 0a", format!("{:#x}", prtc))
+/// ```
+impl LowerHex for Prtc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:x}", Self::PREFIX, self.rindx)
+    }
+}
+
+impl From<Prtc> for [u8; 4] {
+    fn from(prtc: Prtc) -> Self {
+        [Prtc::ID, prtc.index().into(), 0, 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string() {
+        let prtc = Prtc::make(RIndex::make(10u8));
+        assert_eq!("PRTC $10", prtc.to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        let prtc = Prtc::make(RIndex::make(10u8));
+        assert_eq!("PRTC 0A", format!("{:#X}", prtc))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        let prtc = Prtc::make(RIndex::make(10u8));
+        assert_eq!("PRTC 0a", format!("{:#x}", prtc))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let prtc = Prtc::make(RIndex::make(10u8));
+        let bytes: [u8; 4] = prtc.into();
+
+        assert_eq!([5, 10, 0, 0], bytes);
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the print integer instruction, which prints the value of a register.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Prti {
+    rindx: RIndex,
+}
+
+impl Prti {
+    pub const PREFIX: &str = "PRTI";
+    pub const ID: u8 = 4;
+
+    /// Creates a [`Prti`] instance.
+    pub fn make(rindx: RIndex) -> Self {
+        Self { rindx }
+    }
+
+    /// Returns the register index.
+    pub const fn index(&self) -> RIndex {
+        self.rindx
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Prti`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{Prti, RIndex};
+///
+/// let prti = Prti::make(RIndex::make(10u8));
+/// assert_eq!("PRTI $10", prti.to_string())
+/// ```
+impl Display for Prti {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", Self::PREFIX, self.rindx)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Prti`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{Prti, RIndex};
+///
+/// let prti = Prti::make(RIndex::make(10u8));
+/// assert_eq!("PRTI 0A", format!("{:#X}", prti))
+/// ```
+impl UpperHex for Prti {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:X}", Self::PREFIX, self.rindx)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Prti`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{Prti, RIndex};
+///
+/// let prti = Prti::make(RIndex::make(10u8));
+/// assert_eq!("PRTI 0a", format!("{:#x}", prti))
+/// ```
+impl LowerHex for Prti {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:x}", Self::PREFIX, self.rindx)
+    }
+}
+
+impl From<Prti> for [u8; 4] {
+    fn from(prti: Prti) -> Self {
+        [Prti::ID, prti.index().into(), 0, 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string() {
+        let prti = Prti::make(RIndex::make(10u8));
+        assert_eq!("PRTI $10", prti.to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        let prti = Prti::make(RIndex::make(10u8));
+        assert_eq!("PRTI 0A", format!("{:#X}", prti))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        let prti = Prti::make(RIndex::make(10u8));
+        assert_eq!("PRTI 0a", format!("{:#x}", prti))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let prti = Prti::make(RIndex::make(10u8));
+        let bytes: [u8; 4] = prti.into();
+
+        assert_eq!([4, 10, 0, 0], bytes);
+    }
+}
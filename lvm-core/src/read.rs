@@ -0,0 +1,115 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the read instruction, which reads an integer into a register.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Read {
+    rindx: RIndex,
+}
+
+impl Read {
+    pub const PREFIX: &str = "READ";
+    pub const ID: u8 = 6;
+
+    /// Creates a [`Read`] instance.
+    pub fn make(rindx: RIndex) -> Self {
+        Self { rindx }
+    }
+
+    /// Returns the register index.
+    pub const fn index(&self) -> RIndex {
+        self.rindx
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Read`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{Read, RIndex};
+///
+/// let read = Read::make(RIndex::make(10u8));
+/// assert_eq!("READ $10", read.to_string())
+/// ```
+impl Display for Read {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", Self::PREFIX, self.rindx)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Read`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{Read, RIndex};
+///
+/// let read = Read::make(RIndex::make(10u8));
+/// assert_eq!("READ 0A", format!("{:#X}", read))
+/// ```
+impl UpperHex for Read {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:X}", Self::PREFIX, self.rindx)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Read`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{Read, RIndex};
+///
+/// let read = Read::make(RIndex::make(10u8));
+/// assert_eq!("READ 0a", format!("{:#x}", read))
+/// ```
+impl LowerHex for Read {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:x}", Self::PREFIX, self.rindx)
+    }
+}
+
+impl From<Read> for [u8; 4] {
+    fn from(read: Read) -> Self {
+        [Read::ID, read.index().into(), 0, 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string() {
+        let read = Read::make(RIndex::make(10u8));
+        assert_eq!("READ $10", read.to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        let read = Read::make(RIndex::make(10u8));
+        assert_eq!("READ 0A", format!("{:#X}", read))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        let read = Read::make(RIndex::make(10u8));
+        assert_eq!("READ 0a", format!("{:#x}", read))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let read = Read::make(RIndex::make(10u8));
+        let bytes: [u8; 4] = read.into();
+
+        assert_eq!([6, 10, 0, 0], bytes);
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the receive instruction, which takes the oldest message
+/// of the mailbox, storing the id of its sender in the first register and its value in the second one.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RecvMsg {
+    sender: RIndex,
+    value: RIndex,
+}
+
+impl RecvMsg {
+    pub const PREFIX: &str = "RECV";
+    pub const ID: u8 = 17;
+
+    /// Creates a [`RecvMsg`] instance.
+    pub fn make(sender: RIndex, value: RIndex) -> Self {
+        Self { sender, value }
+    }
+
+    /// Returns the register receiving the id of the sending process.
+    pub const fn sender(&self) -> RIndex {
+        self.sender
+    }
+
+    /// Returns the register receiving the value.
+    pub const fn value(&self) -> RIndex {
+        self.value
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`RecvMsg`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{RecvMsg, RIndex};
+///
+/// let recv = RecvMsg::make(RIndex::make(1u8), RIndex::make(10u8));
+/// assert_eq!("RECV $1 $10", recv.to_string())
+/// ```
+impl Display for RecvMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", Self::PREFIX, self.sender, self.value)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`RecvMsg`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{RecvMsg, RIndex};
+///
+/// let recv = RecvMsg::make(RIndex::make(1u8), RIndex::make(10u8));
+/// assert_eq!("RECV 01 0A", format!("{:#X}", recv))
+/// ```
+impl UpperHex for RecvMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:X} {:X}", Self::PREFIX, self.sender, self.value)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`RecvMsg`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{RecvMsg, RIndex};
+///
+/// let recv = RecvMsg::make(RIndex::make(1u8), RIndex::make(10u8));
+/// assert_eq!("RECV 01 0a", format!("{:#x}", recv))
+/// ```
+impl LowerHex for RecvMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:x} {:x}", Self::PREFIX, self.sender, self.value)
+    }
+}
+
+impl From<RecvMsg> for [u8; 4] {
+    fn from(recv: RecvMsg) -> Self {
+        [RecvMsg::ID, recv.sender().into(), recv.value().into(), 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_recv() -> RecvMsg {
+        RecvMsg::make(RIndex::make(1u8), RIndex::make(10u8))
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!("RECV $1 $10", make_recv().to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        assert_eq!("RECV 01 0A", format!("{:#X}", make_recv()))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        assert_eq!("RECV 01 0a", format!("{:#x}", make_recv()))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let bytes: [u8; 4] = make_recv().into();
+        assert_eq!([17, 1, 10, 0], bytes);
+    }
+}
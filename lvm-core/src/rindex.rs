@@ -4,7 +4,7 @@ use std::{
 };
 
 /// The register index.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct RIndex(u8);
 
 impl RIndex {
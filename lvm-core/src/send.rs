@@ -0,0 +1,121 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the send instruction, which sends the value of the
+/// second register to the mailbox of the process whose id is held by the first one.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SendMsg {
+    target: RIndex,
+    value: RIndex,
+}
+
+impl SendMsg {
+    pub const PREFIX: &str = "SEND";
+    pub const ID: u8 = 16;
+
+    /// Creates a [`SendMsg`] instance.
+    pub fn make(target: RIndex, value: RIndex) -> Self {
+        Self { target, value }
+    }
+
+    /// Returns the register holding the id of the receiving process.
+    pub const fn target(&self) -> RIndex {
+        self.target
+    }
+
+    /// Returns the register holding the sent value.
+    pub const fn value(&self) -> RIndex {
+        self.value
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`SendMsg`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{SendMsg, RIndex};
+///
+/// let send = SendMsg::make(RIndex::make(1u8), RIndex::make(10u8));
+/// assert_eq!("SEND $1 $10", send.to_string())
+/// ```
+impl Display for SendMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", Self::PREFIX, self.target, self.value)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`SendMsg`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{SendMsg, RIndex};
+///
+/// let send = SendMsg::make(RIndex::make(1u8), RIndex::make(10u8));
+/// assert_eq!("SEND 01 0A", format!("{:#X}", send))
+/// ```
+impl UpperHex for SendMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:X} {:X}", Self::PREFIX, self.target, self.value)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`SendMsg`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{SendMsg, RIndex};
+///
+/// let send = SendMsg::make(RIndex::make(1u8), RIndex::make(10u8));
+/// assert_eq!("SEND 01 0a", format!("{:#x}", send))
+/// ```
+impl LowerHex for SendMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:x} {:x}", Self::PREFIX, self.target, self.value)
+    }
+}
+
+impl From<SendMsg> for [u8; 4] {
+    fn from(send: SendMsg) -> Self {
+        [SendMsg::ID, send.target().into(), send.value().into(), 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_send() -> SendMsg {
+        SendMsg::make(RIndex::make(1u8), RIndex::make(10u8))
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!("SEND $1 $10", make_send().to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        assert_eq!("SEND 01 0A", format!("{:#X}", make_send()))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        assert_eq!("SEND 01 0a", format!("{:#x}", make_send()))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let bytes: [u8; 4] = make_send().into();
+        assert_eq!([16, 1, 10, 0], bytes);
+    }
+}
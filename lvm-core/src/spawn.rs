@@ -0,0 +1,121 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::RIndex;
+
+/// Structure that represents the spawn instruction, which starts a process at the
+/// instruction address held by the first register and stores its id in the second one.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Spawn {
+    entry: RIndex,
+    pid: RIndex,
+}
+
+impl Spawn {
+    pub const PREFIX: &str = "SPAWN";
+    pub const ID: u8 = 15;
+
+    /// Creates a [`Spawn`] instance.
+    pub fn make(entry: RIndex, pid: RIndex) -> Self {
+        Self { entry, pid }
+    }
+
+    /// Returns the register holding the instruction address the process starts at.
+    pub const fn entry(&self) -> RIndex {
+        self.entry
+    }
+
+    /// Returns the register receiving the process id.
+    pub const fn pid(&self) -> RIndex {
+        self.pid
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Spawn`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{Spawn, RIndex};
+///
+/// let spawn = Spawn::make(RIndex::make(1u8), RIndex::make(10u8));
+/// assert_eq!("SPAWN $1 $10", spawn.to_string())
+/// ```
+impl Display for Spawn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", Self::PREFIX, self.entry, self.pid)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Spawn`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{Spawn, RIndex};
+///
+/// let spawn = Spawn::make(RIndex::make(1u8), RIndex::make(10u8));
+/// assert_eq!("SPAWN 01 0A", format!("{:#X}", spawn))
+/// ```
+impl UpperHex for Spawn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:X} {:X}", Self::PREFIX, self.entry, self.pid)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Spawn`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{Spawn, RIndex};
+///
+/// let spawn = Spawn::make(RIndex::make(1u8), RIndex::make(10u8));
+/// assert_eq!("SPAWN 01 0a", format!("{:#x}", spawn))
+/// ```
+impl LowerHex for Spawn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:x} {:x}", Self::PREFIX, self.entry, self.pid)
+    }
+}
+
+impl From<Spawn> for [u8; 4] {
+    fn from(spawn: Spawn) -> Self {
+        [Spawn::ID, spawn.entry().into(), spawn.pid().into(), 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_spawn() -> Spawn {
+        Spawn::make(RIndex::make(1u8), RIndex::make(10u8))
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!("SPAWN $1 $10", make_spawn().to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        assert_eq!("SPAWN 01 0A", format!("{:#X}", make_spawn()))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        assert_eq!("SPAWN 01 0a", format!("{:#x}", make_spawn()))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let bytes: [u8; 4] = make_spawn().into();
+        assert_eq!([15, 1, 10, 0], bytes);
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::{Debug, Display, LowerHex, UpperHex};
+
+use crate::Operand16;
+
+/// Structure that represents the syscall instruction,
+/// which calls the host handler registered for the number.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Syscall {
+    number: Operand16,
+}
+
+impl Syscall {
+    pub const PREFIX: &str = "SYSCALL";
+    pub const ID: u8 = 3;
+
+    /// Creates a [`Syscall`] instance.
+    pub fn make(number: Operand16) -> Self {
+        Self { number }
+    }
+
+    /// Returns the syscall number.
+    pub const fn number(&self) -> Operand16 {
+        self.number
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// [`Syscall`] implements `Display`.
+///
+/// ```
+/// use lvm_core::{Operand16, Syscall};
+///
+/// let syscall = Syscall::make(Operand16::make(42u16));
+/// assert_eq!("SYSCALL #42", syscall.to_string())
+/// ```
+impl Display for Syscall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", Self::PREFIX, self.number)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Syscall`] implements `UpperHex`.
+///
+/// ```
+/// use lvm_core::{Operand16, Syscall};
+///
+/// let syscall = Syscall::make(Operand16::make(42u16));
+/// assert_eq!("SYSCALL 002A", format!("{:#X}", syscall))
+/// ```
+impl UpperHex for Syscall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:X}", Self::PREFIX, self.number)
+    }
+}
+
+/// Used for a hex representation
+///
+/// # Examples
+///
+/// [`Syscall`] implements `LowerHex`.
+///
+/// ```
+/// use lvm_core::{Operand16, Syscall};
+///
+/// let syscall = Syscall::make(Operand16::make(42u16));
+/// assert_eq!("SYSCALL 002a", format!("{:#x}", syscall))
+/// ```
+impl LowerHex for Syscall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:x}", Self::PREFIX, self.number)
+    }
+}
+
+impl From<Syscall> for [u8; 4] {
+    fn from(syscall: Syscall) -> Self {
+        let number: [u8; 2] = syscall.number().into();
+        [Syscall::ID, number[0], number[1], 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string() {
+        let syscall = Syscall::make(Operand16::make(500u16));
+        assert_eq!("SYSCALL #500", syscall.to_string())
+    }
+
+    #[test]
+    fn to_upper_hex() {
+        let syscall = Syscall::make(Operand16::make(500u16));
+        assert_eq!("SYSCALL 01F4", format!("{:#X}", syscall))
+    }
+
+    #[test]
+    fn to_lower_hex() {
+        let syscall = Syscall::make(Operand16::make(500u16));
+        assert_eq!("SYSCALL 01f4", format!("{:#x}", syscall))
+    }
+
+    #[test]
+    fn to_bytes() {
+        let syscall = Syscall::make(Operand16::make(500u16));
+        let bytes: [u8; 4] = syscall.into();
+
+        assert_eq!([3, 1, 0xF4u8, 0], bytes);
+    }
+}
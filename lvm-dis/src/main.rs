@@ -0,0 +1,56 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::Context;
+use clap::Parser;
+use lvm_analysis::Cfg;
+use lvm_core::Program;
+use lvm_parser::ParseSource;
+
+/// Disassembles a binary program of the language VM.
+#[derive(Debug, Parser)]
+#[command(name = "lvm-dis", version)]
+struct Args {
+    /// The binary program file.
+    file: PathBuf,
+
+    /// Displays the operands in hex.
+    #[arg(long)]
+    hex: bool,
+
+    /// Prints the control-flow graph in the Graphviz DOT language.
+    #[arg(long)]
+    cfg: bool,
+}
+
+fn execute(args: &Args) -> anyhow::Result<()> {
+    let bytes = std::fs::read(&args.file)
+        .with_context(|| format!("cannot read {}", args.file.display()))?;
+    let program = Program::from_binary(&bytes)?;
+
+    if args.cfg {
+        let cfg = Cfg::build(&program);
+        match args.hex {
+            true => println!("{:X}", cfg.dot(&program)),
+            false => println!("{}", cfg.dot(&program)),
+        }
+    } else if !program.is_empty() {
+        match args.hex {
+            true => println!("{:X}", program),
+            false => println!("{}", program),
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match execute(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
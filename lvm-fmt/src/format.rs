@@ -0,0 +1,206 @@
+use lvm_core::Instruction;
+use lvm_parser::{Item, Listing, Location};
+
+/// The indentation of the instructions.
+const INDENT: usize = 4;
+
+/// The width of the mnemonic column, the longest mnemonic and a space.
+const MNEMONIC_WIDTH: usize = 8;
+
+/// The number base of the formatted operands.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, clap::ValueEnum)]
+pub enum Base {
+    Dec,
+    Hex,
+}
+
+/// A line of the formatted source.
+#[derive(Debug)]
+enum Line {
+    Blank,
+    Text {
+        indent: usize,
+        text: String,
+        comment: Option<String>,
+    },
+}
+
+/// Returns the canonical source of the listing, given the locations of its items.
+///
+/// The labels start their own line, the instructions are indented, one per line,
+/// with the operands aligned after the uppercase mnemonic, and the comments
+/// following code on the same line are aligned. Runs of blank lines are kept
+/// as a single one.
+pub fn format(listing: &Listing, locations: &[Location], base: Base) -> String {
+    let mut lines = vec![];
+    let mut previous: Option<usize> = None;
+
+    for (item, location) in listing.items().iter().zip(locations) {
+        let line = location.line();
+
+        if let (Item::Comment(text), Some(true)) = (item, previous.map(|p| p == line)) {
+            if let Some(Line::Text { comment, .. }) = lines.last_mut() {
+                *comment = Some(text.clone());
+                continue;
+            }
+        }
+
+        if previous.is_some_and(|p| line > p + 1) {
+            lines.push(Line::Blank);
+        }
+        previous = Some(line);
+
+        let (indent, text) = match item {
+            Item::Instruction(instruction) => (INDENT, instruction_text(instruction, base)),
+            Item::Label(name) => (0, format!("{}:", name)),
+            Item::Comment(text) => (0, format!(";{}", text)),
+        };
+        lines.push(Line::Text {
+            indent,
+            text,
+            comment: None,
+        });
+    }
+
+    indent_comments(&mut lines);
+    render(&lines)
+}
+
+/// Returns the instruction with the mnemonic padded and the operands in the base.
+fn instruction_text(instruction: &Instruction, base: Base) -> String {
+    let text = instruction.to_string();
+    let mut tokens = text.split_whitespace();
+    let mnemonic = tokens.next().unwrap_or_default();
+
+    let operands: Vec<String> = match base {
+        Base::Dec => tokens.map(str::to_string).collect(),
+        Base::Hex => {
+            // The hex representation leaves out the prefixes of the operands.
+            let hex = format!("{:X}", instruction);
+            tokens
+                .zip(hex.split_whitespace().skip(1))
+                .map(|(dec, hex)| {
+                    let prefix = dec.trim_end_matches(|c: char| c.is_ascii_digit());
+                    format!("{}{}", prefix, hex)
+                })
+                .collect()
+        }
+    };
+
+    match operands.is_empty() {
+        true => mnemonic.to_string(),
+        false => format!("{:<MNEMONIC_WIDTH$}{}", mnemonic, operands.join(" ")),
+    }
+}
+
+/// Indents the comments on their own line like the code they precede.
+fn indent_comments(lines: &mut [Line]) {
+    let mut next = 0;
+
+    for line in lines.iter_mut().rev() {
+        match line {
+            Line::Blank => next = 0,
+            Line::Text { indent, text, .. } if text.starts_with(';') => *indent = next,
+            Line::Text { indent, .. } => next = *indent,
+        }
+    }
+}
+
+fn render(lines: &[Line]) -> String {
+    let width = |line: &Line| match line {
+        Line::Text {
+            indent,
+            text,
+            comment: Some(_),
+        } => Some(indent + text.len()),
+        _ => None,
+    };
+
+    let mut output = String::new();
+    let mut column = 0;
+
+    for (index, line) in lines.iter().enumerate() {
+        match line {
+            Line::Blank => output.push('\n'),
+            Line::Text {
+                indent,
+                text,
+                comment,
+            } => {
+                output.push_str(&" ".repeat(*indent));
+                output.push_str(text);
+
+                if let Some(comment) = comment {
+                    // The trailing comments of consecutive lines share a column.
+                    if index == 0 || width(&lines[index - 1]).is_none() {
+                        column = lines[index..].iter().map_while(width).max().unwrap_or(0);
+                    }
+                    let padding = column - indent - text.len() + 1;
+                    output.push_str(&format!("{};{}", " ".repeat(padding), comment));
+                }
+                output.push('\n');
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_parser::ParseSource;
+
+    use super::*;
+
+    fn format_source(source: &str, base: Base) -> String {
+        let (listing, locations) = Listing::from_located_source(source).unwrap();
+        format(&listing, &locations, base)
+    }
+
+    #[test]
+    fn canonical() {
+        let source = "; sums two numbers
+start: load $1   #2 ; two
+ LOAD $2 #30 ;thirty
+
+
+  add $1 $2 $3 PRTI $3
+; done
+   halt";
+        let expected = "; sums two numbers
+start:
+    LOAD    $1 #2  ; two
+    LOAD    $2 #30 ;thirty
+
+    ADD     $1 $2 $3
+    PRTI    $3
+    ; done
+    HALT
+";
+        assert_eq!(expected, format_source(source, Base::Dec));
+    }
+
+    #[test]
+    fn idempotent() {
+        let source = "main: ; entry\nIVEC #8 #3 ;install\nhalt\n\n; handler\nprtc $1\n  iret";
+        let once = format_source(source, Base::Dec);
+
+        assert_eq!(once, format_source(&once, Base::Dec));
+        assert!(once.starts_with("main:             ; entry\n    IVEC    #8 #3 ;install\n"));
+    }
+
+    #[test]
+    fn hex() {
+        let source = "LOAD $10 #500\nIVEC #8 #3";
+        let formatted = format_source(source, Base::Hex);
+        assert_eq!("    LOAD    $0A #01F4\n    IVEC    #08 #0003\n", formatted);
+
+        let program = Listing::from_hex_source(&formatted).unwrap().program();
+        assert_eq!(Listing::from_source(source).unwrap().program(), program);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!("", format_source("\n  \n", Base::Dec));
+    }
+}
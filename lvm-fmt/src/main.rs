@@ -0,0 +1,78 @@
+mod format;
+
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::Context;
+use clap::Parser;
+use format::*;
+use lvm_parser::{Listing, ParseSource};
+
+/// Formats assembly programs of the language VM in the canonical form.
+#[derive(Debug, Parser)]
+#[command(name = "lvm-fmt", version)]
+struct Args {
+    /// The assembly files.
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Reports the files that are not formatted instead of writing them.
+    #[arg(long)]
+    check: bool,
+
+    /// Reads the files as assembly text with hex operands.
+    #[arg(long)]
+    hex: bool,
+
+    /// The number base of the formatted operands, the base of the files by default.
+    #[arg(short, long, value_enum)]
+    base: Option<Base>,
+}
+
+fn execute(args: &Args) -> anyhow::Result<bool> {
+    let base = match (args.base, args.hex) {
+        (Some(base), _) => base,
+        (None, true) => Base::Hex,
+        (None, false) => Base::Dec,
+    };
+    let mut formatted = true;
+
+    for path in &args.files {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+
+        let (listing, locations) = match args.hex {
+            true => Listing::from_located_hex_source(&source),
+            false => Listing::from_located_source(&source),
+        }
+        .with_context(|| format!("cannot parse {}", path.display()))?;
+
+        let output = format(&listing, &locations, base);
+        if output == source {
+            continue;
+        }
+
+        match args.check {
+            true => {
+                println!("{} is not formatted", path.display());
+                formatted = false;
+            }
+            false => std::fs::write(path, output)
+                .with_context(|| format!("cannot write {}", path.display()))?,
+        }
+    }
+
+    Ok(formatted)
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match execute(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use std::fmt::Display;
+
+use lvm_analysis::{
+    resolve_spawns, solve, Cfg, ConstantPropagation, EdgeKind, Liveness, ReachingDefinitions,
+};
+use lvm_core::{Instruction, Program, RIndex};
+use lvm_machine::{VMConfig, Verifier, ViolationKind};
+use lvm_parser::Location;
+use serde::Serialize;
+
+/// How serious a diagnostic is.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The program is rejected by the verifier.
+    Error,
+    /// The code is suspicious.
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a program, at the location of an instruction.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct Diagnostic {
+    #[serde(skip)]
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn make(
+        offset: usize,
+        location: Location,
+        severity: Severity,
+        code: &'static str,
+        message: String,
+    ) -> Self {
+        Self {
+            offset,
+            line: location.line(),
+            column: location.column(),
+            severity,
+            code,
+            message,
+        }
+    }
+
+    /// Returns the diagnostic with the source line and a caret under the column,
+    /// like the errors of the parser.
+    pub fn render(&self, file: &str, source: &str) -> String {
+        let line = source.lines().nth(self.line - 1).unwrap_or_default();
+        format!(
+            "{}: {}[{}]: {}\n  at {}, line {}, column {}:\n{}\n{}^\n",
+            file,
+            self.severity,
+            self.code,
+            self.message,
+            file,
+            self.line,
+            self.column,
+            line,
+            " ".repeat(self.column - 1)
+        )
+    }
+}
+
+/// Returns the diagnostics of the program, ordered by location.
+pub fn lint(program: &Program, locations: &[Location], config: VMConfig) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let (cfg, resolved) = resolve_spawns(program);
+    let locate = |offset: usize| {
+        locations
+            .get(offset)
+            .copied()
+            .unwrap_or(Location::make(1, 1))
+    };
+    let mut report = |offset: usize, severity, code, message: String| {
+        diagnostics.push(Diagnostic::make(
+            offset,
+            locate(offset),
+            severity,
+            code,
+            message,
+        ));
+    };
+
+    if let Err(violations) = Verifier::make(config).verify(program) {
+        for violation in violations {
+            let code = violation_code(violation.kind());
+            report(
+                violation.offset(),
+                Severity::Error,
+                code,
+                violation.kind().to_string(),
+            );
+        }
+    }
+
+    // The code reached from the entries, and from the entry without entering a handler.
+    // A process may start anywhere when a `SPAWN` target is not a constant.
+    let reached = match resolved {
+        true => reachable(&cfg, cfg.entries(), |_| true),
+        false => vec![true; cfg.blocks().len()],
+    };
+    let main = reachable(&cfg, &[], |kind| kind == EdgeKind::Fallthrough);
+
+    for (index, block) in cfg.blocks().iter().enumerate() {
+        if !reached[index] {
+            report(
+                block.start(),
+                Severity::Warning,
+                "unreachable-code",
+                "unreachable code".to_string(),
+            );
+        }
+    }
+
+    let reaching = solve(
+        &ReachingDefinitions::make(config.registers()),
+        program,
+        &cfg,
+    );
+    let liveness = solve(&Liveness::default(), program, &cfg);
+    let constants = solve(&ConstantPropagation::make(program, &cfg), program, &cfg);
+
+    for (offset, instruction) in program.iter().enumerate() {
+        let block = cfg.block_of(offset).unwrap_or_default();
+        if !reached[block] {
+            continue;
+        }
+
+        if main[block] {
+            if let Some(defs) = reaching.before(offset) {
+                for register in unique(instruction.reads()) {
+                    let mut defs = ReachingDefinitions::of(defs, register).peekable();
+                    if defs.peek().is_some() && defs.all(|d| d.offset().is_none()) {
+                        let message = format!("{} is read before it is written", register);
+                        report(offset, Severity::Warning, "unwritten-register", message);
+                    }
+                }
+            }
+        }
+
+        let pure = matches!(
+            instruction,
+            Instruction::LoadI(_) | Instruction::AddI(_) | Instruction::DivI(_)
+        );
+        if let (true, Some(live)) = (pure, liveness.after(offset)) {
+            for register in instruction.writes() {
+                if !live.contains(register) {
+                    let message = format!("the value written to {} is never read", register);
+                    report(offset, Severity::Warning, "unread-write", message);
+                }
+            }
+        }
+
+        if let (Instruction::AddI(add), Some(values)) = (instruction, constants.before(offset)) {
+            let a = values.constant(add.index1());
+            let b = values.constant(add.index2());
+            if let (Some(a), Some(b)) = (a, b) {
+                if a.checked_add(b).is_none() {
+                    let message = format!("{} + {} overflows u16", a, b);
+                    report(offset, Severity::Warning, "add-overflow", message);
+                }
+            }
+        }
+    }
+
+    diagnostics.sort_by_key(|d| (d.line, d.column, d.severity));
+    diagnostics
+}
+
+fn violation_code(kind: ViolationKind) -> &'static str {
+    match kind {
+        ViolationKind::InvalidRegister(_) => "invalid-register",
+        ViolationKind::InvalidTarget(_) => "invalid-target",
+        ViolationKind::InvalidVector(_) => "invalid-vector",
+        ViolationKind::IretOutsideHandler => "iret-outside-handler",
+        ViolationKind::MissingIret => "missing-iret",
+        ViolationKind::DepthMismatch { .. } => "depth-mismatch",
+    }
+}
+
+/// Returns the blocks reached from the first block and the other entries following the edges.
+fn reachable<F>(cfg: &Cfg, entries: &[usize], follow: F) -> Vec<bool>
+where
+    F: Fn(EdgeKind) -> bool,
+{
+    let mut reached = vec![false; cfg.blocks().len()];
+    let mut work = entries.to_vec();
+    if !reached.is_empty() {
+        work.push(0);
+    }
+
+    while let Some(block) = work.pop() {
+        if !std::mem::replace(&mut reached[block], true) {
+            let edges = cfg.successors(block).filter(|e| follow(e.kind()));
+            work.extend(edges.map(|e| e.to()));
+        }
+    }
+
+    reached
+}
+
+fn unique(mut registers: Vec<RIndex>) -> Vec<RIndex> {
+    registers.sort();
+    registers.dedup();
+    registers
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_parser::ParseSource;
+
+    use super::*;
+
+    fn codes(source: &str) -> Vec<(usize, &'static str)> {
+        let (program, locations) = Program::from_located_source(source).unwrap();
+        lint(&program, &locations, VMConfig::default())
+            .iter()
+            .map(|d| (d.line, d.code))
+            .collect()
+    }
+
+    #[test]
+    fn clean() {
+        let source = "LOAD $1 #2\nLOAD $2 #3\nADD $1 $2 $3\nPRTI $3\nHALT";
+        assert!(codes(source).is_empty());
+    }
+
+    #[test]
+    fn registers() {
+        let source = "LOAD $1 #2\nADD $1 $2 $3\nPRTI $3\nPRTI $9";
+        assert_eq!(
+            vec![(2, "unwritten-register"), (4, "invalid-register")],
+            codes(source)
+        );
+    }
+
+    #[test]
+    fn writes() {
+        let source = "LOAD $1 #2\nLOAD $1 #3\nPRTI $1\nLOAD $2 #1";
+        assert_eq!(
+            vec![(1, "unread-write"), (4, "unread-write")],
+            codes(source)
+        );
+    }
+
+    #[test]
+    fn unreachable() {
+        let source = "LOAD $1 #2\nPRTI $1\nHALT\nPRTI $1\nLOAD $1 #1";
+        assert_eq!(vec![(4, "unreachable-code")], codes(source));
+    }
+
+    #[test]
+    fn overflow() {
+        let source = "LOAD $1 #65535\nLOAD $2 #1\nADD $1 $2 $3\nADD $2 $2 $4\nPRTI $3\nPRTI $4";
+        assert_eq!(vec![(3, "add-overflow")], codes(source));
+    }
+
+    #[test]
+    fn handler_overflow() {
+        // The timer handler may clear $2 before the addition.
+        let source =
+            "LOAD $1 #65535\nIVEC #8 #6\nLOAD $2 #1\nADD $1 $2 $3\nPRTI $3\nHALT\nLOAD $2 #0\nIRET";
+        assert!(codes(source).is_empty());
+    }
+
+    #[test]
+    fn handlers() {
+        // The handler reads the registers of the code it interrupts.
+        let source = "IVEC #8 #4\nLOAD $1 #2\nPRTI $1\nHALT\nPRTI $1\nIRET";
+        assert!(codes(source).is_empty());
+    }
+
+    #[test]
+    fn spawn() {
+        // The spawned process starts at line 5, and inherits the registers.
+        let source = "LOAD $1 #4\nSPAWN $1 $2\nPRTI $2\nHALT\nLOAD $3 #7\nPRTI $3\nHALT";
+        assert!(codes(source).is_empty());
+
+        let source =
+            "LOAD $1 #4\nSPAWN $1 $2\nPRTI $2\nHALT\nLOAD $3 #65535\nADD $3 $3 $4\nPRTI $4";
+        assert_eq!(vec![(6, "add-overflow")], codes(source));
+
+        // Any code may be a process when the target is not a constant.
+        let source = "READ $1\nSPAWN $1 $2\nPRTI $2\nHALT\nLOAD $3 #7\nPRTI $3";
+        assert!(codes(source).is_empty());
+
+        let source = "LOAD $1 #4\nSPAWN $1 $2\nPRTI $2\nHALT\nPRTI $1\nHALT\nPRTI $2";
+        assert_eq!(vec![(7, "unreachable-code")], codes(source));
+    }
+
+    #[test]
+    fn render() {
+        let source = "LOAD $1 #2\n  PRTI $9\nPRTI $1";
+        let (program, locations) = Program::from_located_source(source).unwrap();
+        let diagnostics = lint(&program, &locations, VMConfig::default());
+
+        let expected = "a.lvm: error[invalid-register]: invalid register $9
+  at a.lvm, line 2, column 3:
+  PRTI $9
+  ^
+";
+        assert_eq!(expected, diagnostics[0].render("a.lvm", source));
+    }
+}
@@ -0,0 +1,116 @@
+mod lint;
+
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::Context;
+use clap::Parser;
+use lint::*;
+use lvm_core::Program;
+use lvm_machine::VMConfig;
+use lvm_parser::{Location, ParseSource};
+use serde::Serialize;
+
+/// Reports suspicious code in assembly programs of the language VM.
+#[derive(Debug, Parser)]
+#[command(name = "lvm-lint", version)]
+struct Args {
+    /// The assembly files.
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// The number of registers of the machine.
+    #[arg(short, long, default_value_t = VMConfig::default().registers())]
+    registers: usize,
+
+    /// The output format.
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Fails on warnings too.
+    #[arg(long)]
+    deny_warnings: bool,
+}
+
+/// The output format of the diagnostics.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, clap::ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// A diagnostic of a file, as written in JSON.
+#[derive(Debug, Serialize)]
+struct FileDiagnostic<'a> {
+    file: String,
+    #[serde(flatten)]
+    diagnostic: &'a Diagnostic,
+}
+
+fn execute(args: &Args) -> anyhow::Result<bool> {
+    let config = VMConfig::default().with_registers(args.registers);
+    let mut reports = vec![];
+
+    for path in &args.files {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+
+        let diagnostics = match Program::from_located_source(&source) {
+            Ok((program, locations)) => lint(&program, &locations, config),
+            Err(e) => {
+                let location = e.location().unwrap_or(Location::make(1, 1));
+                vec![Diagnostic {
+                    offset: 0,
+                    line: location.line(),
+                    column: location.column(),
+                    severity: Severity::Error,
+                    code: "parse",
+                    message: e.message().to_string(),
+                }]
+            }
+        };
+        reports.push((path.display().to_string(), source, diagnostics));
+    }
+
+    match args.format {
+        Format::Text => {
+            for (file, source, diagnostics) in &reports {
+                for diagnostic in diagnostics {
+                    match diagnostic.code {
+                        "parse" => print!("{}: error[parse]:\n{}", file, diagnostic.message),
+                        _ => print!("{}", diagnostic.render(file, source)),
+                    }
+                }
+            }
+        }
+        Format::Json => {
+            let json: Vec<FileDiagnostic> = reports
+                .iter()
+                .flat_map(|(file, _, diagnostics)| {
+                    diagnostics.iter().map(|diagnostic| FileDiagnostic {
+                        file: file.clone(),
+                        diagnostic,
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+    }
+
+    let failed = reports.iter().flat_map(|(_, _, d)| d).any(|d| {
+        d.severity == Severity::Error || (args.deny_warnings && d.severity == Severity::Warning)
+    });
+    Ok(!failed)
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match execute(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use lvm_core::Program;
+
+use crate::{ProcessStatus, VMConfig, VM};
+
+/// The final state of a program run by a [`BatchRunner`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BatchResult {
+    registers: Vec<u16>,
+    steps: usize,
+    status: ProcessStatus,
+}
+
+impl BatchResult {
+    /// Returns the final values of the registers.
+    pub fn registers(&self) -> &[u16] {
+        &self.registers
+    }
+
+    /// Returns the number of executed instructions.
+    pub const fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns [`ProcessStatus::Done`] or [`ProcessStatus::Faulted`], or
+    /// [`ProcessStatus::Ready`] when the program was stopped by the step limit.
+    pub const fn status(&self) -> ProcessStatus {
+        self.status
+    }
+}
+
+type Setup = Arc<dyn Fn(usize, &mut VM) + Send + Sync>;
+
+/// Runs a batch of programs in parallel, each in its own machine on one of
+/// a pool of threads. The machines share nothing, so the results only depend
+/// on the programs, whatever the number of threads.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_core::{Instruction, Load, Operand16, Program, RIndex};
+/// use lvm_machine::{BatchRunner, ProcessStatus};
+///
+/// let programs: Vec<Program> = (0..10)
+///     .map(|i| {
+///         let load = Load::make(RIndex::make(0), Operand16::make(i));
+///         Program::make(vec![Instruction::LoadI(load)])
+///     })
+///     .collect();
+///
+/// let results = BatchRunner::make(4).run(&programs);
+/// assert_eq!(7, results[7].registers()[0]);
+/// assert_eq!(ProcessStatus::Done, results[7].status());
+/// ```
+#[derive(Clone)]
+pub struct BatchRunner {
+    threads: usize,
+    config: VMConfig,
+    max_steps: Option<usize>,
+    setup: Option<Setup>,
+}
+
+impl BatchRunner {
+    /// Creates a [`BatchRunner`] instance using `threads` threads, at least one.
+    pub fn make(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            config: VMConfig::default(),
+            max_steps: None,
+            setup: None,
+        }
+    }
+
+    /// Sets the configuration of the machines.
+    pub fn with_config(mut self, config: VMConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Stops the programs after the number of executed instructions.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Sets a function called with the index of the program and its machine, before it runs,
+    /// e.g. to register syscalls or to replace the devices.
+    pub fn with_setup<F>(mut self, setup: F) -> Self
+    where
+        F: Fn(usize, &mut VM) + Send + Sync + 'static,
+    {
+        self.setup = Some(Arc::new(setup));
+        self
+    }
+
+    /// Returns the number of threads.
+    pub const fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Runs the programs and returns their results, in the order of the programs.
+    pub fn run(&self, programs: &[Program]) -> Vec<BatchResult> {
+        let next = AtomicUsize::new(0);
+        let results: Vec<Mutex<Option<BatchResult>>> =
+            programs.iter().map(|_| Mutex::new(None)).collect();
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.threads.min(programs.len()) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let program = match programs.get(index) {
+                        Some(program) => program,
+                        None => break,
+                    };
+
+                    let result = self.run_one(index, program);
+                    *results[index].lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+                });
+            }
+        });
+
+        results
+            .into_iter()
+            .filter_map(|r| r.into_inner().unwrap_or_else(|e| e.into_inner()))
+            .collect()
+    }
+
+    fn run_one(&self, index: usize, program: &Program) -> BatchResult {
+        let mut vm = VM::with_config(self.config);
+        if let Some(setup) = &self.setup {
+            setup(index, &mut vm);
+        }
+        vm.load_program(program.clone());
+
+        let mut steps = 0;
+        let status = loop {
+            if self.max_steps.is_some_and(|max| steps >= max) {
+                break ProcessStatus::Ready;
+            }
+
+            match vm.step() {
+                Ok(Some(_)) => steps += 1,
+                Ok(None) => break ProcessStatus::Done,
+                Err(err) => break ProcessStatus::Faulted(err),
+            }
+        };
+
+        BatchResult {
+            registers: vm.registers().to_vec(),
+            steps,
+            status,
+        }
+    }
+}
+
+impl Default for BatchRunner {
+    /// Uses as many threads as the available parallelism.
+    fn default() -> Self {
+        let threads = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        Self::make(threads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::{Add, Div, Instruction, Iret, Ivec, Load, Operand16, Operand8, RIndex};
+
+    use super::*;
+    use crate::{Interrupts, Scheduler, Timer, VMError};
+
+    fn assert_send<T: Send>() {}
+
+    fn make_programs() -> Vec<Program> {
+        let r = RIndex::make;
+        (0..50u16)
+            .map(|i| {
+                let mut code = vec![
+                    Instruction::LoadI(Load::make(r(0), Operand16::make(i))),
+                    Instruction::LoadI(Load::make(r(1), Operand16::make(i % 3))),
+                ];
+                for _ in 0..i {
+                    code.push(Instruction::AddI(Add::make(r(0), r(2), r(2))));
+                }
+                code.push(Instruction::DivI(Div::make(r(2), r(1), r(3))));
+                Program::make(code)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn send() {
+        assert_send::<VM>();
+        assert_send::<Scheduler>();
+        assert_send::<BatchRunner>();
+    }
+
+    #[test]
+    fn deterministic() {
+        let programs = make_programs();
+        let expected = BatchRunner::make(1).run(&programs);
+        assert_eq!(50, expected.len());
+        assert_eq!(&[10, 1, 100, 100], &expected[10].registers()[..4]);
+        assert_eq!(
+            ProcessStatus::Faulted(VMError::DivisionByZero),
+            expected[9].status()
+        );
+
+        for threads in [2, 3, 8, 64] {
+            assert_eq!(expected, BatchRunner::make(threads).run(&programs));
+        }
+        assert!(BatchRunner::default().run(&[]).is_empty());
+    }
+
+    #[test]
+    fn max_steps() {
+        // The timer handler returns to itself, so the program never ends.
+        let ivec = Ivec::make(Operand8::make(Interrupts::TIMER), Operand16::make(1));
+        let program = Program::make(vec![
+            Instruction::IvecI(ivec),
+            Instruction::IretI(Iret::make()),
+        ]);
+
+        let runner = BatchRunner::make(2)
+            .with_max_steps(100)
+            .with_setup(|_, vm| vm.set_timer(Timer::make(1)));
+        let results = runner.run(&[program.clone(), program]);
+
+        assert_eq!(ProcessStatus::Ready, results[1].status());
+        assert_eq!(100, results[1].steps());
+    }
+}
@@ -0,0 +1,119 @@
+/// The configuration of a [`crate::VM`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct VMConfig {
+    registers: usize,
+    memory: usize,
+    journal: Option<usize>,
+    mailbox: usize,
+}
+
+impl VMConfig {
+    pub const DEFAULT_REGISTERS: usize = 8;
+    pub const MAX_REGISTERS: usize = u8::MAX as usize + 1;
+    pub const DEFAULT_MEMORY: usize = 1024;
+    pub const MAX_MEMORY: usize = u16::MAX as usize + 1;
+    pub const DEFAULT_MAILBOX: usize = 16;
+
+    /// Returns the number of registers.
+    pub const fn registers(&self) -> usize {
+        self.registers
+    }
+
+    /// Sets the number of registers, capped to [`VMConfig::MAX_REGISTERS`]
+    /// since registers are addressed by an `u8` index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_machine::VMConfig;
+    ///
+    /// let config = VMConfig::default().with_registers(16);
+    /// assert_eq!(16, config.registers());
+    ///
+    /// let config = VMConfig::default().with_registers(1000);
+    /// assert_eq!(VMConfig::MAX_REGISTERS, config.registers());
+    /// ```
+    pub fn with_registers(mut self, registers: usize) -> Self {
+        self.registers = registers.min(Self::MAX_REGISTERS);
+        self
+    }
+
+    /// Returns the number of bytes of memory.
+    pub const fn memory(&self) -> usize {
+        self.memory
+    }
+
+    /// Sets the number of bytes of memory, capped to [`VMConfig::MAX_MEMORY`]
+    /// since the memory is addressed by an `u16` value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_machine::VMConfig;
+    ///
+    /// let config = VMConfig::default().with_memory(4096);
+    /// assert_eq!(4096, config.memory());
+    ///
+    /// let config = VMConfig::default().with_memory(1 << 20);
+    /// assert_eq!(VMConfig::MAX_MEMORY, config.memory());
+    /// ```
+    pub fn with_memory(mut self, memory: usize) -> Self {
+        self.memory = memory.min(Self::MAX_MEMORY);
+        self
+    }
+
+    /// Returns the number of bytes the undo log may use,
+    /// or `None` when the writes are not recorded.
+    pub const fn journal(&self) -> Option<usize> {
+        self.journal
+    }
+
+    /// Records the register writes of the executed instructions
+    /// in an undo log using at most the given number of bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_machine::VMConfig;
+    ///
+    /// assert_eq!(None, VMConfig::default().journal());
+    ///
+    /// let config = VMConfig::default().with_journal(1 << 20);
+    /// assert_eq!(Some(1 << 20), config.journal());
+    /// ```
+    pub fn with_journal(mut self, capacity: usize) -> Self {
+        self.journal = Some(capacity);
+        self
+    }
+
+    /// Returns the number of messages the mailbox holds.
+    pub const fn mailbox(&self) -> usize {
+        self.mailbox
+    }
+
+    /// Sets the number of messages the mailbox holds, at least one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_machine::VMConfig;
+    ///
+    /// assert_eq!(VMConfig::DEFAULT_MAILBOX, VMConfig::default().mailbox());
+    /// assert_eq!(1, VMConfig::default().with_mailbox(0).mailbox());
+    /// ```
+    pub fn with_mailbox(mut self, capacity: usize) -> Self {
+        self.mailbox = capacity.max(1);
+        self
+    }
+}
+
+impl Default for VMConfig {
+    fn default() -> Self {
+        Self {
+            registers: Self::DEFAULT_REGISTERS,
+            memory: Self::DEFAULT_MEMORY,
+            journal: None,
+            mailbox: Self::DEFAULT_MAILBOX,
+        }
+    }
+}
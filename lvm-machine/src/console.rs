@@ -0,0 +1,144 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    sync::{Arc, Mutex},
+};
+
+use crate::{Device, VMError};
+
+/// The console device: `PRTI` and `PRTC` write to its output and `READ` reads from its input.
+/// The default console uses the standard input and output.
+pub struct Console {
+    input: Box<dyn BufRead + Send>,
+    output: Box<dyn Write + Send>,
+}
+
+impl Console {
+    /// Creates a [`Console`] instance reading from the input and writing to the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_machine::{Console, OutputBuffer};
+    ///
+    /// let output = OutputBuffer::default();
+    /// let mut console = Console::make(Box::new("42\n".as_bytes()), Box::new(output.clone()));
+    ///
+    /// assert_eq!(Ok(42), console.read_integer());
+    /// console.print_integer(7).unwrap();
+    /// assert_eq!("7", output.to_string());
+    /// ```
+    pub fn make(input: Box<dyn BufRead + Send>, output: Box<dyn Write + Send>) -> Self {
+        Self { input, output }
+    }
+
+    /// Creates a [`Console`] instance using the standard input and output.
+    pub fn stdio() -> Self {
+        Self::make(
+            Box::new(BufReader::new(std::io::stdin())),
+            Box::new(std::io::stdout()),
+        )
+    }
+
+    /// Prints the decimal representation of a value.
+    pub fn print_integer(&mut self, value: u16) -> Result<(), VMError> {
+        write!(self.output, "{}", value)?;
+        self.output.flush()?;
+        Ok(())
+    }
+
+    /// Prints the character with the code of the value.
+    pub fn print_char(&mut self, value: u16) -> Result<(), VMError> {
+        let c = char::from_u32(value as u32).ok_or(VMError::InvalidChar(value))?;
+        write!(self.output, "{}", c)?;
+        self.output.flush()?;
+        Ok(())
+    }
+
+    /// Reads a line holding a decimal value.
+    pub fn read_integer(&mut self) -> Result<u16, VMError> {
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(VMError::DeviceError(std::io::ErrorKind::UnexpectedEof));
+        }
+
+        line.trim().parse().map_err(|_| VMError::InvalidInput)
+    }
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::stdio()
+    }
+}
+
+impl Device for Console {
+    fn name(&self) -> &str {
+        "console"
+    }
+}
+
+/// An in-memory output which can be shared with a [`Console`],
+/// to inspect what a program printed.
+#[derive(Debug, Default, Clone)]
+pub struct OutputBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl OutputBuffer {
+    /// Returns the printed bytes.
+    pub fn contents(&self) -> Vec<u8> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Used for the regular string representation, replacing invalid UTF-8.
+impl std::fmt::Display for OutputBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.contents()))
+    }
+}
+
+impl Write for OutputBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_console(input: &'static str) -> (Console, OutputBuffer) {
+        let output = OutputBuffer::default();
+        let console = Console::make(Box::new(input.as_bytes()), Box::new(output.clone()));
+        (console, output)
+    }
+
+    #[test]
+    fn print() {
+        let (mut console, output) = make_console("");
+        console.print_integer(42).unwrap();
+        console.print_char(b'\n' as u16).unwrap();
+        console.print_char(0x263A).unwrap();
+
+        assert_eq!("42\n\u{263A}", output.to_string());
+        assert_eq!(
+            Err(VMError::InvalidChar(0xD800)),
+            console.print_char(0xD800)
+        );
+    }
+
+    #[test]
+    fn read() {
+        let (mut console, _) = make_console(" 12 \nabc\n70000\n");
+        assert_eq!(Ok(12), console.read_integer());
+        assert_eq!(Err(VMError::InvalidInput), console.read_integer());
+        assert_eq!(Err(VMError::InvalidInput), console.read_integer());
+        assert_eq!(
+            Err(VMError::DeviceError(std::io::ErrorKind::UnexpectedEof)),
+            console.read_integer()
+        );
+    }
+}
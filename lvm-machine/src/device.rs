@@ -0,0 +1,9 @@
+/// A peripheral of a [`crate::VM`], driven by dedicated instructions.
+/// Devices are owned by the machine, so they can be moved across threads with it.
+pub trait Device: Send {
+    /// The name used in the error messages.
+    fn name(&self) -> &str;
+
+    /// Called when the machine is reset. Devices without state do nothing.
+    fn reset(&mut self) {}
+}
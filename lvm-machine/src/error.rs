@@ -0,0 +1,135 @@
+use std::fmt::Display;
+
+use lvm_core::RIndex;
+
+use crate::Interrupts;
+
+/// The errors raised while executing instructions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum VMError {
+    /// The instruction refers a register the machine does not have.
+    InvalidRegister(RIndex),
+    /// The memory range starting at the address is beyond the memory of the machine.
+    InvalidAddress(usize),
+    /// There is no handler registered for the syscall number.
+    UnknownSyscall(u16),
+    /// The syscall handler failed with the error code.
+    SyscallFailed(u16),
+    /// The syscall handler is async and runs only in the async mode.
+    AsyncSyscall(u16),
+    /// The machine waits for an async syscall, see [`crate::VM::poll_step`].
+    Suspended,
+    /// A device failed to read or write.
+    DeviceError(std::io::ErrorKind),
+    /// The value read by a device is not a valid integer.
+    InvalidInput,
+    /// The value is not the code of a character.
+    InvalidChar(u16),
+    /// There is no open file with the handle.
+    InvalidHandle(u16),
+    /// The value is not an open mode.
+    InvalidMode(u16),
+    /// The path is empty or not valid UTF-8.
+    InvalidPath,
+    /// The file does not exist.
+    FileNotFound,
+    /// The file system refused the access.
+    PermissionDenied,
+    /// The divisor is zero.
+    DivisionByZero,
+    /// The interrupt handlers are nested too deep.
+    StackOverflow,
+    /// The machine has no such interrupt vector.
+    InvalidVector(u8),
+    /// `IRET` outside an interrupt handler.
+    NoInterrupt,
+    /// `RECV` with no message in the mailbox.
+    EmptyMailbox,
+    /// The mailbox of the receiving process is full.
+    MailboxFull,
+    /// `SEND` to a process which does not exist or is not running.
+    InvalidProcess(u16),
+}
+
+impl VMError {
+    /// Returns the interrupt vector the fault is delivered to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_machine::{Interrupts, VMError};
+    ///
+    /// assert_eq!(Interrupts::DIVISION_BY_ZERO, VMError::DivisionByZero.vector());
+    /// ```
+    pub const fn vector(&self) -> u8 {
+        match self {
+            Self::InvalidRegister(_) => Interrupts::INVALID_REGISTER,
+            Self::InvalidAddress(_) => Interrupts::INVALID_ADDRESS,
+            Self::DivisionByZero => Interrupts::DIVISION_BY_ZERO,
+            Self::StackOverflow => Interrupts::STACK_OVERFLOW,
+            Self::UnknownSyscall(_)
+            | Self::SyscallFailed(_)
+            | Self::AsyncSyscall(_)
+            | Self::Suspended => Interrupts::SYSCALL,
+            Self::DeviceError(_)
+            | Self::InvalidInput
+            | Self::InvalidChar(_)
+            | Self::InvalidHandle(_)
+            | Self::InvalidMode(_)
+            | Self::InvalidPath
+            | Self::FileNotFound
+            | Self::PermissionDenied
+            | Self::EmptyMailbox
+            | Self::MailboxFull
+            | Self::InvalidProcess(_) => Interrupts::DEVICE,
+            Self::InvalidVector(_) | Self::NoInterrupt => Interrupts::ILLEGAL,
+        }
+    }
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_core::RIndex;
+/// use lvm_machine::VMError;
+///
+/// let err = VMError::InvalidRegister(RIndex::make(10u8));
+/// assert_eq!("invalid register $10", err.to_string())
+/// ```
+impl Display for VMError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidRegister(rindx) => write!(f, "invalid register {}", rindx),
+            Self::InvalidAddress(addr) => write!(f, "invalid address {}", addr),
+            Self::UnknownSyscall(number) => write!(f, "unknown syscall #{}", number),
+            Self::SyscallFailed(code) => write!(f, "syscall failed with code {}", code),
+            Self::AsyncSyscall(number) => write!(f, "syscall #{} needs the async mode", number),
+            Self::Suspended => write!(f, "suspended on an async syscall"),
+            Self::DeviceError(kind) => write!(f, "device error: {}", kind),
+            Self::InvalidInput => write!(f, "invalid input"),
+            Self::InvalidChar(value) => write!(f, "invalid character {}", value),
+            Self::InvalidHandle(handle) => write!(f, "invalid file handle {}", handle),
+            Self::InvalidMode(mode) => write!(f, "invalid open mode {}", mode),
+            Self::InvalidPath => write!(f, "invalid path"),
+            Self::FileNotFound => write!(f, "file not found"),
+            Self::PermissionDenied => write!(f, "permission denied"),
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::StackOverflow => write!(f, "stack overflow"),
+            Self::InvalidVector(vector) => write!(f, "invalid interrupt vector {}", vector),
+            Self::NoInterrupt => write!(f, "no interrupt to return from"),
+            Self::EmptyMailbox => write!(f, "empty mailbox"),
+            Self::MailboxFull => write!(f, "mailbox full"),
+            Self::InvalidProcess(pid) => write!(f, "invalid process {}", pid),
+        }
+    }
+}
+
+impl std::error::Error for VMError {}
+
+impl From<std::io::Error> for VMError {
+    fn from(err: std::io::Error) -> Self {
+        Self::DeviceError(err.kind())
+    }
+}
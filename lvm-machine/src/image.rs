@@ -0,0 +1,768 @@
+use std::fmt::Display;
+
+use lvm_core::Program;
+use lvm_parser::ParseSource;
+use serde::{Deserialize, Serialize};
+
+use crate::{Interrupts, Mailbox, Message, Snapshot, Timer, VMConfig, VM};
+
+/// The errors raised while reading an [`Image`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ImageError {
+    /// The bytes do not start with [`Image::MAGIC`].
+    InvalidMagic,
+    /// The image was written with another version of the format.
+    UnsupportedVersion(u8),
+    /// The bytes end before the image is complete.
+    Truncated,
+    /// There are bytes after the end of the image.
+    TrailingBytes(usize),
+    /// The number of registers is not between 1 and [`VMConfig::MAX_REGISTERS`].
+    InvalidRegisters(usize),
+    /// The number of bytes of memory is above [`VMConfig::MAX_MEMORY`].
+    InvalidMemory(usize),
+    /// The program counter is beyond the end of the program.
+    InvalidPc(usize),
+    /// The interrupt state is not one the machine can reach.
+    InvalidInterrupts,
+    /// The mailbox has no capacity or more messages than its capacity.
+    InvalidMailbox,
+    /// The program cannot be parsed.
+    InvalidProgram(String),
+    /// The JSON document cannot be parsed.
+    InvalidJson(String),
+}
+
+/// Used for the regular string representation.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_machine::ImageError;
+///
+/// let err = ImageError::UnsupportedVersion(9);
+/// assert_eq!("unsupported image version 9", err.to_string())
+/// ```
+impl Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidMagic => write!(f, "not a vm image"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported image version {}", v),
+            Self::Truncated => write!(f, "truncated image"),
+            Self::TrailingBytes(n) => write!(f, "{} unexpected bytes after the image", n),
+            Self::InvalidRegisters(n) => write!(
+                f,
+                "invalid number of registers {}, expected 1 to {}",
+                n,
+                VMConfig::MAX_REGISTERS
+            ),
+            Self::InvalidMemory(n) => write!(
+                f,
+                "invalid memory of {} bytes, expected at most {}",
+                n,
+                VMConfig::MAX_MEMORY
+            ),
+            Self::InvalidPc(pc) => write!(f, "program counter {} beyond the program", pc),
+            Self::InvalidInterrupts => write!(f, "invalid interrupt state"),
+            Self::InvalidMailbox => write!(f, "invalid mailbox"),
+            Self::InvalidProgram(e) => write!(f, "invalid program: {}", e),
+            Self::InvalidJson(e) => write!(f, "invalid json: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+/// The state of a [`VM`] which can be written to a file and restored later.
+///
+/// The binary layout, with the numbers in little endian, is:
+/// - the [`Image::MAGIC`] bytes and the version byte
+/// - the number of registers (`u16`) followed by their values (`u16` each)
+/// - the number of bytes of memory (`u32`) followed by the bytes
+/// - the program counter (`u32`)
+/// - the number of instructions (`u32`) followed by their bytes
+/// - the handler address of every vector (`u32`, `u32::MAX` when none is installed)
+/// - the number of running handlers (`u8`) followed by their return addresses (`u32` each)
+/// - the timer period and count (`u32` each)
+/// - the number of pending interrupts (`u8`) followed by their vectors (`u8` each)
+/// - the mailbox capacity and the number of messages (`u32` each) followed by
+///   the messages, the oldest first (`u16` sender and `u16` value each)
+///
+/// The JSON document has the same fields, with the memory as a hex string
+/// and the program as assembly text.
+///
+/// The images of any other version than [`Image::VERSION`] are rejected.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Image {
+    registers: Vec<u16>,
+    memory: Vec<u8>,
+    pc: usize,
+    program: Program,
+    interrupts: Interrupts,
+    timer: Timer,
+    pending: Vec<u8>,
+    mailbox: Mailbox,
+}
+
+/// The JSON representation of an [`Image`].
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonImage {
+    version: u8,
+    registers: Vec<u16>,
+    memory: String,
+    pc: usize,
+    program: Vec<String>,
+    interrupts: JsonInterrupts,
+    mailbox: JsonMailbox,
+}
+
+/// The JSON representation of the interrupt state of an [`Image`].
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonInterrupts {
+    vectors: Vec<Option<usize>>,
+    frames: Vec<usize>,
+    period: usize,
+    count: usize,
+    pending: Vec<u8>,
+}
+
+/// The JSON representation of the mailbox of an [`Image`], with the messages
+/// as pairs of sender and value.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonMailbox {
+    capacity: usize,
+    messages: Vec<(u16, u16)>,
+}
+
+/// Reads the fields of a binary image.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ImageError> {
+        if self.bytes.len() < n {
+            return Err(ImageError::Truncated);
+        }
+
+        let (fst, snd) = self.bytes.split_at(n);
+        self.bytes = snd;
+        Ok(fst)
+    }
+
+    fn u8(&mut self) -> Result<u8, ImageError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ImageError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, ImageError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+impl Image {
+    /// The first bytes of a binary image.
+    pub const MAGIC: [u8; 4] = *b"LVMI";
+
+    /// The version of the images written and read by this crate.
+    pub const VERSION: u8 = 1;
+
+    const INSTRUCTION_LEN: usize = 4;
+
+    const NO_HANDLER: u32 = u32::MAX;
+
+    fn make(
+        registers: Vec<u16>,
+        memory: Vec<u8>,
+        pc: usize,
+        program: Program,
+        (interrupts, timer, pending): (Interrupts, Timer, Vec<u8>),
+        mailbox: Mailbox,
+    ) -> Result<Self, ImageError> {
+        if registers.is_empty() || registers.len() > VMConfig::MAX_REGISTERS {
+            return Err(ImageError::InvalidRegisters(registers.len()));
+        }
+
+        if memory.len() > VMConfig::MAX_MEMORY {
+            return Err(ImageError::InvalidMemory(memory.len()));
+        }
+
+        if pc > program.len() {
+            return Err(ImageError::InvalidPc(pc));
+        }
+
+        let valid = interrupts.depth() <= Interrupts::MAX_DEPTH
+            && interrupts.frames().iter().all(|ret| *ret <= program.len())
+            && (timer.period() == 0 || timer.count() < timer.period())
+            && pending.iter().all(|v| (*v as usize) < Interrupts::VECTORS);
+        if !valid {
+            return Err(ImageError::InvalidInterrupts);
+        }
+
+        Ok(Self {
+            registers,
+            memory,
+            pc,
+            program,
+            interrupts,
+            timer,
+            pending,
+            mailbox,
+        })
+    }
+
+    fn check_version(version: u8) -> Result<(), ImageError> {
+        match version {
+            Self::VERSION => Ok(()),
+            _ => Err(ImageError::UnsupportedVersion(version)),
+        }
+    }
+
+    fn make_mailbox(capacity: usize, messages: &[(u16, u16)]) -> Result<Mailbox, ImageError> {
+        if capacity == 0 {
+            return Err(ImageError::InvalidMailbox);
+        }
+
+        let mut mailbox = Mailbox::make(capacity);
+        for (sender, value) in messages {
+            mailbox
+                .push(Message::make(*sender, *value))
+                .map_err(|_| ImageError::InvalidMailbox)?;
+        }
+        Ok(mailbox)
+    }
+
+    /// Returns the values of the registers.
+    pub fn registers(&self) -> &[u16] {
+        &self.registers
+    }
+
+    /// Returns the memory.
+    pub fn memory(&self) -> &[u8] {
+        &self.memory
+    }
+
+    /// Returns the position of the next instruction to be executed.
+    pub const fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the loaded program.
+    pub const fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Returns the interrupt vector table with the running handlers.
+    pub const fn interrupts(&self) -> &Interrupts {
+        &self.interrupts
+    }
+
+    /// Returns the timer, with its count.
+    pub const fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    /// Returns the host interrupts not yet delivered, the oldest first.
+    pub fn pending(&self) -> &[u8] {
+        &self.pending
+    }
+
+    /// Returns the mailbox, with the messages not yet received.
+    pub const fn mailbox(&self) -> &Mailbox {
+        &self.mailbox
+    }
+
+    /// Writes the binary image.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Self::MAGIC.to_vec();
+        bytes.push(Self::VERSION);
+
+        bytes.extend((self.registers.len() as u16).to_le_bytes());
+        for register in &self.registers {
+            bytes.extend(register.to_le_bytes());
+        }
+
+        bytes.extend((self.memory.len() as u32).to_le_bytes());
+        bytes.extend(&self.memory);
+
+        bytes.extend((self.pc as u32).to_le_bytes());
+        bytes.extend((self.program.len() as u32).to_le_bytes());
+        bytes.extend(Vec::<u8>::from(self.program.clone()));
+
+        for handler in self.interrupts.vectors() {
+            let handler = handler.map_or(Self::NO_HANDLER, |h| h as u32);
+            bytes.extend(handler.to_le_bytes());
+        }
+        bytes.push(self.interrupts.depth() as u8);
+        for ret in self.interrupts.frames() {
+            bytes.extend((*ret as u32).to_le_bytes());
+        }
+        bytes.extend((self.timer.period() as u32).to_le_bytes());
+        bytes.extend((self.timer.count() as u32).to_le_bytes());
+        bytes.push(self.pending.len() as u8);
+        bytes.extend(&self.pending);
+
+        bytes.extend((self.mailbox.capacity() as u32).to_le_bytes());
+        bytes.extend((self.mailbox.len() as u32).to_le_bytes());
+        for message in self.mailbox.iter() {
+            bytes.extend(message.sender().to_le_bytes());
+            bytes.extend(message.value().to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Reads and validates a binary image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_machine::{Image, ImageError, VM};
+    ///
+    /// let bytes = VM::new().image().to_bytes();
+    /// let image = Image::from_bytes(&bytes).unwrap();
+    /// assert_eq!(8, image.registers().len());
+    ///
+    /// assert_eq!(Err(ImageError::Truncated), Image::from_bytes(&bytes[..10]));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageError> {
+        let mut reader = Reader { bytes };
+
+        if reader.take(Self::MAGIC.len()).ok() != Some(Self::MAGIC.as_slice()) {
+            return Err(ImageError::InvalidMagic);
+        }
+
+        let version = reader.u8()?;
+        Self::check_version(version)?;
+
+        let count = reader.u16()? as usize;
+        let registers = (0..count)
+            .map(|_| reader.u16())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let len = reader.u32()? as usize;
+        if len > VMConfig::MAX_MEMORY {
+            return Err(ImageError::InvalidMemory(len));
+        }
+        let memory = reader.take(len)?.to_vec();
+
+        let pc = reader.u32()? as usize;
+        let len = reader.u32()? as usize;
+        let code = reader.take(len.saturating_mul(Self::INSTRUCTION_LEN))?;
+        let program =
+            Program::from_binary(code).map_err(|e| ImageError::InvalidProgram(e.to_string()))?;
+
+        let interrupts = Self::read_interrupts(&mut reader)?;
+        let mailbox = Self::read_mailbox(&mut reader)?;
+
+        if !reader.bytes.is_empty() {
+            return Err(ImageError::TrailingBytes(reader.bytes.len()));
+        }
+
+        Self::make(registers, memory, pc, program, interrupts, mailbox)
+    }
+
+    fn read_mailbox(reader: &mut Reader) -> Result<Mailbox, ImageError> {
+        let capacity = reader.u32()? as usize;
+        let len = reader.u32()? as usize;
+        if len > capacity {
+            return Err(ImageError::InvalidMailbox);
+        }
+
+        let messages = (0..len)
+            .map(|_| Ok((reader.u16()?, reader.u16()?)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::make_mailbox(capacity, &messages)
+    }
+
+    fn read_interrupts(reader: &mut Reader) -> Result<(Interrupts, Timer, Vec<u8>), ImageError> {
+        let mut vectors = [None; Interrupts::VECTORS];
+        for vector in vectors.iter_mut() {
+            let handler = reader.u32()?;
+            *vector = (handler != Self::NO_HANDLER).then_some(handler as usize);
+        }
+
+        let depth = reader.u8()? as usize;
+        let frames = (0..depth)
+            .map(|_| reader.u32().map(|ret| ret as usize))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let period = reader.u32()? as usize;
+        let count = reader.u32()? as usize;
+
+        let len = reader.u8()? as usize;
+        let pending = reader.take(len)?.to_vec();
+
+        Ok((
+            Interrupts::make(vectors, frames),
+            Timer::make(period).with_count(count),
+            pending,
+        ))
+    }
+
+    /// Writes the JSON document.
+    pub fn to_json(&self) -> String {
+        let json = JsonImage {
+            version: Self::VERSION,
+            registers: self.registers.clone(),
+            memory: self.memory.iter().map(|b| format!("{:02x}", b)).collect(),
+            pc: self.pc,
+            program: self.program.iter().map(|i| i.to_string()).collect(),
+            interrupts: JsonInterrupts {
+                vectors: self.interrupts.vectors().to_vec(),
+                frames: self.interrupts.frames().to_vec(),
+                period: self.timer.period(),
+                count: self.timer.count(),
+                pending: self.pending.clone(),
+            },
+            mailbox: JsonMailbox {
+                capacity: self.mailbox.capacity(),
+                messages: self
+                    .mailbox
+                    .iter()
+                    .map(|m| (m.sender(), m.value()))
+                    .collect(),
+            },
+        };
+
+        serde_json::to_string_pretty(&json).expect("an image is always serializable")
+    }
+
+    /// Reads and validates a JSON document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::RIndex;
+    /// use lvm_machine::{Image, ImageError, VM};
+    ///
+    /// let mut vm = VM::new();
+    /// vm.set_register(RIndex::make(1), 10).unwrap();
+    /// let json = vm.image().to_json();
+    /// let image = Image::from_json(&json).unwrap();
+    /// assert_eq!(10, image.registers()[1]);
+    ///
+    /// let json = json.replace(r#""version": 1"#, r#""version": 2"#);
+    /// assert_eq!(Err(ImageError::UnsupportedVersion(2)), Image::from_json(&json));
+    /// ```
+    pub fn from_json(src: &str) -> Result<Self, ImageError> {
+        let json: JsonImage =
+            serde_json::from_str(src).map_err(|e| ImageError::InvalidJson(e.to_string()))?;
+
+        Self::check_version(json.version)?;
+
+        let memory = decode_hex(&json.memory)?;
+
+        let program = Program::from_source(&json.program.join("\n"))
+            .map_err(|e| ImageError::InvalidProgram(e.to_string()))?;
+
+        let JsonInterrupts {
+            vectors,
+            frames,
+            period,
+            count,
+            pending,
+        } = json.interrupts;
+        let vectors = vectors
+            .try_into()
+            .map_err(|_| ImageError::InvalidInterrupts)?;
+        let interrupts = (
+            Interrupts::make(vectors, frames),
+            Timer::make(period).with_count(count),
+            pending,
+        );
+
+        let mailbox = Self::make_mailbox(json.mailbox.capacity, &json.mailbox.messages)?;
+
+        Self::make(
+            json.registers,
+            memory,
+            json.pc,
+            program,
+            interrupts,
+            mailbox,
+        )
+    }
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, ImageError> {
+    let invalid = || ImageError::InvalidJson("invalid memory hex string".to_string());
+    if !hex.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+                .ok_or_else(invalid)
+        })
+        .collect()
+}
+
+impl VM {
+    /// Captures the whole state in an image.
+    pub fn image(&self) -> Image {
+        Image {
+            registers: self.registers().to_vec(),
+            memory: self.memory().to_vec(),
+            pc: self.pc(),
+            program: self.program().clone(),
+            interrupts: self.interrupts().clone(),
+            timer: *self.timer(),
+            pending: self.pending().collect(),
+            mailbox: self.mailbox().clone(),
+        }
+    }
+
+    /// Creates a [`VM`] instance with the state of an image and the
+    /// configuration, except the sizes of the registers, memory and mailbox taken from the image.
+    /// The timer of the image replaces the default one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::RIndex;
+    /// use lvm_machine::{Image, VMConfig, VM};
+    ///
+    /// let mut vm = VM::new();
+    /// vm.set_register(RIndex::make(3), 42).unwrap();
+    ///
+    /// let image = Image::from_bytes(&vm.image().to_bytes()).unwrap();
+    /// let vm = VM::from_image(&image, VMConfig::default());
+    /// assert_eq!(Ok(42), vm.register(RIndex::make(3)));
+    /// ```
+    pub fn from_image(image: &Image, config: VMConfig) -> Self {
+        let config = config
+            .with_registers(image.registers.len())
+            .with_memory(image.memory.len())
+            .with_mailbox(image.mailbox.capacity());
+        let mut vm = VM::with_config(config);
+        vm.load_program(image.program.clone());
+        vm.restore(&Snapshot::make(
+            image.registers.clone(),
+            image.memory.clone(),
+            image.pc,
+            image.interrupts.clone(),
+        ));
+        vm.set_timer(image.timer);
+        for vector in &image.pending {
+            let _ = vm.raise(*vector);
+        }
+        for message in image.mailbox.iter() {
+            let _ = vm.deliver(message.sender(), message.value());
+        }
+        vm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::{Add, Instruction, Load, Operand16, RIndex};
+    use lvm_parser::ParseSource;
+
+    use super::*;
+
+    fn make_vm() -> VM {
+        let config = VMConfig::default().with_registers(4).with_memory(8);
+        let mut vm = VM::with_config(config);
+        vm.write_memory(6, &[0xAB, 0xCD]).unwrap();
+        vm.load_program(Program::make(vec![
+            Instruction::LoadI(Load::make(RIndex::make(1), Operand16::make(500))),
+            Instruction::AddI(Add::make(RIndex::make(1), RIndex::make(1), RIndex::make(2))),
+        ]));
+        vm.step().unwrap();
+        vm
+    }
+
+    #[test]
+    fn bytes() {
+        let vm = make_vm();
+        let bytes = vm.image().to_bytes();
+        assert_eq!(b"LVMI\x01\x04\x00", &bytes[..7]);
+
+        let image = Image::from_bytes(&bytes).unwrap();
+        assert_eq!(vm.image(), image);
+
+        let mut restored = VM::from_image(&image, VMConfig::default());
+        assert_eq!(1, restored.pc());
+        restored.step().unwrap();
+        assert_eq!(&[0, 500, 1000, 0], restored.registers());
+        assert_eq!(&[0, 0, 0, 0, 0, 0, 0xAB, 0xCD], restored.memory());
+    }
+
+    #[test]
+    fn json() {
+        let vm = make_vm();
+        let json = vm.image().to_json();
+        assert!(json.contains("\"LOAD $1 #500\""));
+        assert!(json.contains("\"000000000000abcd\""));
+
+        let image = Image::from_json(&json).unwrap();
+        assert_eq!(vm.image(), image);
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let bytes = make_vm().image().to_bytes();
+
+        assert_eq!(Err(ImageError::InvalidMagic), Image::from_bytes(b"LVM"));
+        assert_eq!(
+            Err(ImageError::InvalidMagic),
+            Image::from_bytes(&bytes[1..])
+        );
+
+        for version in [0, 2, 3] {
+            let mut other = bytes.clone();
+            other[4] = version;
+            assert_eq!(
+                Err(ImageError::UnsupportedVersion(version)),
+                Image::from_bytes(&other)
+            );
+        }
+
+        assert_eq!(
+            Err(ImageError::Truncated),
+            Image::from_bytes(&bytes[..bytes.len() - 1])
+        );
+
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert_eq!(
+            Err(ImageError::TrailingBytes(1)),
+            Image::from_bytes(&longer)
+        );
+
+        // The memory length is right after the 4 registers.
+        let mut memory = bytes.clone();
+        memory[17] = 1;
+        assert_eq!(
+            Err(ImageError::InvalidMemory((1 << 16) + 8)),
+            Image::from_bytes(&memory)
+        );
+
+        // The program counter is right after the 8 bytes of memory.
+        let mut pc = bytes.clone();
+        pc[27] = 3;
+        assert_eq!(Err(ImageError::InvalidPc(3)), Image::from_bytes(&pc));
+
+        // The opcode of the first instruction.
+        let mut code = bytes.clone();
+        code[35] = 0xFF;
+        assert!(matches!(
+            Image::from_bytes(&code),
+            Err(ImageError::InvalidProgram(_))
+        ));
+
+        // The depth is right after the 2 instructions and the 16 vectors.
+        let mut depth = bytes;
+        depth[107] = 1;
+        depth.splice(108..108, 3u32.to_le_bytes());
+        assert_eq!(
+            Err(ImageError::InvalidInterrupts),
+            Image::from_bytes(&depth)
+        );
+    }
+
+    #[test]
+    fn mailbox() {
+        let config = VMConfig::default().with_mailbox(3);
+        let mut vm = VM::with_config(config);
+        vm.deliver(1, 10).unwrap();
+        vm.deliver(2, 20).unwrap();
+
+        let image = vm.image();
+        assert_eq!(image, Image::from_bytes(&image.to_bytes()).unwrap());
+        assert_eq!(image, Image::from_json(&image.to_json()).unwrap());
+
+        let restored = VM::from_image(&image, VMConfig::default());
+        assert_eq!(vm.mailbox(), restored.mailbox());
+        assert_eq!(3, restored.config().mailbox());
+
+        // The number of messages is the last field before the two messages.
+        let mut bytes = image.to_bytes();
+        let at = bytes.len() - 12;
+        bytes[at] = 4;
+        bytes.extend([0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(Err(ImageError::InvalidMailbox), Image::from_bytes(&bytes));
+    }
+
+    #[test]
+    fn in_handler() {
+        // The timer interrupts the loads, and the handler sums them.
+        let source =
+            "IVEC #8 #6\nLOAD $1 #1\nLOAD $1 #2\nLOAD $1 #3\nLOAD $1 #4\nHALT\nADD $2 $1 $2\nIRET";
+        let program = Program::from_source(source).unwrap();
+        let mut vm = VM::new();
+        vm.set_timer(Timer::make(3));
+        vm.load_program(program);
+        vm.step().unwrap();
+        vm.step().unwrap();
+        vm.step().unwrap();
+        vm.raise(9).unwrap();
+        assert_eq!(1, vm.interrupts().depth());
+
+        let image = vm.image();
+        assert_eq!(Some(6), image.interrupts().handler(Interrupts::TIMER));
+        assert_eq!(&[9], image.pending());
+        assert_eq!(image, Image::from_bytes(&image.to_bytes()).unwrap());
+        assert_eq!(image, Image::from_json(&image.to_json()).unwrap());
+
+        let mut restored = VM::from_image(&image, VMConfig::default());
+        assert_eq!(vm.timer(), restored.timer());
+        loop {
+            let (expected, actual) = (vm.step(), restored.step());
+            assert_eq!(expected, actual);
+            assert_eq!(
+                (vm.pc(), vm.interrupts()),
+                (restored.pc(), restored.interrupts())
+            );
+            if !matches!(expected, Ok(Some(_))) {
+                break;
+            }
+        }
+        assert_eq!(vm.registers(), restored.registers());
+    }
+
+    #[test]
+    fn invalid_json() {
+        let json = make_vm().image().to_json();
+        let edit = |key: &str, value: serde_json::Value| {
+            let mut document: serde_json::Value = serde_json::from_str(&json).unwrap();
+            match value {
+                serde_json::Value::Null => document.as_object_mut().unwrap().remove(key),
+                value => document
+                    .as_object_mut()
+                    .unwrap()
+                    .insert(key.to_string(), value),
+            };
+            Image::from_json(&document.to_string())
+        };
+
+        assert!(matches!(
+            edit("program", serde_json::json!(["JUMP"])),
+            Err(ImageError::InvalidProgram(_))
+        ));
+        assert_eq!(Err(ImageError::InvalidPc(3)), edit("pc", 3.into()));
+        assert_eq!(
+            Err(ImageError::UnsupportedVersion(3)),
+            edit("version", 3.into())
+        );
+        assert!(matches!(
+            edit("memory", "0g".into()),
+            Err(ImageError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            edit("mailbox", serde_json::Value::Null),
+            Err(ImageError::InvalidJson(_))
+        ));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{Device, VMError};
+
+/// The interrupt vector table of a [`crate::VM`] with the frames of the running handlers.
+///
+/// A fault raised by an instruction is delivered to the handler of its vector,
+/// see [`VMError::vector`]; the faulting instruction is skipped, so `IRET` resumes
+/// with the next one. Host interrupts, like the timer, are delivered between
+/// instructions while no handler is running. Without a handler, faults stop
+/// the program as before and host interrupts are dropped.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Interrupts {
+    vectors: [Option<usize>; Interrupts::VECTORS],
+    frames: Vec<usize>,
+}
+
+impl Interrupts {
+    /// The number of vectors in the table.
+    pub const VECTORS: usize = 16;
+    /// The number of nested handlers; going deeper is a stack overflow.
+    pub const MAX_DEPTH: usize = 8;
+
+    /// An instruction refers a register the machine does not have.
+    pub const INVALID_REGISTER: u8 = 0;
+    /// An instruction refers memory the machine does not have.
+    pub const INVALID_ADDRESS: u8 = 1;
+    /// A `DIV` by zero.
+    pub const DIVISION_BY_ZERO: u8 = 2;
+    /// The handlers are nested too deep. It is only delivered when a handler
+    /// can still be entered, so it is fatal when raised by entering a handler.
+    pub const STACK_OVERFLOW: u8 = 3;
+    /// An unknown or failed syscall.
+    pub const SYSCALL: u8 = 4;
+    /// A device failed, including the refused file accesses.
+    pub const DEVICE: u8 = 5;
+    /// An invalid use of the interrupt instructions, like `IRET` outside a handler.
+    pub const ILLEGAL: u8 = 6;
+    /// Raised by the [`Timer`].
+    pub const TIMER: u8 = 8;
+
+    pub(crate) fn make(vectors: [Option<usize>; Self::VECTORS], frames: Vec<usize>) -> Self {
+        Self { vectors, frames }
+    }
+
+    /// Returns the handler addresses, indexed by vector.
+    pub fn vectors(&self) -> &[Option<usize>; Self::VECTORS] {
+        &self.vectors
+    }
+
+    /// Returns the return addresses of the running handlers, the innermost last.
+    pub fn frames(&self) -> &[usize] {
+        &self.frames
+    }
+
+    /// Returns the address of the handler installed for the vector.
+    pub fn handler(&self, vector: u8) -> Option<usize> {
+        self.vectors.get(vector as usize).copied().flatten()
+    }
+
+    /// Returns the number of handlers running.
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true while a handler runs.
+    pub fn in_handler(&self) -> bool {
+        !self.frames.is_empty()
+    }
+
+    /// Installs the handler and returns the previous one.
+    pub(crate) fn set_handler(
+        &mut self,
+        vector: u8,
+        handler: Option<usize>,
+    ) -> Result<Option<usize>, VMError> {
+        let slot = self
+            .vectors
+            .get_mut(vector as usize)
+            .ok_or(VMError::InvalidVector(vector))?;
+        Ok(std::mem::replace(slot, handler))
+    }
+
+    pub(crate) fn push(&mut self, ret: usize) -> Result<(), VMError> {
+        if self.frames.len() >= Self::MAX_DEPTH {
+            return Err(VMError::StackOverflow);
+        }
+
+        self.frames.push(ret);
+        Ok(())
+    }
+
+    pub(crate) fn pop(&mut self) -> Result<usize, VMError> {
+        self.frames.pop().ok_or(VMError::NoInterrupt)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl Default for Interrupts {
+    fn default() -> Self {
+        Self {
+            vectors: [None; Self::VECTORS],
+            frames: vec![],
+        }
+    }
+}
+
+/// A device raising [`Interrupts::TIMER`] every `period` executed instructions.
+/// The count is kept across the handlers, so the ticks are deterministic.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Timer {
+    period: usize,
+    count: usize,
+}
+
+impl Timer {
+    /// Creates a [`Timer`] instance firing every `period` instructions.
+    /// A zero period disables the timer.
+    pub fn make(period: usize) -> Self {
+        Self { period, count: 0 }
+    }
+
+    /// Sets the number of instructions counted since the timer last fired.
+    pub(crate) fn with_count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Returns the number of instructions between two interrupts.
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the number of instructions counted since the timer last fired.
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Counts an executed instruction and returns true when the timer fires.
+    pub fn tick(&mut self) -> bool {
+        if self.period == 0 {
+            return false;
+        }
+
+        self.count += 1;
+        if self.count == self.period {
+            self.count = 0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Device for Timer {
+    fn name(&self) -> &str {
+        "timer"
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table() {
+        let mut interrupts = Interrupts::default();
+        assert_eq!(Ok(None), interrupts.set_handler(1, Some(10)));
+        assert_eq!(Ok(Some(10)), interrupts.set_handler(1, Some(20)));
+        assert_eq!(Some(20), interrupts.handler(1));
+        assert_eq!(None, interrupts.handler(100));
+        assert_eq!(
+            Err(VMError::InvalidVector(16)),
+            interrupts.set_handler(16, Some(1))
+        );
+    }
+
+    #[test]
+    fn frames() {
+        let mut interrupts = Interrupts::default();
+        assert_eq!(Err(VMError::NoInterrupt), interrupts.pop());
+
+        for ret in 0..Interrupts::MAX_DEPTH {
+            interrupts.push(ret).unwrap();
+        }
+        assert_eq!(Err(VMError::StackOverflow), interrupts.push(100));
+        assert_eq!(Ok(Interrupts::MAX_DEPTH - 1), interrupts.pop());
+        assert!(interrupts.in_handler());
+    }
+
+    #[test]
+    fn timer() {
+        let mut timer = Timer::make(3);
+        let ticks: Vec<bool> = (0..6).map(|_| timer.tick()).collect();
+        assert_eq!(vec![false, false, true, false, false, true], ticks);
+
+        let mut timer = Timer::default();
+        assert!((0..10).all(|_| !timer.tick()));
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::VecDeque;
+
+use lvm_core::{Instruction, RIndex};
+
+use crate::Message;
+
+/// A write done by an instruction, with the value it replaced.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JournalWrite {
+    /// A register with its previous value.
+    Register(RIndex, u16),
+    /// A memory address with its previous byte.
+    Memory(usize, u8),
+    /// An interrupt vector with its previous handler.
+    Vector(u8, Option<usize>),
+    /// An interrupt handler was entered.
+    Enter,
+    /// An interrupt handler returned to the address.
+    Leave(usize),
+    /// A message taken from the mailbox.
+    Receive(Message),
+    /// The timer counted an instruction, with its previous count.
+    Tick(usize),
+    /// An interrupt was queued, to be delivered once no handler runs.
+    Raise(u8),
+    /// A queued interrupt was delivered.
+    Deliver(u8),
+}
+
+/// The writes done by an executed instruction, with the values they replaced.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct JournalEntry {
+    pc: usize,
+    instruction: Instruction,
+    writes: Vec<JournalWrite>,
+}
+
+impl JournalEntry {
+    /// Returns the program counter before the instruction was executed.
+    pub const fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the executed instruction.
+    pub const fn instruction(&self) -> Instruction {
+        self.instruction
+    }
+
+    /// Returns the writes with the previous values, in the order they were done.
+    pub fn writes(&self) -> &[JournalWrite] {
+        &self.writes
+    }
+
+    /// Returns true when the instruction wrote the register.
+    pub fn writes_register(&self, rindx: RIndex) -> bool {
+        self.writes
+            .iter()
+            .any(|w| matches!(w, JournalWrite::Register(r, _) if *r == rindx))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.writes.len() * std::mem::size_of::<JournalWrite>()
+    }
+}
+
+/// The undo log of a [`crate::VM`], holding the latest executed instructions
+/// within a memory capacity. The oldest entries are dropped to make room.
+#[derive(Debug, Clone)]
+pub struct Journal {
+    entries: VecDeque<JournalEntry>,
+    pending: Vec<JournalWrite>,
+    capacity: usize,
+    size: usize,
+}
+
+impl Journal {
+    pub(crate) fn make(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            pending: vec![],
+            capacity,
+            size: 0,
+        }
+    }
+
+    /// Returns the number of bytes the entries may use.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns an estimate of the number of bytes used by the entries.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true when there is nothing to step back.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entries, the oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &JournalEntry> {
+        self.entries.iter()
+    }
+
+    pub(crate) fn begin(&mut self) {
+        self.pending.clear();
+    }
+
+    pub(crate) fn write(&mut self, write: JournalWrite) {
+        self.pending.push(write);
+    }
+
+    pub(crate) fn commit(&mut self, pc: usize, instruction: Instruction) {
+        let entry = JournalEntry {
+            pc,
+            instruction,
+            writes: std::mem::take(&mut self.pending),
+        };
+
+        self.size += entry.size();
+        self.entries.push_back(entry);
+
+        while self.size > self.capacity {
+            match self.entries.pop_front() {
+                Some(entry) => self.size -= entry.size(),
+                None => break,
+            }
+        }
+    }
+
+    /// Commits the writes of a faulting instruction, if it did any,
+    /// so they can be reverted like those of a completed one.
+    pub(crate) fn commit_fault(&mut self, pc: usize, instruction: Instruction) {
+        if !self.pending.is_empty() {
+            self.commit(pc, instruction);
+        }
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<JournalEntry> {
+        let entry = self.entries.pop_back()?;
+        self.size -= entry.size();
+        Some(entry)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.pending.clear();
+        self.size = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::{Load, Operand16};
+
+    use super::*;
+
+    fn make_load(r: u8, v: u16) -> Instruction {
+        Instruction::LoadI(Load::make(RIndex::make(r), Operand16::make(v)))
+    }
+
+    #[test]
+    fn commit() {
+        let mut journal = Journal::make(1024);
+        journal.begin();
+        journal.write(JournalWrite::Register(RIndex::make(1), 7));
+        journal.write(JournalWrite::Memory(100, 8));
+        journal.commit(3, make_load(1, 10));
+
+        assert_eq!(1, journal.len());
+        let entry = journal.pop().unwrap();
+        assert_eq!(3, entry.pc());
+        assert_eq!(
+            &[
+                JournalWrite::Register(RIndex::make(1), 7),
+                JournalWrite::Memory(100, 8)
+            ],
+            entry.writes()
+        );
+        assert!(entry.writes_register(RIndex::make(1)));
+        assert!(!entry.writes_register(RIndex::make(2)));
+        assert_eq!(0, journal.size());
+    }
+
+    #[test]
+    fn capacity() {
+        let mut journal = Journal::make(0);
+        journal.write(JournalWrite::Register(RIndex::make(1), 7));
+        journal.commit(0, make_load(1, 10));
+        assert!(journal.is_empty());
+
+        let entry_size = std::mem::size_of::<JournalEntry>() + std::mem::size_of::<JournalWrite>();
+        let mut journal = Journal::make(2 * entry_size);
+        for pc in 0..5 {
+            journal.begin();
+            journal.write(JournalWrite::Register(RIndex::make(1), pc as u16));
+            journal.commit(pc, make_load(1, 10));
+        }
+
+        assert_eq!(2, journal.len());
+        assert_eq!(3, journal.iter().next().unwrap().pc());
+        assert!(journal.size() <= journal.capacity());
+    }
+
+    #[test]
+    fn commit_fault() {
+        let mut journal = Journal::make(1024);
+        journal.begin();
+        journal.commit_fault(0, make_load(1, 10));
+        assert!(journal.is_empty());
+
+        journal.write(JournalWrite::Register(RIndex::make(1), 7));
+        journal.commit_fault(1, make_load(1, 10));
+        assert_eq!(1, journal.len());
+    }
+}
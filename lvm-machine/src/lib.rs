@@ -1,5 +1,33 @@
+mod batch;
+mod config;
+mod console;
+mod device;
+mod error;
+mod image;
+mod interrupt;
+mod journal;
+mod mailbox;
 mod pbytes;
+mod scheduler;
+mod snapshot;
+mod syscall;
+mod verify;
+mod vfs;
 mod vm;
 
+pub use batch::*;
+pub use config::*;
+pub use console::*;
+pub use device::*;
+pub use error::*;
+pub use image::*;
+pub use interrupt::*;
+pub use journal::*;
+pub use mailbox::*;
 pub use pbytes::*;
+pub use scheduler::*;
+pub use snapshot::*;
+pub use syscall::*;
+pub use verify::*;
+pub use vfs::*;
 pub use vm::*;
@@ -0,0 +1,115 @@
+use std::collections::VecDeque;
+
+use crate::VMError;
+
+/// A message sent by a `SEND` instruction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Message {
+    sender: u16,
+    value: u16,
+}
+
+impl Message {
+    /// Creates a [`Message`] instance.
+    pub fn make(sender: u16, value: u16) -> Self {
+        Self { sender, value }
+    }
+
+    /// Returns the id of the sending process.
+    pub const fn sender(&self) -> u16 {
+        self.sender
+    }
+
+    /// Returns the sent value.
+    pub const fn value(&self) -> u16 {
+        self.value
+    }
+}
+
+/// The bounded queue of the messages received by a [`crate::VM`], the oldest first.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Mailbox {
+    messages: VecDeque<Message>,
+    capacity: usize,
+}
+
+impl Mailbox {
+    pub(crate) fn make(capacity: usize) -> Self {
+        Self {
+            messages: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Returns the number of messages the mailbox holds.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns true when there is no message.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Returns true when no more messages are accepted.
+    pub fn is_full(&self) -> bool {
+        self.messages.len() >= self.capacity
+    }
+
+    /// Returns the messages, the oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Message> {
+        self.messages.iter()
+    }
+
+    pub(crate) fn push(&mut self, message: Message) -> Result<(), VMError> {
+        if self.is_full() {
+            return Err(VMError::MailboxFull);
+        }
+
+        self.messages.push_back(message);
+        Ok(())
+    }
+
+    pub(crate) fn pop(&mut self) -> Result<Message, VMError> {
+        self.messages.pop_front().ok_or(VMError::EmptyMailbox)
+    }
+
+    /// Puts back a message taken by [`Mailbox::pop`].
+    pub(crate) fn unpop(&mut self, message: Message) {
+        self.messages.push_front(message);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.messages.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded() {
+        let mut mailbox = Mailbox::make(2);
+        mailbox.push(Message::make(1, 10)).unwrap();
+        mailbox.push(Message::make(2, 20)).unwrap();
+        assert!(mailbox.is_full());
+        assert_eq!(
+            Err(VMError::MailboxFull),
+            mailbox.push(Message::make(3, 30))
+        );
+
+        let message = mailbox.pop().unwrap();
+        assert_eq!((1, 10), (message.sender(), message.value()));
+        mailbox.unpop(message);
+        assert_eq!(2, mailbox.len());
+
+        mailbox.clear();
+        assert_eq!(Err(VMError::EmptyMailbox), mailbox.pop());
+    }
+}
@@ -8,7 +8,7 @@ impl PBytes {
         Self(bytes)
     }
 
-    pub fn iter(&self) -> PBytesIter {
+    pub fn iter(&self) -> PBytesIter<'_> {
         PBytesIter {
             bytes: &self.0,
             cur: 0,
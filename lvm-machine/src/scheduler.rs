@@ -0,0 +1,547 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use lvm_core::{Instruction, Program, RIndex};
+
+use crate::{Interrupts, Snapshot, VMConfig, VMError, VM};
+
+/// A process requested by a `SPAWN` instruction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SpawnRequest {
+    entry: usize,
+    pid: RIndex,
+}
+
+impl SpawnRequest {
+    pub(crate) fn make(entry: usize, pid: RIndex) -> Self {
+        Self { entry, pid }
+    }
+
+    /// Returns the instruction address the process starts at.
+    pub const fn entry(&self) -> usize {
+        self.entry
+    }
+
+    /// Returns the register of the parent receiving the process id.
+    pub const fn pid(&self) -> RIndex {
+        self.pid
+    }
+}
+
+/// The status of a process.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ProcessStatus {
+    /// The process waits for its next slice.
+    Ready,
+    /// The process waits for a message to receive, or for room in the mailbox
+    /// of the process it sends to.
+    Blocked,
+    /// The process was blocked when every other running process was blocked too.
+    Deadlocked,
+    /// The program of the process is done.
+    Done,
+    /// The process stopped on a fault it did not handle.
+    Faulted(VMError),
+}
+
+/// A machine run by a [`Scheduler`].
+pub struct Process {
+    pid: u16,
+    parent: Option<u16>,
+    vm: VM,
+    status: ProcessStatus,
+    steps: usize,
+}
+
+impl Process {
+    /// Returns the process id.
+    pub const fn pid(&self) -> u16 {
+        self.pid
+    }
+
+    /// Returns the id of the process which spawned it, `None` for the processes spawned by the host.
+    pub const fn parent(&self) -> Option<u16> {
+        self.parent
+    }
+
+    /// Returns the machine of the process.
+    pub fn vm(&self) -> &VM {
+        &self.vm
+    }
+
+    /// Returns the status.
+    pub const fn status(&self) -> ProcessStatus {
+        self.status
+    }
+
+    /// Returns the number of executed instructions.
+    pub const fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns true when the process is ready or blocked.
+    pub const fn is_running(&self) -> bool {
+        matches!(self.status, ProcessStatus::Ready | ProcessStatus::Blocked)
+    }
+}
+
+type Setup = Box<dyn FnMut(u16, &mut VM) + Send>;
+
+/// Runs many machines by interleaving them, round-robin, a quantum of instructions at a time.
+///
+/// A `SPAWN` instruction starts a process with a copy of the registers and memory
+/// of its parent, at the requested instruction address of the same program.
+/// The parent receives the id of the new process, the new process sees 0.
+/// The ids start at 1; when they are exhausted, the parent sees 0 too.
+///
+/// A `SEND` instruction delivers a message to the mailbox of a running process;
+/// sending to any other process faults the sender, like any other instruction,
+/// so its `DEVICE` handler may catch it. A process blocks on `RECV`
+/// while its mailbox is empty and on `SEND` while the mailbox of the receiver
+/// is full. When every running process is blocked, they are all deadlocked.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_core::{Instruction, Load, Operand16, Program, RIndex};
+/// use lvm_machine::{ProcessStatus, Scheduler};
+///
+/// let load = Instruction::LoadI(Load::make(RIndex::make(1), Operand16::make(10)));
+/// let mut scheduler = Scheduler::make(2);
+/// let pid = scheduler.spawn(Program::make(vec![load, load, load]));
+///
+/// scheduler.run();
+/// assert_eq!(Some(ProcessStatus::Done), scheduler.status(pid));
+/// ```
+pub struct Scheduler {
+    config: VMConfig,
+    quantum: usize,
+    processes: BTreeMap<u16, Process>,
+    queue: VecDeque<u16>,
+    next_pid: Option<u16>,
+    setup: Option<Setup>,
+}
+
+impl Scheduler {
+    pub const DEFAULT_QUANTUM: usize = 100;
+
+    /// Creates a [`Scheduler`] instance running `quantum` instructions per slice, at least one.
+    pub fn make(quantum: usize) -> Self {
+        Self {
+            config: VMConfig::default(),
+            quantum: quantum.max(1),
+            processes: BTreeMap::new(),
+            queue: VecDeque::new(),
+            next_pid: Some(1),
+            setup: None,
+        }
+    }
+
+    /// Sets the configuration of the machines spawned by the host.
+    /// The spawned processes inherit the configuration of their parent.
+    pub fn with_config(mut self, config: VMConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets a function called with every new machine, before it runs,
+    /// e.g. to register syscalls or to replace the devices.
+    pub fn with_setup<F>(mut self, setup: F) -> Self
+    where
+        F: FnMut(u16, &mut VM) + Send + 'static,
+    {
+        self.setup = Some(Box::new(setup));
+        self
+    }
+
+    /// Returns the number of instructions per slice.
+    pub const fn quantum(&self) -> usize {
+        self.quantum
+    }
+
+    /// Starts a process running the program and returns its id,
+    /// or 0 when the ids are exhausted.
+    pub fn spawn(&mut self, program: Program) -> u16 {
+        let mut vm = VM::with_config(self.config);
+        vm.load_program(program);
+        self.add(None, vm)
+    }
+
+    /// Returns the status of a process.
+    pub fn status(&self, pid: u16) -> Option<ProcessStatus> {
+        self.processes.get(&pid).map(Process::status)
+    }
+
+    /// Returns a process.
+    pub fn process(&self, pid: u16) -> Option<&Process> {
+        self.processes.get(&pid)
+    }
+
+    /// Returns the processes, by id.
+    pub fn processes(&self) -> impl Iterator<Item = &Process> {
+        self.processes.values()
+    }
+
+    /// Returns true when no process is running.
+    pub fn is_done(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns the ids of the deadlocked processes.
+    pub fn deadlocked(&self) -> Vec<u16> {
+        self.processes()
+            .filter(|p| p.status == ProcessStatus::Deadlocked)
+            .map(Process::pid)
+            .collect()
+    }
+
+    /// Runs a slice of the next process which is not blocked.
+    /// Returns the id of the process or `None` if no process can run.
+    pub fn run_slice(&mut self) -> Option<u16> {
+        let pid = self.next_runnable()?;
+        let peers = self
+            .processes()
+            .filter(|p| p.is_running())
+            .map(Process::pid)
+            .collect();
+        if let Some(process) = self.processes.get_mut(&pid) {
+            process.vm.set_peers(Some(peers));
+        }
+
+        for _ in 0..self.quantum {
+            if self.is_blocked(pid) {
+                self.set_status(pid, ProcessStatus::Blocked);
+                break;
+            }
+
+            let process = self.processes.get_mut(&pid)?;
+            process.status = ProcessStatus::Ready;
+            match process.vm.step() {
+                Ok(Some(_)) => process.steps += 1,
+                Ok(None) => process.status = ProcessStatus::Done,
+                Err(err) => process.status = ProcessStatus::Faulted(err),
+            }
+
+            if process.status == ProcessStatus::Ready && process.vm.is_done() {
+                process.status = ProcessStatus::Done;
+            }
+
+            let spawn = process.vm.take_spawn();
+            if let Some((target, value)) = process.vm.take_message() {
+                self.post(pid, target, value);
+            }
+
+            // The slice ends with a spawn, so the new process is queued before its parent.
+            if let Some(request) = spawn {
+                self.fork(pid, request);
+                break;
+            }
+
+            if self.status(pid) != Some(ProcessStatus::Ready) {
+                break;
+            }
+        }
+
+        if self.process(pid).is_some_and(Process::is_running) {
+            self.queue.push_back(pid);
+        }
+
+        Some(pid)
+    }
+
+    /// Runs slices until no process can run.
+    pub fn run(&mut self) {
+        while self.run_slice().is_some() {}
+    }
+
+    /// Takes the next queued process which is not blocked.
+    /// When all are blocked, they are deadlocked and removed from the queue.
+    fn next_runnable(&mut self) -> Option<u16> {
+        for _ in 0..self.queue.len() {
+            let pid = self.queue.pop_front()?;
+            if !self.is_blocked(pid) {
+                return Some(pid);
+            }
+
+            self.set_status(pid, ProcessStatus::Blocked);
+            self.queue.push_back(pid);
+        }
+
+        while let Some(pid) = self.queue.pop_front() {
+            self.set_status(pid, ProcessStatus::Deadlocked);
+        }
+        None
+    }
+
+    /// Returns true when the next instruction of the process would block.
+    fn is_blocked(&self, pid: u16) -> bool {
+        let vm = match self.processes.get(&pid) {
+            Some(process) => &process.vm,
+            None => return false,
+        };
+
+        match vm.program().get(vm.pc()) {
+            Some(Instruction::RecvI(_)) => vm.mailbox().is_empty(),
+            Some(Instruction::SendI(send)) => vm.register(send.target()).is_ok_and(|target| {
+                self.processes
+                    .get(&target)
+                    .is_some_and(|p| p.is_running() && p.vm.mailbox().is_full())
+            }),
+            _ => false,
+        }
+    }
+
+    /// Delivers a message. The machine of the sender checked the target is running
+    /// and the sender did not run while its mailbox was full.
+    fn post(&mut self, sender: u16, target: u16, value: u16) {
+        if let Some(process) = self.processes.get_mut(&target) {
+            let _ = process.vm.deliver(sender, value);
+        }
+    }
+
+    fn set_status(&mut self, pid: u16, status: ProcessStatus) {
+        if let Some(process) = self.processes.get_mut(&pid) {
+            process.status = status;
+        }
+    }
+
+    fn fork(&mut self, parent: u16, request: SpawnRequest) {
+        let vm = match self.processes.get(&parent) {
+            Some(process) => &process.vm,
+            None => return,
+        };
+
+        let mut child = VM::with_config(*vm.config());
+        child.load_program(vm.program().clone());
+        child.restore(&Snapshot::make(
+            vm.registers().to_vec(),
+            vm.memory().to_vec(),
+            request.entry(),
+            Interrupts::default(),
+        ));
+
+        let pid = self.add(Some(parent), child);
+        if let Some(process) = self.processes.get_mut(&parent) {
+            let _ = process.vm.set_register(request.pid(), pid);
+        }
+    }
+
+    fn add(&mut self, parent: Option<u16>, mut vm: VM) -> u16 {
+        let pid = match self.next_pid {
+            Some(pid) => pid,
+            None => return 0,
+        };
+        self.next_pid = pid.checked_add(1);
+
+        if let Some(setup) = &mut self.setup {
+            setup(pid, &mut vm);
+        }
+
+        let status = if vm.is_done() {
+            ProcessStatus::Done
+        } else {
+            self.queue.push_back(pid);
+            ProcessStatus::Ready
+        };
+
+        self.processes.insert(
+            pid,
+            Process {
+                pid,
+                parent,
+                vm,
+                status,
+                steps: 0,
+            },
+        );
+        pid
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::make(Self::DEFAULT_QUANTUM)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::{
+        Add, Div, Halt, Iret, Ivec, Load, Operand16, Operand8, Prti, RecvMsg, SendMsg, Spawn,
+    };
+
+    use super::*;
+    use crate::{Console, OutputBuffer};
+
+    fn r(i: u8) -> RIndex {
+        RIndex::make(i)
+    }
+
+    fn load(i: u8, v: u16) -> Instruction {
+        Instruction::LoadI(Load::make(r(i), Operand16::make(v)))
+    }
+
+    fn prti(i: u8) -> Instruction {
+        Instruction::PrtiI(Prti::make(r(i)))
+    }
+
+    fn with_output(quantum: usize) -> (Scheduler, OutputBuffer) {
+        let output = OutputBuffer::default();
+        let buffer = output.clone();
+        let scheduler = Scheduler::make(quantum).with_setup(move |_, vm| {
+            vm.set_console(Console::make(
+                Box::new("".as_bytes()),
+                Box::new(buffer.clone()),
+            ))
+        });
+        (scheduler, output)
+    }
+
+    #[test]
+    fn interleave() {
+        let (mut scheduler, output) = with_output(2);
+        let a = scheduler.spawn(Program::make(vec![load(1, 1), prti(1), prti(1), prti(1)]));
+        let b = scheduler.spawn(Program::make(vec![load(1, 2), prti(1), prti(1)]));
+
+        scheduler.run();
+        assert_eq!("12112", output.to_string());
+        assert_eq!(Some(ProcessStatus::Done), scheduler.status(a));
+        assert_eq!(3, scheduler.process(b).unwrap().steps());
+        assert!(scheduler.is_done());
+    }
+
+    #[test]
+    fn spawn() {
+        let (mut scheduler, output) = with_output(10);
+        let parent = scheduler.spawn(Program::make(vec![
+            load(0, 5),
+            load(1, 7),
+            Instruction::SpawnI(Spawn::make(r(0), r(2))),
+            prti(2),
+            Instruction::HaltI(Halt::make()),
+            prti(2),
+            prti(1),
+        ]));
+
+        scheduler.run();
+        // The slice of the parent ends with the spawn, the child runs first.
+        assert_eq!("072", output.to_string());
+
+        let child = scheduler.process(2).unwrap();
+        assert_eq!(Some(parent), child.parent());
+        assert_eq!(Ok(7), child.vm().register(r(1)));
+        assert_eq!(2, child.steps());
+        assert_eq!(2, scheduler.processes().count());
+    }
+
+    #[test]
+    fn faults() {
+        let mut scheduler = Scheduler::default();
+        let div = Instruction::DivI(Div::make(r(1), r(2), r(3)));
+        let faulted = scheduler.spawn(Program::make(vec![div, load(1, 1)]));
+        let add = Instruction::AddI(Add::make(r(1), r(2), r(3)));
+        let done = scheduler.spawn(Program::make(vec![add]));
+        let empty = scheduler.spawn(Program::default());
+
+        assert_eq!(Some(faulted), scheduler.run_slice());
+        assert_eq!(
+            Some(ProcessStatus::Faulted(VMError::DivisionByZero)),
+            scheduler.status(faulted)
+        );
+        assert_eq!(0, scheduler.process(faulted).unwrap().vm().pc());
+
+        scheduler.run();
+        assert_eq!(Some(ProcessStatus::Done), scheduler.status(done));
+        assert_eq!(Some(ProcessStatus::Done), scheduler.status(empty));
+        assert_eq!(None, scheduler.status(100));
+    }
+
+    fn send(target: u8, value: u8) -> Instruction {
+        Instruction::SendI(SendMsg::make(r(target), r(value)))
+    }
+
+    fn recv(sender: u8, value: u8) -> Instruction {
+        Instruction::RecvI(RecvMsg::make(r(sender), r(value)))
+    }
+
+    #[test]
+    fn messages() {
+        let (mut scheduler, output) = with_output(1);
+        let client = scheduler.spawn(Program::make(vec![
+            load(0, 2),
+            load(1, 40),
+            send(0, 1),
+            recv(2, 3),
+            prti(3),
+            prti(2),
+        ]));
+        let server = scheduler.spawn(Program::make(vec![
+            recv(0, 1),
+            load(2, 2),
+            Instruction::AddI(Add::make(r(1), r(2), r(1))),
+            send(0, 1),
+        ]));
+
+        scheduler.run();
+        assert_eq!("422", output.to_string());
+        assert_eq!(Some(ProcessStatus::Done), scheduler.status(client));
+        assert_eq!(Some(ProcessStatus::Done), scheduler.status(server));
+        assert!(scheduler.deadlocked().is_empty());
+    }
+
+    #[test]
+    fn bounded() {
+        let mut scheduler = Scheduler::make(10).with_config(VMConfig::default().with_mailbox(1));
+        let sender = scheduler.spawn(Program::make(vec![load(0, 2), send(0, 1), send(0, 1)]));
+        let receiver = scheduler.spawn(Program::make(vec![load(0, 0), recv(1, 2), recv(1, 2)]));
+
+        // The second message waits for room in the mailbox of the receiver.
+        assert_eq!(Some(sender), scheduler.run_slice());
+        assert_eq!(Some(ProcessStatus::Blocked), scheduler.status(sender));
+        assert_eq!(2, scheduler.process(sender).unwrap().steps());
+
+        scheduler.run();
+        assert_eq!(Some(ProcessStatus::Done), scheduler.status(sender));
+        assert_eq!(Some(ProcessStatus::Done), scheduler.status(receiver));
+        assert_eq!(
+            Ok(1),
+            scheduler.process(receiver).unwrap().vm().register(r(1))
+        );
+    }
+
+    #[test]
+    fn deadlock() {
+        let mut scheduler = Scheduler::default();
+        let a = scheduler.spawn(Program::make(vec![recv(0, 1)]));
+        let b = scheduler.spawn(Program::make(vec![load(0, 1), recv(0, 1)]));
+        let c = scheduler.spawn(Program::make(vec![load(0, 9), send(0, 0)]));
+
+        scheduler.run();
+        assert_eq!(vec![a, b], scheduler.deadlocked());
+        assert_eq!(
+            Some(ProcessStatus::Faulted(VMError::InvalidProcess(9))),
+            scheduler.status(c)
+        );
+        assert!(scheduler.is_done());
+    }
+
+    #[test]
+    fn send_handler() {
+        let (mut scheduler, output) = with_output(10);
+        let vector = Operand8::make(Interrupts::DEVICE);
+        let pid = scheduler.spawn(Program::make(vec![
+            Instruction::IvecI(Ivec::make(vector, Operand16::make(4))),
+            load(0, 9),
+            send(0, 0),
+            Instruction::HaltI(Halt::make()),
+            load(1, 7),
+            prti(1),
+            Instruction::IretI(Iret::make()),
+        ]));
+
+        // The failed send enters the handler, which returns after it.
+        scheduler.run();
+        assert_eq!("7", output.to_string());
+        assert_eq!(Some(ProcessStatus::Done), scheduler.status(pid));
+        assert_eq!(7, scheduler.process(pid).unwrap().steps());
+    }
+}
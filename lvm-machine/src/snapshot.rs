@@ -0,0 +1,47 @@
+use crate::Interrupts;
+
+/// The mutable state of a [`crate::VM`], which can be restored later.
+/// The loaded program and the devices are not part of the snapshot.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Snapshot {
+    registers: Vec<u16>,
+    memory: Vec<u8>,
+    pc: usize,
+    interrupts: Interrupts,
+}
+
+impl Snapshot {
+    pub(crate) fn make(
+        registers: Vec<u16>,
+        memory: Vec<u8>,
+        pc: usize,
+        interrupts: Interrupts,
+    ) -> Self {
+        Self {
+            registers,
+            memory,
+            pc,
+            interrupts,
+        }
+    }
+
+    /// Returns the values of the registers.
+    pub fn registers(&self) -> &[u16] {
+        &self.registers
+    }
+
+    /// Returns the memory.
+    pub fn memory(&self) -> &[u8] {
+        &self.memory
+    }
+
+    /// Returns the position of the next instruction to be executed.
+    pub const fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the interrupt vector table with the running handlers.
+    pub const fn interrupts(&self) -> &Interrupts {
+        &self.interrupts
+    }
+}
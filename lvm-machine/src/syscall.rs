@@ -0,0 +1,215 @@
+use std::{collections::BTreeMap, future::Future, pin::Pin};
+
+use lvm_core::RIndex;
+
+use crate::{Journal, JournalWrite, VMError};
+
+/// The access of a syscall handler to the state of the machine.
+/// The writes are recorded in the undo log like the writes of any instruction.
+pub struct VmContext<'a> {
+    number: u16,
+    registers: &'a mut [u16],
+    memory: &'a mut [u8],
+    journal: Option<&'a mut Journal>,
+}
+
+impl<'a> VmContext<'a> {
+    pub(crate) fn make(
+        number: u16,
+        registers: &'a mut [u16],
+        memory: &'a mut [u8],
+        journal: Option<&'a mut Journal>,
+    ) -> Self {
+        Self {
+            number,
+            registers,
+            memory,
+            journal,
+        }
+    }
+
+    /// Returns the number of the running syscall.
+    pub const fn number(&self) -> u16 {
+        self.number
+    }
+
+    /// Returns the values of the registers.
+    pub fn registers(&self) -> &[u16] {
+        self.registers
+    }
+
+    /// Returns the value of a register.
+    pub fn register(&self, rindx: RIndex) -> Result<u16, VMError> {
+        let idx: u8 = rindx.into();
+        self.registers
+            .get(idx as usize)
+            .copied()
+            .ok_or(VMError::InvalidRegister(rindx))
+    }
+
+    /// Sets the value of a register.
+    pub fn set_register(&mut self, rindx: RIndex, value: u16) -> Result<(), VMError> {
+        let idx: u8 = rindx.into();
+        let register = self
+            .registers
+            .get_mut(idx as usize)
+            .ok_or(VMError::InvalidRegister(rindx))?;
+
+        let old = std::mem::replace(register, value);
+        if let Some(journal) = &mut self.journal {
+            journal.write(JournalWrite::Register(rindx, old));
+        }
+        Ok(())
+    }
+
+    /// Returns the whole memory.
+    pub fn memory(&self) -> &[u8] {
+        self.memory
+    }
+
+    /// Returns the bytes of memory starting at the address.
+    pub fn read(&self, addr: usize, len: usize) -> Result<&[u8], VMError> {
+        addr.checked_add(len)
+            .and_then(|end| self.memory.get(addr..end))
+            .ok_or(VMError::InvalidAddress(addr))
+    }
+
+    /// Writes the bytes to memory starting at the address.
+    pub fn write(&mut self, addr: usize, bytes: &[u8]) -> Result<(), VMError> {
+        let range = addr
+            .checked_add(bytes.len())
+            .and_then(|end| self.memory.get_mut(addr..end))
+            .ok_or(VMError::InvalidAddress(addr))?;
+
+        if let Some(journal) = &mut self.journal {
+            for (i, old) in range.iter().enumerate() {
+                journal.write(JournalWrite::Memory(addr + i, *old));
+            }
+        }
+
+        range.copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// A host function called by the `SYSCALL` instruction.
+pub type SyscallHandler = Box<dyn Fn(&mut VmContext) -> Result<(), VMError> + Send>;
+
+/// Applies the result of an async syscall to the machine once its future completes.
+pub type SyscallCompletion = Box<dyn FnOnce(&mut VmContext) -> Result<(), VMError> + Send>;
+
+/// The host future of an async syscall. It owns what it needs, so the machine
+/// stays free while it is pending.
+pub type SyscallFuture = Pin<Box<dyn Future<Output = Result<SyscallCompletion, VMError>> + Send>>;
+
+/// A host function called by the `SYSCALL` instruction in the async mode,
+/// see [`crate::VM::run_async`]. It reads its arguments and returns a future.
+pub type AsyncSyscallHandler = Box<dyn Fn(&VmContext) -> SyscallFuture + Send>;
+
+/// The syscall handlers of a [`crate::VM`], by number.
+#[derive(Default)]
+pub struct Syscalls {
+    handlers: BTreeMap<u16, SyscallHandler>,
+    async_handlers: BTreeMap<u16, AsyncSyscallHandler>,
+}
+
+impl Syscalls {
+    /// Registers the handler of a syscall number, replacing any previous one.
+    pub fn register<F>(&mut self, number: u16, handler: F)
+    where
+        F: Fn(&mut VmContext) -> Result<(), VMError> + Send + 'static,
+    {
+        self.async_handlers.remove(&number);
+        self.handlers.insert(number, Box::new(handler));
+    }
+
+    /// Registers the async handler of a syscall number, replacing any previous one.
+    pub fn register_async<F>(&mut self, number: u16, handler: F)
+    where
+        F: Fn(&VmContext) -> SyscallFuture + Send + 'static,
+    {
+        self.handlers.remove(&number);
+        self.async_handlers.insert(number, Box::new(handler));
+    }
+
+    /// Returns true when there is a handler for the syscall number.
+    pub fn contains(&self, number: u16) -> bool {
+        self.handlers.contains_key(&number) || self.is_async(number)
+    }
+
+    /// Returns true when the handler of the syscall number is async.
+    pub fn is_async(&self, number: u16) -> bool {
+        self.async_handlers.contains_key(&number)
+    }
+
+    /// Returns the registered syscall numbers, in ascending order.
+    pub fn numbers(&self) -> impl Iterator<Item = u16> + '_ {
+        let mut numbers: Vec<u16> = self
+            .handlers
+            .keys()
+            .chain(self.async_handlers.keys())
+            .copied()
+            .collect();
+        numbers.sort_unstable();
+        numbers.into_iter()
+    }
+
+    pub(crate) fn get(&self, number: u16) -> Result<&SyscallHandler, VMError> {
+        if self.is_async(number) {
+            return Err(VMError::AsyncSyscall(number));
+        }
+
+        self.handlers
+            .get(&number)
+            .ok_or(VMError::UnknownSyscall(number))
+    }
+
+    pub(crate) fn get_async(&self, number: u16) -> Result<&AsyncSyscallHandler, VMError> {
+        self.async_handlers
+            .get(&number)
+            .ok_or(VMError::UnknownSyscall(number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn context() {
+        let mut registers = [0u16; 4];
+        let mut memory = [0u8; 8];
+        let mut journal = Journal::make(1024);
+        let mut ctx = VmContext::make(7, &mut registers, &mut memory, Some(&mut journal));
+
+        assert_eq!(7, ctx.number());
+        ctx.set_register(RIndex::make(1), 42).unwrap();
+        assert_eq!(Ok(42), ctx.register(RIndex::make(1)));
+        assert!(ctx.set_register(RIndex::make(4), 1).is_err());
+
+        ctx.write(6, &[1, 2]).unwrap();
+        assert_eq!(Ok([1u8, 2u8].as_slice()), ctx.read(6, 2));
+        assert_eq!(Err(VMError::InvalidAddress(7)), ctx.write(7, &[1, 2]));
+        assert_eq!(
+            Err(VMError::InvalidAddress(usize::MAX)),
+            ctx.read(usize::MAX, 2)
+        );
+    }
+
+    #[test]
+    fn registry() {
+        let mut syscalls = Syscalls::default();
+        syscalls.register(2, |_| Ok(()));
+        syscalls.register(1, |_| Err(VMError::SyscallFailed(5)));
+        syscalls.register_async(3, |_| Box::pin(async { Err(VMError::SyscallFailed(1)) }));
+
+        assert!(syscalls.contains(1));
+        assert!(syscalls.is_async(3));
+        assert_eq!(vec![1, 2, 3], syscalls.numbers().collect::<Vec<_>>());
+        assert!(matches!(syscalls.get(3), Err(VMError::AsyncSyscall(3))));
+        assert!(matches!(syscalls.get(4), Err(VMError::UnknownSyscall(4))));
+
+        syscalls.register(3, |_| Ok(()));
+        assert!(!syscalls.is_async(3));
+    }
+}
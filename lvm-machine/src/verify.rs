@@ -0,0 +1,332 @@
+//! The static checks of a program before it runs.
+//!
+//! The instruction set has no jumps, calls or stack, so the checks asked for them
+//! map to the interrupt handlers: the "jump targets on instruction boundaries" are
+//! the `IVEC` handler addresses, and the "balanced stack depth" is the handler
+//! nesting depth, which must be the same on every path reaching an instruction.
+//! A `SPAWN` entry is held by a register, so it is not verified here: the machine
+//! checks it when the instruction runs.
+
+use std::fmt::Display;
+
+use lvm_core::{Instruction, Program, RIndex};
+
+use crate::{Interrupts, VMConfig};
+
+/// The kinds of problems found by the [`Verifier`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ViolationKind {
+    /// The instruction refers a register beyond the register count of the machine.
+    InvalidRegister(RIndex),
+    /// The `IVEC` handler address is not the offset of an instruction.
+    InvalidTarget(u16),
+    /// The `IVEC` vector is beyond the vector table.
+    InvalidVector(u8),
+    /// `IRET` is reachable outside an interrupt handler.
+    IretOutsideHandler,
+    /// A handler runs past the end of the program without `IRET`.
+    MissingIret,
+    /// The instruction is reached with different handler depths.
+    DepthMismatch { expected: usize, found: usize },
+}
+
+/// A problem found by the [`Verifier`] at an instruction offset.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Violation {
+    offset: usize,
+    kind: ViolationKind,
+}
+
+impl Violation {
+    /// Creates a [`Violation`] instance.
+    pub fn make(offset: usize, kind: ViolationKind) -> Self {
+        Self { offset, kind }
+    }
+
+    /// Returns the offset of the instruction.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the kind of the problem.
+    pub const fn kind(&self) -> ViolationKind {
+        self.kind
+    }
+}
+
+impl Display for ViolationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidRegister(rindx) => write!(f, "invalid register {}", rindx),
+            Self::InvalidTarget(address) => {
+                write!(f, "handler address {} is outside the program", address)
+            }
+            Self::InvalidVector(vector) => write!(f, "invalid interrupt vector {}", vector),
+            Self::IretOutsideHandler => write!(f, "IRET outside an interrupt handler"),
+            Self::MissingIret => write!(f, "interrupt handler runs past the end"),
+            Self::DepthMismatch { expected, found } => {
+                write!(f, "reached with handler depth {} and {}", expected, found)
+            }
+        }
+    }
+}
+
+/// Used to display the violation.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_core::RIndex;
+/// use lvm_machine::{Violation, ViolationKind};
+///
+/// let violation = Violation::make(3, ViolationKind::InvalidRegister(RIndex::make(9)));
+/// assert_eq!("0003: invalid register $9", violation.to_string());
+/// ```
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}: {}", self.offset, self.kind)
+    }
+}
+
+/// Checks a [`Program`] before it runs, against the configuration of the machine.
+///
+/// It checks that:
+/// - the registers are within the register count;
+/// - the `IVEC` vectors are within the table and their handlers are instructions;
+/// - the handler frames are balanced: the main code never runs `IRET`, the handlers
+///   do not run past the end of the program and no instruction is reached both from
+///   the main code and from a handler.
+///
+/// The entries of `SPAWN` are held by registers, so they are only checked when it runs.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_core::{Instruction, Iret, Load, Operand16, Program, RIndex};
+/// use lvm_machine::{VMConfig, Verifier, ViolationKind};
+///
+/// let program = Program::make(vec![
+///     Instruction::LoadI(Load::make(RIndex::make(9), Operand16::make(1))),
+///     Instruction::IretI(Iret::make()),
+/// ]);
+///
+/// let violations = Verifier::make(VMConfig::default()).verify(&program).unwrap_err();
+/// assert_eq!(2, violations.len());
+/// assert_eq!(0, violations[0].offset());
+/// assert_eq!(ViolationKind::IretOutsideHandler, violations[1].kind());
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Verifier {
+    config: VMConfig,
+}
+
+impl Verifier {
+    /// Creates a [`Verifier`] instance for machines with the configuration.
+    pub fn make(config: VMConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns all the violations of the program, ordered by offset.
+    pub fn verify(&self, program: &Program) -> Result<(), Vec<Violation>> {
+        let mut violations = vec![];
+        let mut handlers = vec![];
+
+        for (offset, instruction) in program.iter().enumerate() {
+            let registers = instruction.reads().into_iter().chain(instruction.writes());
+            for rindx in registers {
+                let idx: u8 = rindx.into();
+                if idx as usize >= self.config.registers() {
+                    violations.push(Violation::make(
+                        offset,
+                        ViolationKind::InvalidRegister(rindx),
+                    ));
+                }
+            }
+
+            if let Instruction::IvecI(ivec) = instruction {
+                let vector = ivec.vector().value();
+                if vector as usize >= Interrupts::VECTORS {
+                    violations.push(Violation::make(
+                        offset,
+                        ViolationKind::InvalidVector(vector),
+                    ));
+                }
+
+                let address: u16 = ivec.address().into();
+                if (address as usize) < program.len() {
+                    handlers.push(address as usize);
+                } else {
+                    violations.push(Violation::make(
+                        offset,
+                        ViolationKind::InvalidTarget(address),
+                    ));
+                }
+            }
+        }
+
+        violations.extend(Self::check_depths(program, &handlers));
+        violations.sort_by_key(Violation::offset);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Walks the paths from the program entry, at depth 0, and from the handlers,
+    /// at depth 1, and checks that every instruction has a single depth.
+    fn check_depths(program: &Program, handlers: &[usize]) -> Vec<Violation> {
+        let mut violations = vec![];
+        let mut depths: Vec<Option<usize>> = vec![None; program.len()];
+        let mut mismatched = vec![false; program.len()];
+
+        let mut work: Vec<(usize, usize)> = handlers.iter().map(|&h| (h, 1)).collect();
+        if !program.is_empty() {
+            work.push((0, 0));
+        }
+
+        while let Some((offset, depth)) = work.pop() {
+            match depths[offset] {
+                Some(expected) if expected == depth => continue,
+                Some(expected) => {
+                    if !mismatched[offset] {
+                        mismatched[offset] = true;
+                        let kind = ViolationKind::DepthMismatch {
+                            expected,
+                            found: depth,
+                        };
+                        violations.push(Violation::make(offset, kind));
+                    }
+                    continue;
+                }
+                None => depths[offset] = Some(depth),
+            }
+
+            match program.get(offset) {
+                Some(Instruction::HaltI(_)) => (),
+                Some(Instruction::IretI(_)) => {
+                    if depth == 0 {
+                        violations.push(Violation::make(offset, ViolationKind::IretOutsideHandler));
+                    }
+                }
+                _ if offset + 1 < program.len() => work.push((offset + 1, depth)),
+                _ => {
+                    if depth > 0 {
+                        violations.push(Violation::make(offset, ViolationKind::MissingIret));
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::{Add, Halt, Iret, Ivec, Load, Operand16, Operand8};
+
+    use super::*;
+
+    fn make_ivec(vector: u8, address: u16) -> Instruction {
+        Instruction::IvecI(Ivec::make(Operand8::make(vector), Operand16::make(address)))
+    }
+
+    fn make_load(indx: u8) -> Instruction {
+        Instruction::LoadI(Load::make(RIndex::make(indx), Operand16::make(1)))
+    }
+
+    fn kinds(program: Vec<Instruction>) -> Vec<(usize, ViolationKind)> {
+        match Verifier::default().verify(&Program::make(program)) {
+            Ok(()) => vec![],
+            Err(violations) => violations.iter().map(|v| (v.offset(), v.kind())).collect(),
+        }
+    }
+
+    #[test]
+    fn valid() {
+        let program = vec![
+            make_ivec(Interrupts::TIMER, 3),
+            make_load(1),
+            Instruction::HaltI(Halt::make()),
+            make_load(2),
+            Instruction::IretI(Iret::make()),
+        ];
+        assert!(kinds(program).is_empty());
+        assert!(kinds(vec![]).is_empty());
+    }
+
+    #[test]
+    fn registers() {
+        let r = RIndex::make;
+        let program = vec![
+            make_load(7),
+            Instruction::AddI(Add::make(r(8), r(1), r(20))),
+        ];
+        assert_eq!(
+            vec![
+                (1, ViolationKind::InvalidRegister(r(8))),
+                (1, ViolationKind::InvalidRegister(r(20))),
+            ],
+            kinds(program.clone())
+        );
+
+        let config = VMConfig::default().with_registers(32);
+        assert_eq!(
+            Ok(()),
+            Verifier::make(config).verify(&Program::make(program))
+        );
+    }
+
+    #[test]
+    fn targets() {
+        let program = vec![
+            make_ivec(16, 3),
+            make_ivec(1, 4),
+            Instruction::HaltI(Halt::make()),
+            Instruction::IretI(Iret::make()),
+        ];
+        assert_eq!(
+            vec![
+                (0, ViolationKind::InvalidVector(16)),
+                (1, ViolationKind::InvalidTarget(4)),
+            ],
+            kinds(program)
+        );
+    }
+
+    #[test]
+    fn missing_iret() {
+        let program = vec![
+            make_ivec(1, 2),
+            Instruction::HaltI(Halt::make()),
+            make_load(1),
+        ];
+        assert_eq!(vec![(2, ViolationKind::MissingIret)], kinds(program));
+    }
+
+    #[test]
+    fn depths() {
+        // The main code falls into the handler.
+        let program = vec![
+            make_ivec(Interrupts::TIMER, 2),
+            make_load(1),
+            make_load(2),
+            Instruction::IretI(Iret::make()),
+        ];
+        assert_eq!(
+            vec![
+                (
+                    2,
+                    ViolationKind::DepthMismatch {
+                        expected: 0,
+                        found: 1
+                    }
+                ),
+                (3, ViolationKind::IretOutsideHandler),
+            ],
+            kinds(program)
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use std::{
+    collections::BTreeMap,
+    io::{ErrorKind, Read, Write},
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::{Device, VMError};
+
+/// How a file is opened by the `OPEN` instruction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OpenMode {
+    /// Reading an existing file.
+    Read,
+    /// Writing a file, created or truncated.
+    Write,
+    /// Writing at the end of a file, created if missing.
+    Append,
+}
+
+impl OpenMode {
+    /// Returns the mode encoded by a register value: 0 read, 1 write, 2 append.
+    pub fn from_value(value: u16) -> Result<Self, VMError> {
+        match value {
+            0 => Ok(Self::Read),
+            1 => Ok(Self::Write),
+            2 => Ok(Self::Append),
+            _ => Err(VMError::InvalidMode(value)),
+        }
+    }
+
+    /// Returns true when the mode allows reading.
+    pub const fn can_read(&self) -> bool {
+        matches!(self, Self::Read)
+    }
+
+    /// Returns true when the mode allows writing.
+    pub const fn can_write(&self) -> bool {
+        !self.can_read()
+    }
+}
+
+/// An open file of a [`FileSystem`].
+pub trait VfsFile: Read + Write + Send {}
+
+impl<T: Read + Write + Send> VfsFile for T {}
+
+/// The storage behind the [`Vfs`] device.
+pub trait FileSystem: Send {
+    /// Opens the file at the path. Permission errors are reported as
+    /// [`VMError::PermissionDenied`] and missing files as [`VMError::FileNotFound`].
+    fn open(&mut self, path: &str, mode: OpenMode) -> Result<Box<dyn VfsFile>, VMError>;
+}
+
+type Files = Arc<Mutex<BTreeMap<String, Vec<u8>>>>;
+
+/// A [`FileSystem`] keeping the files in memory, never touching the host.
+/// Clones share the same files, so the embedder can inspect what a program wrote.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFs {
+    files: Files,
+    read_only: bool,
+}
+
+impl MemoryFs {
+    /// Returns a read-only view of the same files.
+    pub fn read_only(&self) -> Self {
+        Self {
+            files: self.files.clone(),
+            read_only: true,
+        }
+    }
+
+    /// Adds or replaces a file.
+    pub fn insert(&self, path: &str, contents: &[u8]) {
+        self.lock().insert(path.to_string(), contents.to_vec());
+    }
+
+    /// Returns the contents of a file.
+    pub fn get(&self, path: &str) -> Option<Vec<u8>> {
+        self.lock().get(path).cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Vec<u8>>> {
+        self.files.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl FileSystem for MemoryFs {
+    fn open(&mut self, path: &str, mode: OpenMode) -> Result<Box<dyn VfsFile>, VMError> {
+        if self.read_only && mode.can_write() {
+            return Err(VMError::PermissionDenied);
+        }
+
+        let mut files = self.lock();
+        let pos = match mode {
+            OpenMode::Read if !files.contains_key(path) => return Err(VMError::FileNotFound),
+            OpenMode::Read => 0,
+            OpenMode::Write => {
+                files.insert(path.to_string(), vec![]);
+                0
+            }
+            OpenMode::Append => files.entry(path.to_string()).or_default().len(),
+        };
+
+        Ok(Box::new(MemoryFile {
+            files: self.files.clone(),
+            path: path.to_string(),
+            pos,
+        }))
+    }
+}
+
+struct MemoryFile {
+    files: Files,
+    path: String,
+    pos: usize,
+}
+
+impl Read for MemoryFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let files = self.files.lock().unwrap_or_else(|e| e.into_inner());
+        let data = files.get(&self.path).map_or(&[][..], |d| d.as_slice());
+        let rest = data.get(self.pos..).unwrap_or_default();
+        let len = rest.len().min(buf.len());
+        buf[..len].copy_from_slice(&rest[..len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+impl Write for MemoryFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut files = self.files.lock().unwrap_or_else(|e| e.into_inner());
+        let data = files.entry(self.path.clone()).or_default();
+        let end = self.pos + buf.len();
+        if data.len() < end {
+            data.resize(end, 0);
+        }
+        data[self.pos..end].copy_from_slice(buf);
+        self.pos = end;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A [`FileSystem`] restricted to a directory of the host.
+/// The paths are relative to the directory and may not leave it,
+/// including through the symbolic links inside it.
+#[derive(Debug, Clone)]
+pub struct HostDir {
+    root: PathBuf,
+    read_only: bool,
+}
+
+impl HostDir {
+    /// Creates a [`HostDir`] instance rooted at the directory.
+    pub fn make(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            read_only: false,
+        }
+    }
+
+    /// Refuses the files opened for writing.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    fn resolve(&self, path: &str) -> Result<PathBuf, VMError> {
+        let path = Path::new(path);
+        let mut components = path.components().peekable();
+        if components.peek().is_none() {
+            return Err(VMError::InvalidPath);
+        }
+        if !components.all(|c| matches!(c, Component::Normal(_))) {
+            return Err(VMError::PermissionDenied);
+        }
+
+        // The symbolic links are followed, so the target must be under the root.
+        let root = self.root.canonicalize().map_err(io_error)?;
+        let path = root.join(path);
+        let resolved = match path.canonicalize() {
+            Ok(resolved) => resolved,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                // A dangling link would create its target wherever it points.
+                if path.symlink_metadata().is_ok() {
+                    return Err(VMError::PermissionDenied);
+                }
+
+                let parent = path.parent().ok_or(VMError::InvalidPath)?;
+                let name = path.file_name().ok_or(VMError::InvalidPath)?;
+                parent.canonicalize().map_err(io_error)?.join(name)
+            }
+            Err(err) => return Err(io_error(err)),
+        };
+
+        match resolved.starts_with(&root) {
+            true => Ok(resolved),
+            false => Err(VMError::PermissionDenied),
+        }
+    }
+}
+
+impl FileSystem for HostDir {
+    fn open(&mut self, path: &str, mode: OpenMode) -> Result<Box<dyn VfsFile>, VMError> {
+        if self.read_only && mode.can_write() {
+            return Err(VMError::PermissionDenied);
+        }
+
+        let path = self.resolve(path)?;
+        let mut options = std::fs::OpenOptions::new();
+        match mode {
+            OpenMode::Read => options.read(true),
+            OpenMode::Write => options.write(true).create(true).truncate(true),
+            OpenMode::Append => options.append(true).create(true),
+        };
+
+        let file = options.open(path).map_err(io_error)?;
+        Ok(Box::new(file))
+    }
+}
+
+fn io_error(err: std::io::Error) -> VMError {
+    match err.kind() {
+        ErrorKind::PermissionDenied => VMError::PermissionDenied,
+        ErrorKind::NotFound => VMError::FileNotFound,
+        kind => VMError::DeviceError(kind),
+    }
+}
+
+struct Handle {
+    mode: OpenMode,
+    file: Box<dyn VfsFile>,
+}
+
+/// The file device: `OPEN`, `FREAD`, `FWRITE` and `CLOSE` work on its handles.
+/// The default device keeps the files in memory.
+pub struct Vfs {
+    fs: Box<dyn FileSystem>,
+    handles: BTreeMap<u16, Handle>,
+}
+
+impl Vfs {
+    /// Creates a [`Vfs`] instance backed by the file system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_machine::{MemoryFs, OpenMode, Vfs};
+    ///
+    /// let fs = MemoryFs::default();
+    /// let mut vfs = Vfs::make(Box::new(fs.clone()));
+    ///
+    /// let handle = vfs.open("hello.txt", OpenMode::Write).unwrap();
+    /// vfs.write(handle, b"hello").unwrap();
+    /// vfs.close(handle).unwrap();
+    ///
+    /// assert_eq!(Some(b"hello".to_vec()), fs.get("hello.txt"));
+    /// ```
+    pub fn make(fs: Box<dyn FileSystem>) -> Self {
+        Self {
+            fs,
+            handles: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of open handles.
+    pub fn open_handles(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Opens a file and returns its handle, the lowest one not in use, starting at 1.
+    pub fn open(&mut self, path: &str, mode: OpenMode) -> Result<u16, VMError> {
+        let handle = (1..=u16::MAX)
+            .find(|h| !self.handles.contains_key(h))
+            .ok_or(VMError::DeviceError(ErrorKind::OutOfMemory))?;
+
+        let file = self.fs.open(path, mode)?;
+        self.handles.insert(handle, Handle { mode, file });
+        Ok(handle)
+    }
+
+    /// Reads into the buffer and returns the number of bytes read, 0 at the end of the file.
+    pub fn read(&mut self, handle: u16, buf: &mut [u8]) -> Result<usize, VMError> {
+        let handle = self.handle(handle)?;
+        if !handle.mode.can_read() {
+            return Err(VMError::PermissionDenied);
+        }
+
+        handle.file.read(buf).map_err(io_error)
+    }
+
+    /// Writes the buffer and returns the number of bytes written.
+    pub fn write(&mut self, handle: u16, buf: &[u8]) -> Result<usize, VMError> {
+        let handle = self.handle(handle)?;
+        if !handle.mode.can_write() {
+            return Err(VMError::PermissionDenied);
+        }
+
+        handle.file.write_all(buf).map_err(io_error)?;
+        Ok(buf.len())
+    }
+
+    /// Flushes and closes a handle.
+    pub fn close(&mut self, handle: u16) -> Result<(), VMError> {
+        let mut handle = self
+            .handles
+            .remove(&handle)
+            .ok_or(VMError::InvalidHandle(handle))?;
+
+        handle.file.flush().map_err(io_error)
+    }
+
+    fn handle(&mut self, handle: u16) -> Result<&mut Handle, VMError> {
+        self.handles
+            .get_mut(&handle)
+            .ok_or(VMError::InvalidHandle(handle))
+    }
+}
+
+impl Default for Vfs {
+    fn default() -> Self {
+        Self::make(Box::new(MemoryFs::default()))
+    }
+}
+
+impl Device for Vfs {
+    fn name(&self) -> &str {
+        "vfs"
+    }
+
+    /// Closes all the handles.
+    fn reset(&mut self) {
+        for (_, mut handle) in std::mem::take(&mut self.handles) {
+            let _ = handle.file.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory() {
+        let fs = MemoryFs::default();
+        fs.insert("in.txt", b"abc");
+        let mut vfs = Vfs::make(Box::new(fs.clone()));
+
+        let input = vfs.open("in.txt", OpenMode::Read).unwrap();
+        let output = vfs.open("out.txt", OpenMode::Append).unwrap();
+        assert_eq!((1, 2), (input, output));
+
+        let mut buf = [0u8; 2];
+        assert_eq!(Ok(2), vfs.read(input, &mut buf));
+        assert_eq!(Ok(2), vfs.write(output, &buf));
+        assert_eq!(Ok(1), vfs.read(input, &mut buf));
+        assert_eq!(Ok(0), vfs.read(input, &mut buf));
+
+        assert_eq!(Err(VMError::PermissionDenied), vfs.write(input, &buf));
+        assert_eq!(Err(VMError::PermissionDenied), vfs.read(output, &mut buf));
+        assert_eq!(Ok(()), vfs.close(input));
+        assert_eq!(Err(VMError::InvalidHandle(1)), vfs.close(input));
+        assert_eq!(Ok(1), vfs.open("in.txt", OpenMode::Read));
+
+        assert_eq!(Some(b"ab".to_vec()), fs.get("out.txt"));
+        assert_eq!(
+            Err(VMError::FileNotFound),
+            vfs.open("missing.txt", OpenMode::Read).map(|_| ())
+        );
+
+        vfs.reset();
+        assert_eq!(0, vfs.open_handles());
+    }
+
+    #[test]
+    fn read_only() {
+        let fs = MemoryFs::default();
+        fs.insert("in.txt", b"abc");
+        let mut vfs = Vfs::make(Box::new(fs.read_only()));
+
+        assert!(vfs.open("in.txt", OpenMode::Read).is_ok());
+        assert_eq!(
+            Err(VMError::PermissionDenied),
+            vfs.open("in.txt", OpenMode::Write)
+        );
+        assert_eq!(Some(b"abc".to_vec()), fs.get("in.txt"));
+    }
+
+    #[test]
+    fn host_dir() {
+        let root = std::env::temp_dir().join(format!("lvm-vfs-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let mut vfs = Vfs::make(Box::new(HostDir::make(&root)));
+
+        let handle = vfs.open("out.txt", OpenMode::Write).unwrap();
+        vfs.write(handle, b"hello").unwrap();
+        vfs.close(handle).unwrap();
+        assert_eq!(
+            b"hello".to_vec(),
+            std::fs::read(root.join("out.txt")).unwrap()
+        );
+
+        for path in ["../out.txt", "/etc/passwd", "a/../../out.txt"] {
+            assert_eq!(
+                Err(VMError::PermissionDenied),
+                vfs.open(path, OpenMode::Read)
+            );
+        }
+        assert_eq!(Err(VMError::InvalidPath), vfs.open("", OpenMode::Read));
+
+        let mut vfs = Vfs::make(Box::new(HostDir::make(&root).with_read_only(true)));
+        assert_eq!(
+            Err(VMError::PermissionDenied),
+            vfs.open("out.txt", OpenMode::Append)
+        );
+        assert_eq!(
+            Err(VMError::FileNotFound),
+            vfs.open("missing.txt", OpenMode::Read)
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn host_dir_links() {
+        use std::os::unix::fs::symlink;
+
+        let base = std::env::temp_dir().join(format!("lvm-vfs-links-{}", std::process::id()));
+        let (root, outside) = (base.join("root"), base.join("outside"));
+        std::fs::create_dir_all(root.join("dir")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret.txt"), b"secret").unwrap();
+        std::fs::write(root.join("dir/in.txt"), b"in").unwrap();
+
+        symlink(outside.join("secret.txt"), root.join("secret.txt")).unwrap();
+        symlink(&outside, root.join("out")).unwrap();
+        symlink(outside.join("new.txt"), root.join("new.txt")).unwrap();
+        symlink(root.join("dir/in.txt"), root.join("in.txt")).unwrap();
+
+        let mut vfs = Vfs::make(Box::new(HostDir::make(&root)));
+        for (path, mode) in [
+            ("secret.txt", OpenMode::Read),
+            ("out/secret.txt", OpenMode::Read),
+            ("out/created.txt", OpenMode::Write),
+            ("new.txt", OpenMode::Write),
+        ] {
+            assert_eq!(Err(VMError::PermissionDenied), vfs.open(path, mode));
+        }
+        assert!(!outside.join("created.txt").exists());
+        assert!(!outside.join("new.txt").exists());
+
+        // The links staying under the root are followed.
+        assert!(vfs.open("in.txt", OpenMode::Read).is_ok());
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -1,29 +1,1022 @@
-use std::fmt::{Display, LowerHex, UpperHex};
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fmt::{Display, LowerHex, UpperHex},
+    task::{Context, Poll},
+};
 
-use lvm_core::{Add, Load};
+use lvm_core::{
+    Add, Close, Div, FRead, FWrite, Halt, Instruction, Iret, Ivec, Load, Open, Program, Prtc, Prti,
+    RIndex, Read, RecvMsg, SendMsg, Spawn, Syscall,
+};
+
+use crate::{
+    Console, Device, Interrupts, Journal, JournalWrite, Mailbox, Message, OpenMode, Snapshot,
+    SpawnRequest, SyscallFuture, Syscalls, Timer, VMConfig, VMError, Verifier, Vfs, Violation,
+    VmContext,
+};
+
+/// An async syscall waiting for its host future.
+struct Suspended {
+    pc: usize,
+    instruction: Instruction,
+    number: u16,
+    future: SyscallFuture,
+}
 
 pub struct VM {
-    registers: [u16; 8],
+    config: VMConfig,
+    registers: Vec<u16>,
+    memory: Vec<u8>,
+    program: Program,
+    pc: usize,
+    journal: Option<Journal>,
+    syscalls: Syscalls,
+    console: Console,
+    vfs: Vfs,
+    interrupts: Interrupts,
+    timer: Timer,
+    pending: VecDeque<u8>,
+    jump: Option<usize>,
+    spawn: Option<SpawnRequest>,
+    mailbox: Mailbox,
+    outbox: Option<(u16, u16)>,
+    peers: Option<BTreeSet<u16>>,
+    suspended: Option<Suspended>,
 }
 
 impl VM {
+    /// The number of instructions [`VM::run_async`] executes before yielding to the executor.
+    pub const ASYNC_BUDGET: usize = 1024;
+
     pub fn new() -> Self {
-        Self { registers: [0; 8] }
+        Self::with_config(VMConfig::default())
+    }
+
+    /// Creates a [`VM`] instance with the given configuration.
+    pub fn with_config(config: VMConfig) -> Self {
+        Self {
+            config,
+            registers: vec![0; config.registers()],
+            memory: vec![0; config.memory()],
+            program: Program::default(),
+            pc: 0,
+            journal: config.journal().map(Journal::make),
+            syscalls: Syscalls::default(),
+            console: Console::default(),
+            vfs: Vfs::default(),
+            interrupts: Interrupts::default(),
+            timer: Timer::default(),
+            pending: VecDeque::new(),
+            jump: None,
+            spawn: None,
+            mailbox: Mailbox::make(config.mailbox()),
+            outbox: None,
+            peers: None,
+            suspended: None,
+        }
+    }
+
+    /// Returns the configuration.
+    pub const fn config(&self) -> &VMConfig {
+        &self.config
+    }
+
+    /// Returns the values of the registers.
+    pub fn registers(&self) -> &[u16] {
+        &self.registers
+    }
+
+    /// Returns the value of a register.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::RIndex;
+    /// use lvm_machine::{VMError, VM};
+    ///
+    /// let mut vm = VM::new();
+    /// vm.set_register(RIndex::make(3), 42).unwrap();
+    /// assert_eq!(Ok(42), vm.register(RIndex::make(3)));
+    ///
+    /// let err = VMError::InvalidRegister(RIndex::make(8));
+    /// assert_eq!(Err(err), vm.register(RIndex::make(8)));
+    /// ```
+    pub fn register(&self, rindx: RIndex) -> Result<u16, VMError> {
+        let idx: u8 = rindx.into();
+        self.registers
+            .get(idx as usize)
+            .copied()
+            .ok_or(VMError::InvalidRegister(rindx))
+    }
+
+    /// Sets the value of a register.
+    /// The undo log only records the writes done by the executed instructions,
+    /// see [`VM::clear_journal`].
+    pub fn set_register(&mut self, rindx: RIndex, value: u16) -> Result<(), VMError> {
+        *self.register_mut(rindx)? = value;
+        Ok(())
+    }
+
+    /// Returns the memory.
+    pub fn memory(&self) -> &[u8] {
+        &self.memory
+    }
+
+    /// Writes the bytes to memory starting at the address.
+    /// The undo log only records the writes done by the executed instructions,
+    /// see [`VM::clear_journal`].
+    pub fn write_memory(&mut self, addr: usize, bytes: &[u8]) -> Result<(), VMError> {
+        addr.checked_add(bytes.len())
+            .and_then(|end| self.memory.get_mut(addr..end))
+            .ok_or(VMError::InvalidAddress(addr))?
+            .copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Returns the syscall handlers.
+    pub fn syscalls(&self) -> &Syscalls {
+        &self.syscalls
+    }
+
+    /// Registers the host function called by `SYSCALL #number`,
+    /// replacing any previous handler of the number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Instruction, Operand16, RIndex, Syscall};
+    /// use lvm_machine::{VMError, VM};
+    ///
+    /// let mut vm = VM::new();
+    /// vm.register_syscall(1, |ctx| {
+    ///     let value = ctx.register(RIndex::make(0))?;
+    ///     ctx.set_register(RIndex::make(0), value * 2)
+    /// });
+    ///
+    /// vm.set_register(RIndex::make(0), 21).unwrap();
+    /// vm.execute(Instruction::SyscallI(Syscall::make(Operand16::make(1)))).unwrap();
+    /// assert_eq!(Ok(42), vm.register(RIndex::make(0)));
+    ///
+    /// let unknown = Instruction::SyscallI(Syscall::make(Operand16::make(2)));
+    /// assert_eq!(Err(VMError::UnknownSyscall(2)), vm.execute(unknown));
+    /// ```
+    pub fn register_syscall<F>(&mut self, number: u16, handler: F)
+    where
+        F: Fn(&mut VmContext) -> Result<(), VMError> + Send + 'static,
+    {
+        self.syscalls.register(number, handler);
+    }
+
+    /// Registers the async host function called by `SYSCALL #number`, replacing
+    /// any previous handler of the number. The handler reads its arguments and returns
+    /// a future; the machine suspends until it completes, see [`VM::run_async`].
+    /// [`VM::step`] fails with [`VMError::AsyncSyscall`] on the syscall.
+    pub fn register_async_syscall<F>(&mut self, number: u16, handler: F)
+    where
+        F: Fn(&VmContext) -> SyscallFuture + Send + 'static,
+    {
+        self.syscalls.register_async(number, handler);
+    }
+
+    /// Returns the console device.
+    pub fn console_mut(&mut self) -> &mut Console {
+        &mut self.console
+    }
+
+    /// Replaces the console device, e.g. to capture the output of a program.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Instruction, Prti, RIndex};
+    /// use lvm_machine::{Console, OutputBuffer, VM};
+    ///
+    /// let output = OutputBuffer::default();
+    /// let mut vm = VM::new();
+    /// vm.set_console(Console::make(Box::new("".as_bytes()), Box::new(output.clone())));
+    ///
+    /// vm.set_register(RIndex::make(1), 42).unwrap();
+    /// vm.execute(Instruction::PrtiI(Prti::make(RIndex::make(1)))).unwrap();
+    /// assert_eq!("42", output.to_string());
+    /// ```
+    pub fn set_console(&mut self, console: Console) {
+        self.console = console;
+    }
+
+    /// Returns the file device.
+    pub fn vfs_mut(&mut self) -> &mut Vfs {
+        &mut self.vfs
+    }
+
+    /// Replaces the file device, e.g. to give a program access to a host directory.
+    /// The files are kept in memory by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_machine::{MemoryFs, Vfs, VM};
+    ///
+    /// let fs = MemoryFs::default();
+    /// fs.insert("input.txt", b"42");
+    ///
+    /// let mut vm = VM::new();
+    /// vm.set_vfs(Vfs::make(Box::new(fs.read_only())));
+    /// ```
+    pub fn set_vfs(&mut self, vfs: Vfs) {
+        self.vfs = vfs;
+    }
+
+    /// Returns the interrupt vector table with the running handlers.
+    pub fn interrupts(&self) -> &Interrupts {
+        &self.interrupts
+    }
+
+    /// Returns the timer device.
+    pub const fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    /// Replaces the timer device. The default timer never fires.
+    pub fn set_timer(&mut self, timer: Timer) {
+        self.timer = timer;
+    }
+
+    /// Returns the host interrupts raised and not yet delivered, the oldest first.
+    pub fn pending(&self) -> impl Iterator<Item = u8> + '_ {
+        self.pending.iter().copied()
+    }
+
+    /// Raises a host interrupt, delivered after the next executed instruction
+    /// once no handler runs. An interrupt already pending is not queued twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Halt, Instruction, Ivec, Operand16, Operand8, Program};
+    /// use lvm_machine::VM;
+    ///
+    /// let ivec = Instruction::IvecI(Ivec::make(Operand8::make(9), Operand16::make(3)));
+    /// let halt = Instruction::HaltI(Halt::make());
+    /// let mut vm = VM::new();
+    /// vm.load_program(Program::make(vec![ivec, halt, halt, halt]));
+    ///
+    /// vm.raise(9).unwrap();
+    /// vm.step().unwrap();
+    /// assert_eq!(3, vm.pc());
+    /// assert_eq!(1, vm.interrupts().depth());
+    /// ```
+    pub fn raise(&mut self, vector: u8) -> Result<(), VMError> {
+        if vector as usize >= Interrupts::VECTORS {
+            return Err(VMError::InvalidVector(vector));
+        }
+
+        if !self.pending.contains(&vector) {
+            self.pending.push_back(vector);
+        }
+        Ok(())
+    }
+
+    /// Takes the request of the last `SPAWN` instruction, served by the [`crate::Scheduler`].
+    pub fn take_spawn(&mut self) -> Option<SpawnRequest> {
+        self.spawn.take()
+    }
+
+    /// Returns the messages received and not yet taken by `RECV`.
+    pub fn mailbox(&self) -> &Mailbox {
+        &self.mailbox
+    }
+
+    /// Adds a message to the mailbox. Fails when the mailbox is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Instruction, RIndex, RecvMsg};
+    /// use lvm_machine::VM;
+    ///
+    /// let mut vm = VM::new();
+    /// vm.deliver(3, 42).unwrap();
+    ///
+    /// let recv = RecvMsg::make(RIndex::make(1), RIndex::make(2));
+    /// vm.execute(Instruction::RecvI(recv)).unwrap();
+    /// assert_eq!(Ok(3), vm.register(RIndex::make(1)));
+    /// assert_eq!(Ok(42), vm.register(RIndex::make(2)));
+    /// ```
+    pub fn deliver(&mut self, sender: u16, value: u16) -> Result<(), VMError> {
+        self.mailbox.push(Message::make(sender, value))
+    }
+
+    /// Takes the message of the last `SEND` instruction, as the id of the
+    /// receiving process and the value, delivered by the [`crate::Scheduler`].
+    pub fn take_message(&mut self) -> Option<(u16, u16)> {
+        self.outbox.take()
+    }
+
+    /// Sets the ids of the processes a `SEND` may deliver to, kept up to date by
+    /// the [`crate::Scheduler`]. A `SEND` to any other process faults with
+    /// [`VMError::InvalidProcess`], which its handler may catch.
+    /// Without peers, the default, the messages are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Instruction, RIndex, SendMsg};
+    /// use lvm_machine::{VMError, VM};
+    ///
+    /// let mut vm = VM::new();
+    /// vm.set_peers(Some([1].into()));
+    ///
+    /// let send = Instruction::SendI(SendMsg::make(RIndex::make(1), RIndex::make(2)));
+    /// assert_eq!(Err(VMError::InvalidProcess(0)), vm.execute(send));
+    /// assert_eq!(None, vm.take_message());
+    /// ```
+    pub fn set_peers(&mut self, peers: Option<BTreeSet<u16>>) {
+        self.peers = peers;
+    }
+
+    /// Returns the undo log, if enabled by [`VMConfig::with_journal`].
+    pub fn journal(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+
+    /// Forgets the instructions recorded in the undo log, e.g. after the host
+    /// changed the state with [`VM::set_register`], so stepping back cannot
+    /// silently revert the change.
+    pub fn clear_journal(&mut self) {
+        if let Some(journal) = &mut self.journal {
+            journal.clear();
+        }
+    }
+
+    /// Reverts the last instruction recorded in the undo log,
+    /// restoring the registers and memory it wrote, the interrupt state,
+    /// the count of the timer and the program counter. The interrupts raised by the
+    /// host with [`VM::raise`] are not recorded, so they are queued again.
+    /// Returns the reverted instruction or `None` if there is nothing to revert.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Instruction, Load, Operand16, Program, RIndex};
+    /// use lvm_machine::{VMConfig, VM};
+    ///
+    /// let load = Instruction::LoadI(Load::make(RIndex::make(1), Operand16::make(10)));
+    /// let mut vm = VM::with_config(VMConfig::default().with_journal(1024));
+    /// vm.load_program(Program::make(vec![load]));
+    /// vm.step().unwrap();
+    ///
+    /// assert_eq!(Some(load), vm.step_back());
+    /// assert_eq!(Ok(0), vm.register(RIndex::make(1)));
+    /// assert_eq!(0, vm.pc());
+    /// assert_eq!(None, vm.step_back());
+    /// ```
+    pub fn step_back(&mut self) -> Option<Instruction> {
+        let entry = self.journal.as_mut()?.pop()?;
+        for write in entry.writes().iter().rev() {
+            match *write {
+                JournalWrite::Register(rindx, old) => {
+                    let idx: u8 = rindx.into();
+                    self.registers[idx as usize] = old;
+                }
+                JournalWrite::Memory(addr, old) => self.memory[addr] = old,
+                JournalWrite::Vector(vector, old) => {
+                    let _ = self.interrupts.set_handler(vector, old);
+                }
+                JournalWrite::Enter => {
+                    let _ = self.interrupts.pop();
+                }
+                JournalWrite::Leave(ret) => {
+                    let _ = self.interrupts.push(ret);
+                }
+                JournalWrite::Receive(message) => self.mailbox.unpop(message),
+                JournalWrite::Tick(count) => self.timer = self.timer.with_count(count),
+                JournalWrite::Raise(_) => {
+                    let _ = self.pending.pop_back();
+                }
+                JournalWrite::Deliver(vector) => self.pending.push_front(vector),
+            }
+        }
+
+        self.pc = entry.pc();
+        Some(entry.instruction())
+    }
+
+    /// Steps back until the last instruction which wrote the watched register is reverted.
+    /// Returns that instruction or `None` if the undo log has no such write,
+    /// in which case every recorded instruction is reverted.
+    pub fn reverse_continue(&mut self, watch: RIndex) -> Option<Instruction> {
+        loop {
+            let writes = self.journal.as_ref()?.iter().last()?.writes_register(watch);
+            let instruction = self.step_back()?;
+            if writes {
+                return Some(instruction);
+            }
+        }
+    }
+
+    /// Clears the registers, the memory, the interrupt vector table and the mailbox,
+    /// closes the open files and positions the program counter on the first
+    /// instruction of the loaded program. The undo log is cleared.
+    pub fn reset(&mut self) {
+        self.clear_journal();
+        self.registers.iter_mut().for_each(|r| *r = 0);
+        self.memory.iter_mut().for_each(|b| *b = 0);
+        self.console.reset();
+        self.vfs.reset();
+        self.timer.reset();
+        self.clear_interrupts();
+        self.mailbox.clear();
+        self.pc = 0;
+    }
+
+    /// Captures the registers, the memory, the program counter and the interrupt state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::RIndex;
+    /// use lvm_machine::VM;
+    ///
+    /// let mut vm = VM::new();
+    /// let snapshot = vm.snapshot();
+    ///
+    /// vm.set_register(RIndex::make(3), 42).unwrap();
+    /// vm.restore(&snapshot);
+    /// assert_eq!(Ok(0), vm.register(RIndex::make(3)));
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::make(
+            self.registers.clone(),
+            self.memory.clone(),
+            self.pc,
+            self.interrupts.clone(),
+        )
+    }
+
+    /// Brings back the registers, the memory, the program counter and the
+    /// interrupt state of a snapshot. The undo log and the pending interrupts are cleared.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.clear_journal();
+        self.registers.clear();
+        self.registers.extend_from_slice(snapshot.registers());
+        self.memory.clear();
+        self.memory.extend_from_slice(snapshot.memory());
+        self.interrupts = snapshot.interrupts().clone();
+        self.pending.clear();
+        self.suspended = None;
+        self.pc = snapshot.pc();
+    }
+
+    /// Returns the position of the next instruction to be executed.
+    pub const fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the loaded program.
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Loads a program and positions the program counter on its first instruction.
+    /// The undo log and the interrupt vector table are cleared.
+    pub fn load_program(&mut self, program: Program) {
+        self.clear_journal();
+        self.clear_interrupts();
+        self.program = program;
+        self.pc = 0;
+    }
+
+    /// Checks the program with a [`Verifier`] for the configuration of the machine
+    /// and loads it when there are no violations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Instruction, Load, Operand16, Program, RIndex};
+    /// use lvm_machine::VM;
+    ///
+    /// let load = Instruction::LoadI(Load::make(RIndex::make(8), Operand16::make(1)));
+    /// let mut vm = VM::new();
+    /// assert_eq!(1, vm.load_verified(Program::make(vec![load])).unwrap_err().len());
+    /// assert!(vm.program().is_empty());
+    /// ```
+    pub fn load_verified(&mut self, program: Program) -> Result<(), Vec<Violation>> {
+        Verifier::make(self.config).verify(&program)?;
+        self.load_program(program);
+        Ok(())
+    }
+
+    /// Returns true when there are no more instructions to execute.
+    pub fn is_done(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Executes the instruction at the program counter and advances it.
+    /// Returns the executed instruction or `None` if the program is done.
+    ///
+    /// A fault with a handler installed enters the handler instead of failing,
+    /// then the timer ticks and a pending host interrupt is delivered.
+    /// A suspended machine fails with [`VMError::Suspended`] until
+    /// [`VM::poll_step`] completes its syscall.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Div, Instruction, Iret, Ivec, Operand16, Operand8, Program, RIndex};
+    /// use lvm_machine::{Interrupts, VM};
+    ///
+    /// let r = RIndex::make;
+    /// let vector = Operand8::make(Interrupts::DIVISION_BY_ZERO);
+    /// let mut vm = VM::new();
+    /// vm.load_program(Program::make(vec![
+    ///     Instruction::IvecI(Ivec::make(vector, Operand16::make(3))),
+    ///     Instruction::DivI(Div::make(r(1), r(2), r(3))),
+    ///     Instruction::HaltI(lvm_core::Halt::make()),
+    ///     Instruction::IretI(Iret::make()),
+    /// ]));
+    ///
+    /// while !vm.is_done() {
+    ///     vm.step().unwrap();
+    /// }
+    /// assert_eq!(0, vm.interrupts().depth());
+    /// ```
+    pub fn step(&mut self) -> Result<Option<Instruction>, VMError> {
+        if self.is_suspended() {
+            return Err(VMError::Suspended);
+        }
+
+        let instruction = match self.program.get(self.pc) {
+            Some(instruction) => *instruction,
+            None => return Ok(None),
+        };
+
+        let pc = self.pc;
+        self.begin();
+        let result = self.dispatch(instruction);
+        self.finish(pc, instruction, result)
+    }
+
+    /// Returns true while an async syscall waits for its host future.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended.is_some()
+    }
+
+    /// Executes the instruction at the program counter like [`VM::step`], except
+    /// that an async syscall suspends the machine until its host future completes.
+    /// The machine keeps the pending syscall, so polling again resumes it.
+    ///
+    /// Only the async syscalls suspend the machine: the console `READ` and the file
+    /// instructions run synchronously and block the executor until they complete.
+    pub fn poll_step(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<Instruction>, VMError>> {
+        if self.suspended.is_none() {
+            let instruction = match self.program.get(self.pc) {
+                Some(instruction) => *instruction,
+                None => return Poll::Ready(Ok(None)),
+            };
+
+            let number: u16 = match instruction {
+                Instruction::SyscallI(syscall)
+                    if self.syscalls.is_async(syscall.number().into()) =>
+                {
+                    syscall.number().into()
+                }
+                _ => return Poll::Ready(self.step()),
+            };
+
+            let handler = self.syscalls.get_async(number)?;
+            let ctx = VmContext::make(number, &mut self.registers, &mut self.memory, None);
+            self.suspended = Some(Suspended {
+                pc: self.pc,
+                instruction,
+                number,
+                future: handler(&ctx),
+            });
+        }
+
+        let result = match &mut self.suspended {
+            Some(suspended) => match suspended.future.as_mut().poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            },
+            None => return Poll::Ready(Ok(None)),
+        };
+
+        let Some(Suspended {
+            pc,
+            instruction,
+            number,
+            ..
+        }) = self.suspended.take()
+        else {
+            return Poll::Ready(Ok(None));
+        };
+
+        self.begin();
+        let result = result.and_then(|completion| {
+            let mut ctx = VmContext::make(
+                number,
+                &mut self.registers,
+                &mut self.memory,
+                self.journal.as_mut(),
+            );
+            completion(&mut ctx)
+        });
+        Poll::Ready(self.finish(pc, instruction, result))
+    }
+
+    /// Runs the program to its end in the async mode, awaiting the host futures
+    /// of the async syscalls. It yields to the executor every [`VM::ASYNC_BUDGET`]
+    /// instructions, so long programs do not stall it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::future::Future;
+    ///
+    /// use lvm_core::{Instruction, Operand16, Program, RIndex, Syscall};
+    /// use lvm_machine::{SyscallCompletion, VM};
+    ///
+    /// let mut vm = VM::new();
+    /// vm.register_async_syscall(1, |ctx| {
+    ///     let value = ctx.register(RIndex::make(0));
+    ///     Box::pin(async move {
+    ///         let value = value? * 2;
+    ///         let completion: SyscallCompletion =
+    ///             Box::new(move |ctx| ctx.set_register(RIndex::make(0), value));
+    ///         Ok(completion)
+    ///     })
+    /// });
+    ///
+    /// vm.set_register(RIndex::make(0), 21).unwrap();
+    /// vm.load_program(Program::make(vec![Instruction::SyscallI(Syscall::make(
+    ///     Operand16::make(1),
+    /// ))]));
+    ///
+    /// // The host future is ready at once, so a single poll runs the program.
+    /// let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+    /// let mut run = Box::pin(vm.run_async());
+    /// assert!(run.as_mut().poll(&mut cx).is_ready());
+    /// drop(run);
+    /// assert_eq!(Ok(42), vm.register(RIndex::make(0)));
+    /// ```
+    pub async fn run_async(&mut self) -> Result<(), VMError> {
+        let mut budget = Self::ASYNC_BUDGET;
+        std::future::poll_fn(|cx| loop {
+            if budget == 0 {
+                budget = Self::ASYNC_BUDGET;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+
+            match self.poll_step(cx) {
+                Poll::Ready(Ok(Some(_))) => budget -= 1,
+                Poll::Ready(Ok(None)) => return Poll::Ready(Ok(())),
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Completes the instruction executed at `pc`: a fault enters its handler,
+    /// the timer ticks and a pending interrupt is delivered, then the writes are
+    /// committed to the undo log and the program counter moves on. A fault without
+    /// a handler keeps the program counter, but the writes done before it are
+    /// committed too, so they can be stepped back.
+    fn finish(
+        &mut self,
+        pc: usize,
+        instruction: Instruction,
+        result: Result<(), VMError>,
+    ) -> Result<Option<Instruction>, VMError> {
+        let mut next = match result {
+            Ok(()) => self.jump.take().unwrap_or(pc + 1),
+            Err(err) => {
+                self.jump = None;
+                match self.enter(err.vector(), pc + 1)? {
+                    Some(handler) => handler,
+                    None => {
+                        self.commit_fault(pc, instruction);
+                        return Err(err);
+                    }
+                }
+            }
+        };
+
+        if self.timer.period() != 0 {
+            self.journal_write(JournalWrite::Tick(self.timer.count()));
+        }
+        if self.timer.tick() && !self.pending.contains(&Interrupts::TIMER) {
+            self.pending.push_back(Interrupts::TIMER);
+            self.journal_write(JournalWrite::Raise(Interrupts::TIMER));
+        }
+
+        if !self.interrupts.in_handler() {
+            if let Some(vector) = self.pending.pop_front() {
+                self.journal_write(JournalWrite::Deliver(vector));
+                if let Some(handler) = self.enter(vector, next)? {
+                    next = handler;
+                }
+            }
+        }
+
+        self.commit(pc, instruction);
+        self.pc = next;
+
+        Ok(Some(instruction))
+    }
+
+    /// Executes a single instruction, independent of the loaded program.
+    /// The register and memory writes are recorded in the undo log, if enabled.
+    /// The effects on the devices, like the written files, are not.
+    /// Faults are returned, never delivered to the interrupt handlers.
+    /// A suspended machine fails with [`VMError::Suspended`].
+    pub fn execute(&mut self, instruction: Instruction) -> Result<(), VMError> {
+        if self.is_suspended() {
+            return Err(VMError::Suspended);
+        }
+
+        self.begin();
+        self.dispatch(instruction).inspect_err(|_| {
+            self.jump = None;
+            self.commit_fault(self.pc, instruction);
+        })?;
+        self.commit(self.pc, instruction);
+
+        if let Some(target) = self.jump.take() {
+            self.pc = target;
+        }
+
+        Ok(())
+    }
+
+    fn dispatch(&mut self, instruction: Instruction) -> Result<(), VMError> {
+        match instruction {
+            Instruction::LoadI(load) => self.run_load(load),
+            Instruction::AddI(add) => self.run_add(add),
+            Instruction::SyscallI(syscall) => self.run_syscall(syscall),
+            Instruction::PrtiI(prti) => self.run_prti(prti),
+            Instruction::PrtcI(prtc) => self.run_prtc(prtc),
+            Instruction::ReadI(read) => self.run_read(read),
+            Instruction::OpenI(open) => self.run_open(open),
+            Instruction::FReadI(fread) => self.run_fread(fread),
+            Instruction::FWriteI(fwrite) => self.run_fwrite(fwrite),
+            Instruction::CloseI(close) => self.run_close(close),
+            Instruction::DivI(div) => self.run_div(div),
+            Instruction::IvecI(ivec) => self.run_ivec(ivec),
+            Instruction::IretI(iret) => self.run_iret(iret),
+            Instruction::HaltI(halt) => self.run_halt(halt),
+            Instruction::SpawnI(spawn) => self.run_spawn(spawn),
+            Instruction::SendI(send) => self.run_send(send),
+            Instruction::RecvI(recv) => self.run_recv(recv),
+        }
     }
 
-    pub fn run_load(&mut self, load: Load) {
-        let idx: u8 = load.index().into();
+    pub fn run_load(&mut self, load: Load) -> Result<(), VMError> {
         let value: u16 = load.operand().into();
-        self.registers[idx as usize] = value;
+        self.write_register(load.index(), value)
+    }
+
+    /// Adds two registers. The sum wraps around on overflow, it never faults.
+    pub fn run_add(&mut self, add: Add) -> Result<(), VMError> {
+        let a = *self.register_mut(add.index1())?;
+        let b = *self.register_mut(add.index2())?;
+        self.write_register(add.index3(), a.wrapping_add(b))
+    }
+
+    pub fn run_syscall(&mut self, syscall: Syscall) -> Result<(), VMError> {
+        let number: u16 = syscall.number().into();
+        let handler = self.syscalls.get(number)?;
+        let mut ctx = VmContext::make(
+            number,
+            &mut self.registers,
+            &mut self.memory,
+            self.journal.as_mut(),
+        );
+        handler(&mut ctx)
+    }
+
+    pub fn run_prti(&mut self, prti: Prti) -> Result<(), VMError> {
+        let value = self.register(prti.index())?;
+        self.console.print_integer(value)
+    }
+
+    pub fn run_prtc(&mut self, prtc: Prtc) -> Result<(), VMError> {
+        let value = self.register(prtc.index())?;
+        self.console.print_char(value)
+    }
+
+    pub fn run_read(&mut self, read: Read) -> Result<(), VMError> {
+        self.register(read.index())?;
+        let value = self.console.read_integer()?;
+        self.write_register(read.index(), value)
+    }
+
+    /// Opens the file whose NUL terminated path is in memory at the address
+    /// held by the path register.
+    pub fn run_open(&mut self, open: Open) -> Result<(), VMError> {
+        let addr = self.register(open.path())? as usize;
+        let mode = OpenMode::from_value(self.register(open.mode())?)?;
+        self.register(open.handle())?;
+
+        let bytes = self
+            .memory
+            .get(addr..)
+            .ok_or(VMError::InvalidAddress(addr))?;
+        let len = bytes
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or(VMError::InvalidAddress(addr))?;
+        let path = std::str::from_utf8(&bytes[..len]).map_err(|_| VMError::InvalidPath)?;
+
+        let handle = self.vfs.open(path, mode)?;
+        self.write_register(open.handle(), handle)
+    }
+
+    pub fn run_fread(&mut self, fread: FRead) -> Result<(), VMError> {
+        let handle = self.register(fread.handle())?;
+        let addr = self.register(fread.buffer())? as usize;
+        let len = self.register(fread.len())? as usize;
+        self.memory_range(addr, len)?;
+
+        let mut buf = vec![0u8; len];
+        let read = self.vfs.read(handle, &mut buf)?;
+        self.store(addr, &buf[..read])?;
+        self.write_register(fread.len(), read as u16)
+    }
+
+    pub fn run_fwrite(&mut self, fwrite: FWrite) -> Result<(), VMError> {
+        let handle = self.register(fwrite.handle())?;
+        let addr = self.register(fwrite.buffer())? as usize;
+        let len = self.register(fwrite.len())? as usize;
+
+        let bytes = self.memory_range(addr, len)?.to_vec();
+        let written = self.vfs.write(handle, &bytes)?;
+        self.write_register(fwrite.len(), written as u16)
+    }
+
+    pub fn run_close(&mut self, close: Close) -> Result<(), VMError> {
+        let handle = self.register(close.index())?;
+        self.vfs.close(handle)
+    }
+
+    pub fn run_div(&mut self, div: Div) -> Result<(), VMError> {
+        let a = self.register(div.index1())?;
+        let b = self.register(div.index2())?;
+        self.register(div.index3())?;
+        if b == 0 {
+            return Err(VMError::DivisionByZero);
+        }
+
+        self.write_register(div.index3(), a / b)
+    }
+
+    pub fn run_ivec(&mut self, ivec: Ivec) -> Result<(), VMError> {
+        let vector = ivec.vector().value();
+        let address: u16 = ivec.address().into();
+        let old = self
+            .interrupts
+            .set_handler(vector, Some(address as usize))?;
+        self.journal_write(JournalWrite::Vector(vector, old));
+        Ok(())
+    }
+
+    pub fn run_iret(&mut self, _iret: Iret) -> Result<(), VMError> {
+        let ret = self.interrupts.pop()?;
+        self.journal_write(JournalWrite::Leave(ret));
+        self.jump = Some(ret);
+        Ok(())
+    }
+
+    pub fn run_halt(&mut self, _halt: Halt) -> Result<(), VMError> {
+        self.jump = Some(self.program.len());
+        Ok(())
+    }
+
+    /// Requests a process starting at the instruction address held by the entry register.
+    /// The id register is cleared, so it stays 0 without a scheduler serving the request.
+    pub fn run_spawn(&mut self, spawn: Spawn) -> Result<(), VMError> {
+        let entry = self.register(spawn.entry())? as usize;
+        self.write_register(spawn.pid(), 0)?;
+        self.spawn = Some(SpawnRequest::make(entry, spawn.pid()));
+        Ok(())
+    }
+
+    /// Posts a message, delivered by the [`crate::Scheduler`]; without one it is dropped.
+    /// Fails when the target is not one of the peers.
+    pub fn run_send(&mut self, send: SendMsg) -> Result<(), VMError> {
+        let target = self.register(send.target())?;
+        let value = self.register(send.value())?;
+        if self
+            .peers
+            .as_ref()
+            .is_some_and(|peers| !peers.contains(&target))
+        {
+            return Err(VMError::InvalidProcess(target));
+        }
+        self.outbox = Some((target, value));
+        Ok(())
     }
 
-    pub fn run_add(&mut self, add: Add) {
-        let a: u8 = add.index1().into();
-        let b: u8 = add.index2().into();
-        let c: u8 = add.index3().into();
+    pub fn run_recv(&mut self, recv: RecvMsg) -> Result<(), VMError> {
+        self.register(recv.sender())?;
+        self.register(recv.value())?;
 
-        let ttl = self.registers[a as usize] + self.registers[b as usize];
-        self.registers[c as usize] = ttl;
+        let message = self.mailbox.pop()?;
+        self.journal_write(JournalWrite::Receive(message));
+        self.write_register(recv.sender(), message.sender())?;
+        self.write_register(recv.value(), message.value())
+    }
+
+    /// Enters the handler of the vector, to return to `ret`.
+    /// Returns the address of the handler or `None` if there is no handler.
+    fn enter(&mut self, vector: u8, ret: usize) -> Result<Option<usize>, VMError> {
+        let handler = match self.interrupts.handler(vector) {
+            Some(handler) => handler,
+            None => return Ok(None),
+        };
+
+        self.interrupts.push(ret)?;
+        self.journal_write(JournalWrite::Enter);
+        Ok(Some(handler))
+    }
+
+    fn clear_interrupts(&mut self) {
+        self.interrupts.clear();
+        self.pending.clear();
+        self.jump = None;
+        self.spawn = None;
+        self.outbox = None;
+        self.suspended = None;
+    }
+
+    fn begin(&mut self) {
+        if let Some(journal) = &mut self.journal {
+            journal.begin();
+        }
+    }
+
+    fn journal_write(&mut self, write: JournalWrite) {
+        if let Some(journal) = &mut self.journal {
+            journal.write(write);
+        }
+    }
+
+    fn commit(&mut self, pc: usize, instruction: Instruction) {
+        if let Some(journal) = &mut self.journal {
+            journal.commit(pc, instruction);
+        }
+    }
+
+    fn commit_fault(&mut self, pc: usize, instruction: Instruction) {
+        if let Some(journal) = &mut self.journal {
+            journal.commit_fault(pc, instruction);
+        }
+    }
+
+    /// Writes memory on behalf of an instruction, recording the previous bytes.
+    fn store(&mut self, addr: usize, bytes: &[u8]) -> Result<(), VMError> {
+        self.memory_range(addr, bytes.len())?;
+        let range = &mut self.memory[addr..addr + bytes.len()];
+        if let Some(journal) = &mut self.journal {
+            for (i, old) in range.iter().enumerate() {
+                journal.write(JournalWrite::Memory(addr + i, *old));
+            }
+        }
+
+        range.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn memory_range(&self, addr: usize, len: usize) -> Result<&[u8], VMError> {
+        addr.checked_add(len)
+            .and_then(|end| self.memory.get(addr..end))
+            .ok_or(VMError::InvalidAddress(addr))
+    }
+
+    /// Writes a register on behalf of an instruction, recording the previous value.
+    fn write_register(&mut self, rindx: RIndex, value: u16) -> Result<(), VMError> {
+        let old = std::mem::replace(self.register_mut(rindx)?, value);
+        if let Some(journal) = &mut self.journal {
+            journal.write(JournalWrite::Register(rindx, old));
+        }
+        Ok(())
+    }
+
+    fn register_mut(&mut self, rindx: RIndex) -> Result<&mut u16, VMError> {
+        let idx: u8 = rindx.into();
+        self.registers
+            .get_mut(idx as usize)
+            .ok_or(VMError::InvalidRegister(rindx))
+    }
+}
+
+impl Default for VM {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -32,7 +1025,7 @@ impl Display for VM {
         writeln!(f, "Registers:")?;
         let _ = self
             .registers
-            .into_iter()
+            .iter()
             .enumerate()
             .inspect(|(i, r)| writeln!(f, "  {}: {}", i, r).unwrap())
             .count();
@@ -45,7 +1038,7 @@ impl UpperHex for VM {
         writeln!(f, "Registers:")?;
         let _ = self
             .registers
-            .into_iter()
+            .iter()
             .enumerate()
             .inspect(|(i, r)| writeln!(f, "  {}: 0x{:X}", i, r).unwrap())
             .count();
@@ -58,7 +1051,7 @@ impl LowerHex for VM {
         writeln!(f, "Registers:")?;
         let _ = self
             .registers
-            .into_iter()
+            .iter()
             .enumerate()
             .inspect(|(i, r)| writeln!(f, "  {}: 0x{:x}", i, r).unwrap())
             .count();
@@ -68,9 +1061,10 @@ impl LowerHex for VM {
 
 #[cfg(test)]
 mod tests {
-    use lvm_core::{Operand16, RIndex};
+    use lvm_core::{Operand16, Operand8};
 
     use super::*;
+    use crate::{MemoryFs, OutputBuffer, SyscallCompletion};
 
     fn make_load(indx: u8, value: u16) -> Load {
         let rindx = RIndex::make(indx);
@@ -89,15 +1083,522 @@ mod tests {
     fn load() {
         let mut vm = VM::new();
 
-        vm.run_load(make_load(1, 200));
+        vm.run_load(make_load(1, 200)).unwrap();
         assert_eq!(200, vm.registers[1]);
 
-        vm.run_load(make_load(2, 300));
+        vm.run_load(make_load(2, 300)).unwrap();
         assert_eq!(300, vm.registers[2]);
 
-        vm.run_add(make_add(1, 2, 3));
+        vm.run_add(make_add(1, 2, 3)).unwrap();
         assert_eq!(200, vm.registers[1]);
         assert_eq!(300, vm.registers[2]);
         assert_eq!(500, vm.registers[3]);
     }
+
+    #[test]
+    fn add_wraps() {
+        let mut vm = VM::new();
+
+        vm.run_load(make_load(1, u16::MAX)).unwrap();
+        vm.run_load(make_load(2, 2)).unwrap();
+        vm.run_add(make_add(1, 2, 3)).unwrap();
+        assert_eq!(1, vm.registers[3]);
+    }
+
+    #[test]
+    fn invalid_register() {
+        let mut vm = VM::new();
+
+        let res = vm.run_load(make_load(8, 200));
+        assert_eq!(Err(VMError::InvalidRegister(RIndex::make(8))), res);
+
+        let res = vm.run_add(make_add(1, 2, 9));
+        assert_eq!(Err(VMError::InvalidRegister(RIndex::make(9))), res);
+    }
+
+    #[test]
+    fn with_config() {
+        let mut vm = VM::with_config(VMConfig::default().with_registers(16));
+        assert_eq!(16, vm.registers().len());
+
+        vm.run_load(make_load(15, 200)).unwrap();
+        assert_eq!(200, vm.registers[15]);
+
+        let res = vm.run_load(make_load(16, 200));
+        assert_eq!(Err(VMError::InvalidRegister(RIndex::make(16))), res);
+    }
+
+    #[test]
+    fn reset() {
+        let mut vm = VM::new();
+        vm.load_program(Program::make(vec![Instruction::LoadI(make_load(1, 200))]));
+        vm.step().unwrap();
+        assert_eq!(Ok(200), vm.register(RIndex::make(1)));
+
+        vm.reset();
+        assert_eq!(Ok(0), vm.register(RIndex::make(1)));
+        assert_eq!(0, vm.pc());
+        assert_eq!(1, vm.program().len());
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut vm = VM::new();
+        vm.load_program(Program::make(vec![
+            Instruction::LoadI(make_load(1, 10)),
+            Instruction::LoadI(make_load(2, 20)),
+        ]));
+        vm.step().unwrap();
+        let snapshot = vm.snapshot();
+        assert_eq!(1, snapshot.pc());
+
+        vm.step().unwrap();
+        assert!(vm.is_done());
+
+        vm.restore(&snapshot);
+        assert_eq!(1, vm.pc());
+        assert_eq!(&[0, 10, 0, 0, 0, 0, 0, 0], vm.registers());
+    }
+
+    #[test]
+    fn journal() {
+        let config = VMConfig::default().with_journal(1024);
+        let mut vm = VM::with_config(config);
+        vm.load_program(Program::make(vec![
+            Instruction::LoadI(make_load(1, 10)),
+            Instruction::LoadI(make_load(2, 20)),
+            Instruction::AddI(Add::make(RIndex::make(1), RIndex::make(2), RIndex::make(1))),
+            Instruction::LoadI(make_load(3, 30)),
+        ]));
+        while vm.step().unwrap().is_some() {}
+        assert_eq!(4, vm.journal().unwrap().len());
+
+        let add = vm.reverse_continue(RIndex::make(1)).unwrap();
+        assert_eq!(
+            Instruction::AddI(Add::make(RIndex::make(1), RIndex::make(2), RIndex::make(1))),
+            add
+        );
+        assert_eq!(2, vm.pc());
+        assert_eq!(&[0, 10, 20, 0, 0, 0, 0, 0], vm.registers());
+
+        assert!(vm.reverse_continue(RIndex::make(5)).is_none());
+        assert_eq!(0, vm.pc());
+        assert_eq!(&[0; 8], vm.registers());
+        assert!(vm.journal().unwrap().is_empty());
+    }
+
+    #[test]
+    fn journal_disabled() {
+        let mut vm = VM::new();
+        vm.execute(Instruction::LoadI(make_load(1, 10))).unwrap();
+
+        assert!(vm.journal().is_none());
+        assert_eq!(None, vm.step_back());
+        assert_eq!(None, vm.reverse_continue(RIndex::make(1)));
+    }
+
+    #[test]
+    fn failed_syscall_writes() {
+        let mut vm = VM::with_config(VMConfig::default().with_journal(1024));
+        vm.register_syscall(1, |ctx| {
+            ctx.set_register(RIndex::make(1), 5)?;
+            Err(VMError::SyscallFailed(1))
+        });
+        vm.load_program(Program::make(vec![
+            Instruction::LoadI(make_load(2, 7)),
+            Instruction::SyscallI(Syscall::make(Operand16::make(1))),
+        ]));
+
+        vm.step().unwrap();
+        assert_eq!(Err(VMError::SyscallFailed(1)), vm.step());
+        assert_eq!(5, vm.registers()[1]);
+        assert_eq!(2, vm.journal().unwrap().len());
+
+        let syscall = Instruction::SyscallI(Syscall::make(Operand16::make(1)));
+        assert_eq!(Some(syscall), vm.step_back());
+        assert_eq!(&[0, 0, 7], &vm.registers()[..3]);
+        assert_eq!(1, vm.pc());
+
+        assert_eq!(Err(VMError::SyscallFailed(1)), vm.execute(syscall));
+        assert_eq!(Some(syscall), vm.step_back());
+        assert_eq!(0, vm.registers()[1]);
+    }
+
+    #[test]
+    fn syscall() {
+        let mut vm = VM::with_config(VMConfig::default().with_journal(1024));
+        vm.register_syscall(7, |ctx| {
+            let addr = ctx.register(RIndex::make(0))? as usize;
+            let len = ctx.register(RIndex::make(1))? as usize;
+            let sum = ctx.read(addr, len)?.iter().map(|b| *b as u16).sum();
+            ctx.write(addr, &[0; 2])?;
+            ctx.set_register(RIndex::make(2), sum)
+        });
+        vm.register_syscall(8, |_| Err(VMError::SyscallFailed(3)));
+
+        vm.write_memory(16, &[1, 2, 3]).unwrap();
+        vm.load_program(Program::make(vec![
+            Instruction::LoadI(make_load(0, 16)),
+            Instruction::LoadI(make_load(1, 3)),
+            Instruction::SyscallI(Syscall::make(Operand16::make(7))),
+            Instruction::SyscallI(Syscall::make(Operand16::make(8))),
+            Instruction::SyscallI(Syscall::make(Operand16::make(9))),
+        ]));
+
+        vm.step().unwrap();
+        vm.step().unwrap();
+        vm.step().unwrap();
+        assert_eq!(6, vm.registers()[2]);
+        assert_eq!(&[0, 0, 3], &vm.memory()[16..19]);
+
+        assert_eq!(Err(VMError::SyscallFailed(3)), vm.step());
+        assert_eq!(3, vm.pc());
+
+        vm.step_back();
+        assert_eq!(2, vm.pc());
+        assert_eq!(0, vm.registers()[2]);
+        assert_eq!(&[1, 2, 3], &vm.memory()[16..19]);
+
+        let unknown = Instruction::SyscallI(Syscall::make(Operand16::make(9)));
+        assert_eq!(Err(VMError::UnknownSyscall(9)), vm.execute(unknown));
+        assert_eq!(
+            Err(VMError::InvalidAddress(1024)),
+            vm.write_memory(1024, &[1])
+        );
+    }
+
+    #[test]
+    fn console() {
+        let output = OutputBuffer::default();
+        let mut vm = VM::new();
+        vm.set_console(Console::make(
+            Box::new("20\n".as_bytes()),
+            Box::new(output.clone()),
+        ));
+        vm.load_program(Program::make(vec![
+            Instruction::ReadI(Read::make(RIndex::make(1))),
+            Instruction::LoadI(make_load(2, 22)),
+            Instruction::AddI(Add::make(RIndex::make(1), RIndex::make(2), RIndex::make(3))),
+            Instruction::PrtiI(Prti::make(RIndex::make(3))),
+            Instruction::LoadI(make_load(4, b'\n' as u16)),
+            Instruction::PrtcI(Prtc::make(RIndex::make(4))),
+            Instruction::ReadI(Read::make(RIndex::make(1))),
+        ]));
+
+        for _ in 0..6 {
+            vm.step().unwrap();
+        }
+        assert_eq!("42\n", output.to_string());
+
+        let eof = VMError::DeviceError(std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(Err(eof), vm.step());
+
+        let prti = Instruction::PrtiI(Prti::make(RIndex::make(8)));
+        assert_eq!(
+            Err(VMError::InvalidRegister(RIndex::make(8))),
+            vm.execute(prti)
+        );
+    }
+
+    #[test]
+    fn files() {
+        let r = RIndex::make;
+        let fs = MemoryFs::default();
+        let mut vm = VM::with_config(VMConfig::default().with_journal(1024));
+        vm.set_vfs(Vfs::make(Box::new(fs.clone())));
+        vm.write_memory(0, b"out.txt\0hello").unwrap();
+
+        vm.load_program(Program::make(vec![
+            Instruction::LoadI(make_load(1, 1)),
+            Instruction::OpenI(Open::make(r(0), r(1), r(2))),
+            Instruction::LoadI(make_load(3, 8)),
+            Instruction::LoadI(make_load(4, 5)),
+            Instruction::FWriteI(FWrite::make(r(2), r(3), r(4))),
+            Instruction::CloseI(Close::make(r(2))),
+            Instruction::LoadI(make_load(1, 0)),
+            Instruction::OpenI(Open::make(r(0), r(1), r(2))),
+            Instruction::LoadI(make_load(3, 100)),
+            Instruction::LoadI(make_load(4, 16)),
+            Instruction::FReadI(FRead::make(r(2), r(3), r(4))),
+        ]));
+
+        while !vm.is_done() {
+            vm.step().unwrap();
+        }
+        assert_eq!(Some(b"hello".to_vec()), fs.get("out.txt"));
+        assert_eq!(b"hello", &vm.memory()[100..105]);
+        assert_eq!(Ok(5), vm.register(r(4)));
+
+        vm.step_back();
+        assert_eq!([0u8; 5], vm.memory()[100..105]);
+        assert_eq!(Ok(16), vm.register(r(4)));
+
+        let fwrite = Instruction::FWriteI(FWrite::make(r(2), r(3), r(4)));
+        assert_eq!(Err(VMError::PermissionDenied), vm.execute(fwrite));
+        let close = Instruction::CloseI(Close::make(r(5)));
+        assert_eq!(Err(VMError::InvalidHandle(0)), vm.execute(close));
+        vm.set_register(r(1), 3).unwrap();
+        let open = Instruction::OpenI(Open::make(r(0), r(1), r(2)));
+        assert_eq!(Err(VMError::InvalidMode(3)), vm.execute(open));
+
+        vm.set_vfs(Vfs::make(Box::new(fs.read_only())));
+        vm.set_register(r(1), 1).unwrap();
+        assert_eq!(Err(VMError::PermissionDenied), vm.execute(open));
+    }
+
+    #[test]
+    fn interrupts() {
+        let r = RIndex::make;
+        let ivec = |v, a| Instruction::IvecI(Ivec::make(Operand8::make(v), Operand16::make(a)));
+        let mut vm = VM::with_config(VMConfig::default().with_journal(1 << 16));
+        vm.set_timer(Timer::make(3));
+        vm.set_register(r(0), 1).unwrap();
+        vm.load_program(Program::make(vec![
+            ivec(Interrupts::DIVISION_BY_ZERO, 6),
+            ivec(Interrupts::TIMER, 8),
+            Instruction::DivI(Div::make(r(1), r(2), r(3))),
+            Instruction::LoadI(make_load(4, 1)),
+            Instruction::HaltI(Halt::make()),
+            Instruction::HaltI(Halt::make()),
+            Instruction::LoadI(make_load(5, 7)),
+            Instruction::IretI(Iret::make()),
+            Instruction::AddI(Add::make(r(6), r(0), r(6))),
+            Instruction::IretI(Iret::make()),
+        ]));
+
+        let mut steps = 0;
+        while !vm.is_done() {
+            vm.step().unwrap();
+            steps += 1;
+        }
+        assert_eq!(17, steps);
+        assert_eq!(Ok(1), vm.register(r(4)));
+        assert_eq!(Ok(7), vm.register(r(5)));
+        assert_eq!(Ok(5), vm.register(r(6)));
+        assert_eq!(0, vm.interrupts().depth());
+
+        while vm.step_back().is_some() {}
+        assert_eq!(0, vm.pc());
+        assert_eq!(&Interrupts::default(), vm.interrupts());
+        assert_eq!(&[1, 0, 0, 0, 0, 0, 0, 0], vm.registers());
+        assert_eq!(0, vm.timer().count());
+    }
+
+    #[test]
+    fn interrupts_replay() {
+        let ivec = |v, a| Instruction::IvecI(Ivec::make(Operand8::make(v), Operand16::make(a)));
+        let mut vm = VM::with_config(VMConfig::default().with_journal(1 << 16));
+        vm.set_timer(Timer::make(3));
+        vm.load_program(Program::make(vec![
+            ivec(Interrupts::TIMER, 6),
+            ivec(9, 6),
+            Instruction::LoadI(make_load(1, 1)),
+            Instruction::LoadI(make_load(2, 2)),
+            Instruction::LoadI(make_load(3, 3)),
+            Instruction::HaltI(Halt::make()),
+            Instruction::IretI(Iret::make()),
+        ]));
+
+        let trace = |vm: &mut VM| {
+            let mut pcs = vec![];
+            while !vm.is_done() {
+                vm.step().unwrap();
+                pcs.push(vm.pc());
+            }
+            pcs
+        };
+
+        vm.step().unwrap();
+        vm.step().unwrap();
+        vm.raise(9).unwrap();
+        let first = trace(&mut vm);
+
+        // Stepping back restores the count of the timer and the queued interrupts,
+        // so the replay takes the interrupts at the same points.
+        for _ in 0..first.len() {
+            vm.step_back().unwrap();
+        }
+        assert_eq!(2, vm.timer().count());
+        assert_eq!(vec![9], vm.pending().collect::<Vec<_>>());
+        assert_eq!(first, trace(&mut vm));
+
+        // The host interrupts are not journaled, stepping back over their delivery
+        // queues them again.
+        while vm.step_back().is_some() {}
+        assert_eq!(0, vm.timer().count());
+        assert_eq!(vec![9], vm.pending().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn unhandled_faults() {
+        let r = RIndex::make;
+        let mut vm = VM::new();
+        vm.load_program(Program::make(vec![Instruction::IretI(Iret::make())]));
+        assert_eq!(Err(VMError::NoInterrupt), vm.step());
+        assert_eq!(0, vm.pc());
+
+        vm.execute(Instruction::HaltI(Halt::make())).unwrap();
+        assert!(vm.is_done());
+
+        // The handler faults again until the handlers are nested too deep.
+        let vector = Operand8::make(Interrupts::DIVISION_BY_ZERO);
+        vm.load_program(Program::make(vec![
+            Instruction::IvecI(Ivec::make(vector, Operand16::make(1))),
+            Instruction::DivI(Div::make(r(1), r(2), r(3))),
+        ]));
+        for _ in 0..=Interrupts::MAX_DEPTH {
+            vm.step().unwrap();
+        }
+        assert_eq!(Interrupts::MAX_DEPTH, vm.interrupts().depth());
+        assert_eq!(Err(VMError::StackOverflow), vm.step());
+    }
+
+    #[test]
+    fn step() {
+        let mut vm = VM::new();
+        let program = Program::make(vec![
+            Instruction::LoadI(make_load(1, 200)),
+            Instruction::LoadI(make_load(2, 300)),
+            Instruction::AddI(make_add(1, 2, 3)),
+        ]);
+        vm.load_program(program);
+
+        assert_eq!(Ok(Some(Instruction::LoadI(make_load(1, 200)))), vm.step());
+        assert_eq!(1, vm.pc());
+
+        while vm.step().unwrap().is_some() {}
+
+        assert!(vm.is_done());
+        assert_eq!(3, vm.pc());
+        assert_eq!(500, vm.registers()[3]);
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        struct Unpark(std::thread::Thread);
+
+        impl std::task::Wake for Unpark {
+            fn wake(self: std::sync::Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = std::sync::Arc::new(Unpark(std::thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => std::thread::park(),
+            }
+        }
+    }
+
+    /// A value completed by another thread.
+    #[derive(Default)]
+    struct HostValue {
+        value: Option<u16>,
+        waker: Option<std::task::Waker>,
+    }
+
+    type SharedValue = std::sync::Arc<std::sync::Mutex<HostValue>>;
+
+    fn host_future(shared: SharedValue, register: u8) -> SyscallFuture {
+        Box::pin(async move {
+            let value = std::future::poll_fn(|cx| {
+                let mut host = shared.lock().unwrap();
+                match host.value.take() {
+                    Some(value) => Poll::Ready(value),
+                    None => {
+                        host.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            })
+            .await;
+
+            let completion: SyscallCompletion =
+                Box::new(move |ctx| ctx.set_register(RIndex::make(register), value));
+            Ok(completion)
+        })
+    }
+
+    fn make_async_program() -> Program {
+        Program::make(vec![
+            Instruction::LoadI(make_load(0, 5)),
+            Instruction::SyscallI(Syscall::make(Operand16::make(1))),
+            Instruction::AddI(make_add(0, 1, 2)),
+        ])
+    }
+
+    #[test]
+    fn async_syscalls() {
+        let shared = SharedValue::default();
+        let mut vm = VM::new();
+        let host = shared.clone();
+        vm.register_async_syscall(1, move |_| host_future(host.clone(), 1));
+        vm.load_program(make_async_program());
+
+        let thread = std::thread::spawn(move || loop {
+            let mut host = shared.lock().unwrap();
+            if let Some(waker) = host.waker.take() {
+                host.value = Some(37);
+                waker.wake();
+                return;
+            }
+            drop(host);
+            std::thread::yield_now();
+        });
+
+        assert_eq!(Ok(()), block_on(vm.run_async()));
+        thread.join().unwrap();
+        assert!(vm.is_done());
+        assert_eq!(&[5, 37, 42], &vm.registers()[..3]);
+
+        // The synchronous mode refuses the async syscalls.
+        vm.load_program(make_async_program());
+        vm.step().unwrap();
+        assert_eq!(Err(VMError::AsyncSyscall(1)), vm.step());
+    }
+
+    #[test]
+    fn suspend() {
+        let shared = SharedValue::default();
+        let mut vm = VM::with_config(VMConfig::default().with_journal(1024));
+        let host = shared.clone();
+        vm.register_async_syscall(1, move |_| host_future(host.clone(), 1));
+        vm.load_program(make_async_program());
+
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+        assert!(matches!(vm.poll_step(&mut cx), Poll::Ready(Ok(Some(_)))));
+        assert!(vm.poll_step(&mut cx).is_pending());
+        assert!(vm.is_suspended());
+        assert_eq!(1, vm.pc());
+
+        // The suspended machine keeps its state until the host completes.
+        assert!(vm.poll_step(&mut cx).is_pending());
+        assert_eq!(0, vm.registers()[1]);
+        let load = Instruction::LoadI(make_load(1, 3));
+        assert_eq!(Err(VMError::Suspended), vm.step());
+        assert_eq!(Err(VMError::Suspended), vm.execute(load));
+        assert_eq!(1, vm.pc());
+        shared.lock().unwrap().value = Some(10);
+        assert!(matches!(vm.poll_step(&mut cx), Poll::Ready(Ok(Some(_)))));
+        assert!(!vm.is_suspended());
+        assert_eq!(10, vm.registers()[1]);
+        assert_eq!(2, vm.pc());
+
+        // The completion is journaled like a synchronous syscall.
+        assert!(vm.step_back().is_some());
+        assert_eq!(0, vm.registers()[1]);
+        assert_eq!(1, vm.pc());
+
+        // A failed future faults the syscall.
+        vm.register_async_syscall(1, |_| Box::pin(async { Err(VMError::SyscallFailed(3)) }));
+        assert_eq!(
+            Poll::Ready(Err(VMError::SyscallFailed(3))),
+            vm.poll_step(&mut cx)
+        );
+        assert!(!vm.is_suspended());
+        assert_eq!(1, vm.pc());
+    }
 }
@@ -0,0 +1,80 @@
+use lvm_core::Instruction;
+
+use crate::optimizer::{is_valid, remove};
+
+/// Removes the `LOAD` and `ADD` whose result is overwritten before it is read.
+///
+/// Only the runs of valid `LOAD` and `ADD` instructions are considered: they cannot
+/// fault, while any other instruction may fault or let the host observe the registers,
+/// so all the registers are live before it and at the end of the program. The
+/// instructions before the offset `fixed` are kept.
+pub(crate) fn run(code: &[Instruction], registers: usize, fixed: usize) -> Vec<Instruction> {
+    let mut keep = vec![true; code.len()];
+    let mut live = [true; 256];
+
+    for (offset, instruction) in code.iter().enumerate().rev() {
+        let pure = matches!(instruction, Instruction::LoadI(_) | Instruction::AddI(_));
+        if !pure || !is_valid(instruction, registers) {
+            live = [true; 256];
+            continue;
+        }
+
+        let dead = instruction.writes().iter().all(|rindx| {
+            let idx: u8 = (*rindx).into();
+            !live[idx as usize]
+        });
+        if dead && offset >= fixed {
+            keep[offset] = false;
+            continue;
+        }
+
+        for rindx in instruction.writes() {
+            let idx: u8 = rindx.into();
+            live[idx as usize] = false;
+        }
+        for rindx in instruction.reads() {
+            let idx: u8 = rindx.into();
+            live[idx as usize] = true;
+        }
+    }
+
+    remove(code, &keep)
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::Program;
+    use lvm_parser::ParseSource;
+
+    use super::*;
+
+    fn code(source: &str) -> Vec<Instruction> {
+        Program::from_source(source).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn dead_stores() {
+        let input =
+            code("LOAD $1 #2\nLOAD $2 #3\nLOAD $1 #4\nADD $1 $2 $2\nLOAD $3 #1\nLOAD $3 #2");
+        let expected = code("LOAD $2 #3\nLOAD $1 #4\nADD $1 $2 $2\nLOAD $3 #2");
+        assert_eq!(expected, run(&input, 8, 0));
+    }
+
+    #[test]
+    fn barriers() {
+        // The division may fault, so the registers are live before it.
+        let input = code("LOAD $1 #2\nDIV $2 $3 $4\nLOAD $1 #4");
+        assert_eq!(input, run(&input, 8, 0));
+
+        let input = code("LOAD $1 #2\nLOAD $9 #3\nLOAD $1 #4");
+        assert_eq!(input, run(&input, 8, 0));
+    }
+
+    #[test]
+    fn fixed() {
+        // The offsets before a process entry stay.
+        let input = code("LOAD $1 #2\nLOAD $1 #3\nLOAD $2 #1\nLOAD $2 #4");
+        let expected = code("LOAD $1 #2\nLOAD $1 #3\nLOAD $2 #4");
+        assert_eq!(expected, run(&input, 8, 2));
+    }
+}
@@ -0,0 +1,162 @@
+use lvm_core::{Instruction, Load, Operand16, RIndex};
+
+use crate::optimizer::{is_valid, remove};
+
+/// The registers holding a known constant, following the straight-line code.
+struct Constants([Option<u16>; 256]);
+
+impl Constants {
+    fn new() -> Self {
+        Self([None; 256])
+    }
+
+    fn get(&self, rindx: RIndex) -> Option<u16> {
+        let idx: u8 = rindx.into();
+        self.0[idx as usize]
+    }
+
+    fn set(&mut self, rindx: RIndex, value: Option<u16>) {
+        let idx: u8 = rindx.into();
+        self.0[idx as usize] = value;
+    }
+
+    /// Updates the constants with the effect of the instruction.
+    fn apply(&mut self, instruction: &Instruction, registers: usize) {
+        if !is_valid(instruction, registers) {
+            *self = Self::new();
+            return;
+        }
+
+        match instruction {
+            Instruction::LoadI(load) => self.set(load.index(), Some(load.operand().into())),
+            Instruction::AddI(add) => {
+                let value = self
+                    .get(add.index1())
+                    .zip(self.get(add.index2()))
+                    .map(|(a, b)| a.wrapping_add(b));
+                self.set(add.index3(), value);
+            }
+            Instruction::SyscallI(_) | Instruction::HaltI(_) => *self = Self::new(),
+            _ => {
+                for rindx in instruction.writes() {
+                    self.set(rindx, None);
+                }
+            }
+        }
+    }
+}
+
+/// Replaces the `ADD` of two known constants with the `LOAD` of their sum. No
+/// constant is known at the entries, where a process starts with other registers.
+pub(crate) fn fold(code: &[Instruction], registers: usize, entries: &[usize]) -> Vec<Instruction> {
+    let mut constants = Constants::new();
+    code.iter()
+        .enumerate()
+        .map(|(offset, instruction)| {
+            if entries.contains(&offset) {
+                constants = Constants::new();
+            }
+            let folded = match instruction {
+                Instruction::AddI(add) if is_valid(instruction, registers) => constants
+                    .get(add.index1())
+                    .zip(constants.get(add.index2()))
+                    .map(|(a, b)| {
+                        let load = Load::make(add.index3(), Operand16::make(a.wrapping_add(b)));
+                        Instruction::LoadI(load)
+                    }),
+                _ => None,
+            };
+
+            constants.apply(instruction, registers);
+            folded.unwrap_or(*instruction)
+        })
+        .collect()
+}
+
+/// Removes the `LOAD` of a constant the register already holds, from the offset
+/// `fixed` on. No constant is known at the entries.
+pub(crate) fn remove_no_ops(
+    code: &[Instruction],
+    registers: usize,
+    entries: &[usize],
+    fixed: usize,
+) -> Vec<Instruction> {
+    let mut constants = Constants::new();
+    let keep: Vec<bool> = code
+        .iter()
+        .enumerate()
+        .map(|(offset, instruction)| {
+            if entries.contains(&offset) {
+                constants = Constants::new();
+            }
+            let no_op = match instruction {
+                _ if offset < fixed => false,
+                Instruction::LoadI(load) if is_valid(instruction, registers) => {
+                    constants.get(load.index()) == Some(load.operand().into())
+                }
+                _ => false,
+            };
+
+            constants.apply(instruction, registers);
+            !no_op
+        })
+        .collect();
+
+    remove(code, &keep)
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::Program;
+    use lvm_parser::ParseSource;
+
+    use super::*;
+
+    fn code(source: &str) -> Vec<Instruction> {
+        Program::from_source(source).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn folding() {
+        let input =
+            code("LOAD $1 #2\nLOAD $2 #3\nADD $1 $2 $3\nADD $3 $3 $4\nREAD $1\nADD $1 $2 $5");
+        let expected =
+            code("LOAD $1 #2\nLOAD $2 #3\nLOAD $3 #5\nLOAD $4 #10\nREAD $1\nADD $1 $2 $5");
+        assert_eq!(expected, fold(&input, 8, &[]));
+
+        // The sum wraps like the machine does.
+        let input = code("LOAD $1 #65535\nLOAD $2 #2\nADD $1 $2 $3");
+        assert_eq!(
+            code("LOAD $1 #65535\nLOAD $2 #2\nLOAD $3 #1"),
+            fold(&input, 8, &[])
+        );
+    }
+
+    #[test]
+    fn no_ops() {
+        let input = code("LOAD $1 #2\nLOAD $1 #2\nPRTI $1\nLOAD $1 #2\nSYSCALL #1\nLOAD $1 #2");
+        let expected = code("LOAD $1 #2\nPRTI $1\nSYSCALL #1\nLOAD $1 #2");
+        assert_eq!(expected, remove_no_ops(&input, 8, &[], 0));
+    }
+
+    #[test]
+    fn invalid_registers() {
+        // The faulting instructions stay.
+        let input = code("LOAD $1 #2\nLOAD $9 #1\nADD $1 $1 $9\nLOAD $1 #2");
+        assert_eq!(input, fold(&input, 8, &[]));
+        assert_eq!(input, remove_no_ops(&input, 8, &[], 0));
+    }
+
+    #[test]
+    fn entries() {
+        // A process starting at an entry has other registers.
+        let input = code("LOAD $1 #2\nLOAD $1 #2\nADD $1 $1 $2\nLOAD $1 #2\nADD $1 $1 $2");
+        let expected = code("LOAD $1 #2\nLOAD $1 #2\nADD $1 $1 $2\nLOAD $1 #2\nLOAD $2 #4");
+        assert_eq!(expected, fold(&input, 8, &[2]));
+
+        // The instructions before the last entry keep their offsets.
+        assert_eq!(input, remove_no_ops(&input, 8, &[2], 2));
+        let expected = code("LOAD $1 #2\nADD $1 $1 $2\nADD $1 $1 $2");
+        assert_eq!(expected, remove_no_ops(&input, 8, &[], 0));
+    }
+}
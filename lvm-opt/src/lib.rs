@@ -0,0 +1,6 @@
+mod dead_stores;
+mod folding;
+mod optimizer;
+mod unreachable;
+
+pub use optimizer::*;
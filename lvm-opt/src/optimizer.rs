@@ -0,0 +1,355 @@
+use lvm_analysis::resolve_spawns;
+use lvm_core::{Instruction, Ivec, Operand16, Program};
+use lvm_machine::VMConfig;
+
+use crate::{dead_stores, folding, unreachable};
+
+/// A peephole optimizer for programs run by machines with a configuration.
+///
+/// The passes are enabled by default and can be toggled one by one:
+/// - *dead stores* removes the `LOAD` and `ADD` whose result is overwritten before it is read;
+/// - *folding* replaces the `ADD` of two constants with the `LOAD` of their sum;
+/// - *no-ops* removes the `LOAD` of a constant the register already holds;
+/// - *unreachable* removes the code no path reaches, like the code after `HALT`.
+///
+/// The instruction set has no jump instruction, so there is no jump threading: the
+/// only addresses are the `IVEC` handler addresses, which are retargeted when the
+/// code before them is removed. The handlers may run between any two instructions,
+/// so the programs installing them only go through the unreachable pass.
+///
+/// The processes started by `SPAWN` begin at addresses held in registers, so the
+/// passes need every target to be a constant: otherwise the program is left
+/// unchanged. The code is folded from each target without the constants before
+/// it, the instructions before the last target are never removed, so the targets
+/// keep their offsets, and the unreachable pass is skipped. Removing instructions
+/// changes how many of them run in a quantum, so the processes may interleave
+/// differently.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_core::Program;
+/// use lvm_opt::Optimizer;
+/// use lvm_parser::ParseSource;
+///
+/// let program = Program::from_source("LOAD $1 #2\nLOAD $2 #3\nADD $1 $2 $1\nLOAD $2 #0").unwrap();
+/// let optimized = Optimizer::default().optimize(&program);
+/// assert_eq!("LOAD $1 #5\nLOAD $2 #0", optimized.to_string());
+///
+/// let optimized = Optimizer::default().with_folding(false).optimize(&program);
+/// assert_eq!(program, optimized);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Optimizer {
+    config: VMConfig,
+    dead_stores: bool,
+    folding: bool,
+    no_ops: bool,
+    unreachable: bool,
+}
+
+impl Optimizer {
+    /// The number of times the passes run, at most, before a program stops changing.
+    pub const MAX_ROUNDS: usize = 16;
+
+    /// Creates an [`Optimizer`] instance with all the passes enabled.
+    pub fn make(config: VMConfig) -> Self {
+        Self {
+            config,
+            dead_stores: true,
+            folding: true,
+            no_ops: true,
+            unreachable: true,
+        }
+    }
+
+    /// Enables or disables the dead store elimination.
+    pub fn with_dead_stores(mut self, enabled: bool) -> Self {
+        self.dead_stores = enabled;
+        self
+    }
+
+    /// Enables or disables the constant folding.
+    pub fn with_folding(mut self, enabled: bool) -> Self {
+        self.folding = enabled;
+        self
+    }
+
+    /// Enables or disables the no-op removal.
+    pub fn with_no_ops(mut self, enabled: bool) -> Self {
+        self.no_ops = enabled;
+        self
+    }
+
+    /// Enables or disables the unreachable code removal.
+    pub fn with_unreachable(mut self, enabled: bool) -> Self {
+        self.unreachable = enabled;
+        self
+    }
+
+    /// Returns the optimized program.
+    pub fn optimize(&self, program: &Program) -> Program {
+        let mut entries = vec![];
+        if program.iter().any(|i| matches!(i, Instruction::SpawnI(_))) {
+            let (cfg, resolved) = resolve_spawns(program);
+            if !resolved {
+                return program.clone();
+            }
+            entries.extend(cfg.entries().iter().map(|b| cfg.blocks()[*b].start()));
+        }
+        // The instructions before the last process entry keep their offsets.
+        let fixed = entries.iter().max().copied().unwrap_or(0);
+
+        let handlers = program.iter().any(|i| matches!(i, Instruction::IvecI(_)));
+        let registers = self.config.registers();
+        let mut code: Vec<Instruction> = program.iter().copied().collect();
+
+        for _ in 0..Self::MAX_ROUNDS {
+            let before = code.len();
+            let mut changed = false;
+
+            if self.unreachable && entries.is_empty() {
+                code = unreachable::run(&code);
+            }
+            if !handlers {
+                if self.folding {
+                    let folded = folding::fold(&code, registers, &entries);
+                    changed |= folded != code;
+                    code = folded;
+                }
+                if self.no_ops {
+                    code = folding::remove_no_ops(&code, registers, &entries, fixed);
+                }
+                if self.dead_stores {
+                    code = dead_stores::run(&code, registers, fixed);
+                }
+            }
+
+            if !changed && code.len() == before {
+                break;
+            }
+        }
+
+        Program::make(code)
+    }
+}
+
+impl Default for Optimizer {
+    fn default() -> Self {
+        Self::make(VMConfig::default())
+    }
+}
+
+/// Returns true when the registers of the instruction are within the register count.
+pub(crate) fn is_valid(instruction: &Instruction, registers: usize) -> bool {
+    let mut rindxs = instruction.reads().into_iter().chain(instruction.writes());
+    rindxs.all(|rindx| {
+        let idx: u8 = rindx.into();
+        (idx as usize) < registers
+    })
+}
+
+/// Removes the instructions not kept, retargeting the `IVEC` handler addresses
+/// to the first kept instruction at or after them.
+pub(crate) fn remove(code: &[Instruction], keep: &[bool]) -> Vec<Instruction> {
+    let mut offsets = Vec::with_capacity(code.len() + 1);
+    let mut kept = 0;
+    for k in keep {
+        offsets.push(kept);
+        kept += *k as usize;
+    }
+    offsets.push(kept);
+
+    code.iter()
+        .zip(keep)
+        .filter(|(_, k)| **k)
+        .map(|(instruction, _)| match instruction {
+            Instruction::IvecI(ivec) => {
+                let address: u16 = ivec.address().into();
+                match offsets.get(address as usize) {
+                    Some(&offset) => {
+                        let ivec = Ivec::make(ivec.vector(), Operand16::make(offset as u16));
+                        Instruction::IvecI(ivec)
+                    }
+                    None => *instruction,
+                }
+            }
+            _ => *instruction,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::{Add, Div, Halt, Iret, Load, Prtc, Prti, RIndex, Syscall};
+    use lvm_machine::{Console, Interrupts, OutputBuffer, Timer, VMError, VM};
+    use lvm_parser::ParseSource;
+
+    use super::*;
+
+    /// A xorshift generator, so the programs are the same on every run.
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+    }
+
+    fn make_program(random: &mut Random, handlers: bool) -> Program {
+        // A few registers beyond the default count make some instructions fault.
+        let r = |random: &mut Random| RIndex::make(random.next(9) as u8);
+        let len = 1 + random.next(24) as usize;
+        let code = (0..len)
+            .map(|_| match random.next(if handlers { 10 } else { 9 }) {
+                0..=2 => {
+                    let value = Operand16::make(random.next(4) as u16 * 21_845);
+                    Instruction::LoadI(Load::make(r(random), value))
+                }
+                3..=4 => Instruction::AddI(Add::make(r(random), r(random), r(random))),
+                5 => Instruction::PrtiI(Prti::make(r(random))),
+                6 => Instruction::DivI(Div::make(r(random), r(random), r(random))),
+                7 => match random.next(4) {
+                    0 => Instruction::SyscallI(Syscall::make(Operand16::make(1))),
+                    1 => Instruction::PrtcI(Prtc::make(r(random))),
+                    _ => Instruction::HaltI(Halt::make()),
+                },
+                8 => Instruction::IretI(Iret::make()),
+                _ => {
+                    let vector = match random.next(4) {
+                        0 => Interrupts::ILLEGAL,
+                        1 => Interrupts::DIVISION_BY_ZERO,
+                        2 => Interrupts::INVALID_REGISTER,
+                        _ => Interrupts::TIMER,
+                    };
+                    let address = Operand16::make(random.next(len as u64) as u16);
+                    Instruction::IvecI(Ivec::make(vector.into(), address))
+                }
+            })
+            .collect();
+        Program::make(code)
+    }
+
+    /// Runs the program and returns its registers, output and result.
+    fn execute(program: &Program) -> (Vec<u16>, String, Result<(), VMError>) {
+        let output = OutputBuffer::default();
+        let mut vm = VM::new();
+        vm.set_console(Console::make(
+            Box::new("".as_bytes()),
+            Box::new(output.clone()),
+        ));
+        vm.set_timer(Timer::make(3));
+        vm.register_syscall(1, |ctx| {
+            let value = ctx.register(RIndex::make(1))?;
+            ctx.set_register(RIndex::make(2), value ^ 0x5A5A)
+        });
+        vm.load_program(program.clone());
+
+        let mut result = Ok(());
+        for _ in 0..1_000 {
+            match vm.step() {
+                Ok(Some(_)) => (),
+                Ok(None) => break,
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+        }
+        (vm.registers().to_vec(), output.to_string(), result)
+    }
+
+    fn differential(optimizer: Optimizer, seed: u64, handlers: bool) -> usize {
+        let mut random = Random(seed);
+        let mut removed = 0;
+        for _ in 0..500 {
+            let program = make_program(&mut random, handlers);
+            let optimized = optimizer.optimize(&program);
+            assert_eq!(
+                execute(&program),
+                execute(&optimized),
+                "\n{}\n--\n{}",
+                program,
+                optimized
+            );
+            removed += program.len() - optimized.len();
+        }
+        removed
+    }
+
+    #[test]
+    fn differential_passes() {
+        let none = Optimizer::default()
+            .with_dead_stores(false)
+            .with_folding(false)
+            .with_no_ops(false)
+            .with_unreachable(false);
+        assert_eq!(0, differential(none, 1, true));
+
+        let passes = [
+            none.with_dead_stores(true),
+            none.with_folding(true),
+            none.with_no_ops(true),
+            none.with_unreachable(true),
+            Optimizer::default(),
+        ];
+        for (seed, optimizer) in passes.into_iter().enumerate() {
+            differential(optimizer, seed as u64 + 2, false);
+            differential(optimizer, seed as u64 + 20, true);
+        }
+        assert!(differential(Optimizer::default(), 7, false) > 0);
+    }
+
+    #[test]
+    fn chains() {
+        let source = "LOAD $0 #1\nLOAD $1 #2\nADD $0 $1 $2\nADD $2 $2 $2\nLOAD $0 #1\nPRTI $2\nHALT\nPRTI $0";
+        let program = Program::from_source(source).unwrap();
+        let optimized = Optimizer::default().optimize(&program);
+        assert_eq!(
+            "LOAD $0 #1\nLOAD $1 #2\nLOAD $2 #6\nPRTI $2\nHALT",
+            optimized.to_string()
+        );
+    }
+
+    #[test]
+    fn scheduled() {
+        let program = Program::from_source("LOAD $1 #2\nLOAD $1 #2\nSEND $1 $1").unwrap();
+        let optimized = Optimizer::default().optimize(&program);
+        assert_eq!("LOAD $1 #2\nSEND $1 $1", optimized.to_string());
+
+        // The process entry keeps its offset and is folded without the constants before it.
+        let source = "LOAD $1 #2\nLOAD $2 #3\nADD $1 $2 $3\nLOAD $4 #6\nSPAWN $4 $5\nHALT\n\
+                      LOAD $3 #1\nADD $1 $2 $3\nPRTI $3\nHALT\nPRTI $1";
+        let program = Program::from_source(source).unwrap();
+        let optimized = Optimizer::default().optimize(&program);
+        assert_eq!(
+            "LOAD $1 #2\nLOAD $2 #3\nLOAD $3 #5\nLOAD $4 #6\nSPAWN $4 $5\nHALT\n\
+             ADD $1 $2 $3\nPRTI $3\nHALT\nPRTI $1",
+            optimized.to_string()
+        );
+
+        // A process may start anywhere when the target is not a constant.
+        let program = Program::from_source("READ $1\nSPAWN $1 $2\nLOAD $3 #1\nLOAD $3 #2").unwrap();
+        assert_eq!(program, Optimizer::default().optimize(&program));
+    }
+
+    #[test]
+    fn retarget() {
+        let ivec = |address| {
+            Instruction::IvecI(Ivec::make(
+                Interrupts::TIMER.into(),
+                Operand16::make(address),
+            ))
+        };
+        let halt = Instruction::HaltI(Halt::make());
+        let code = vec![ivec(3), halt, halt, halt];
+        assert_eq!(
+            vec![ivec(1), halt],
+            remove(&code, &[true, false, false, true])
+        );
+        assert_eq!(vec![ivec(1)], remove(&code, &[true, false, false, false]));
+    }
+}
@@ -0,0 +1,74 @@
+use lvm_analysis::Cfg;
+use lvm_core::{Instruction, Program};
+use lvm_machine::Interrupts;
+
+use crate::optimizer::remove;
+
+/// Removes the blocks no path from the entry reaches, like the code after `HALT`
+/// or the handlers whose `IVEC` never runs.
+///
+/// An `IRET` outside a handler raises an `ILLEGAL` interrupt, whose handler returns
+/// after it, so when the program installs one the code after every `IRET` is kept.
+pub(crate) fn run(code: &[Instruction]) -> Vec<Instruction> {
+    let cfg = Cfg::build(&Program::make(code.to_vec()));
+    let illegal = code.iter().any(|i| match i {
+        Instruction::IvecI(ivec) => u8::from(ivec.vector()) == Interrupts::ILLEGAL,
+        _ => false,
+    });
+    let mut reached = vec![false; cfg.blocks().len()];
+    let mut work = vec![];
+    if !code.is_empty() {
+        work.push(0);
+    }
+
+    while let Some(block) = work.pop() {
+        if !std::mem::replace(&mut reached[block], true) {
+            work.extend(cfg.successors(block).map(|edge| edge.to()));
+
+            let last = cfg.blocks()[block].last();
+            if illegal && matches!(code[last], Instruction::IretI(_)) {
+                work.extend(cfg.block_of(last + 1));
+            }
+        }
+    }
+
+    let mut keep = vec![true; code.len()];
+    for (block, _) in reached.iter().enumerate().filter(|(_, r)| !**r) {
+        for offset in cfg.blocks()[block].range() {
+            keep[offset] = false;
+        }
+    }
+
+    remove(code, &keep)
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_parser::ParseSource;
+
+    use super::*;
+
+    fn code(source: &str) -> Vec<Instruction> {
+        Program::from_source(source).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn after_halt() {
+        let input = code("LOAD $1 #2\nHALT\nLOAD $1 #3\nPRTI $1");
+        assert_eq!(code("LOAD $1 #2\nHALT"), run(&input));
+    }
+
+    #[test]
+    fn handlers() {
+        // The dead code before the handler moves it, so IVEC is retargeted.
+        let input = code("IVEC #8 #4\nHALT\nPRTI $1\nIRET\nPRTI $2\nIRET\nIVEC #2 #2\nIRET");
+        assert_eq!(code("IVEC #8 #2\nHALT\nPRTI $2\nIRET"), run(&input));
+    }
+
+    #[test]
+    fn faulting_iret() {
+        // The IRET outside a handler faults, and the handler returns to the LOAD.
+        let input = code("IVEC #6 #4\nIRET\nLOAD $1 #7\nHALT\nIRET");
+        assert_eq!(input, run(&input));
+    }
+}
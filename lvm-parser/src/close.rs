@@ -0,0 +1,123 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{Close, RIndex};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "close";
+
+fn close_from_str(input: &str) -> Result<&str, Close> {
+    let (input, _) = tag(Close::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx) = RIndex::parse_str(input)?;
+
+    Ok((input, Close::make(indx)))
+}
+
+fn close_from_hex_str(input: &str) -> Result<&str, Close> {
+    let (input, _) = tag(Close::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, Close::make(indx)))
+}
+
+fn close_from_bytes(input: &[u8]) -> Result<&[u8], Close> {
+    let (input, _) = tag([Close::ID])(input)?;
+    let (input, indx) = RIndex::parse_bytes(input)?;
+    let (input, _) = tag([0u8, 0u8])(input)?;
+
+    Ok((input, Close::make(indx)))
+}
+
+impl ParseString for Close {
+    type Output = Self;
+
+    /// Tries to create an [`Close`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Close;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "CLOSE $10";
+    /// let (_, close) = Close::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, close.index().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, close_from_str)(input)
+    }
+
+    /// Tries to create an [`Close`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Close;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "CLOSE $0A";
+    /// let (_, close) = Close::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, close.index().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, close_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for Close {
+    type Output = Self;
+
+    /// Tries to create an [`Close`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Close;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [10u8, 10u8, 0u8, 0u8].as_slice();
+    /// let (_, close) = Close::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, close.index().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, close_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, close) = Close::parse_str("CLOSE $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(10u8, close.index().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, close) = Close::parse_hex_str("CLOSE $0A").unwrap();
+        assert_eq!(10u8, close.index().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [10u8, 10u8, 0u8, 0u8, 9u8].as_slice();
+        let (rst, close) = Close::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(10u8, close.index().into());
+    }
+
+    #[test]
+    fn parse_bytes_failed() {
+        let input = [10u8, 10u8, 0u8, 1u8].as_slice();
+        assert!(Close::parse_bytes(input).is_err());
+    }
+}
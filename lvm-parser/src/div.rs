@@ -0,0 +1,158 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{Div, RIndex};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "div";
+
+fn load_from_str(input: &str) -> Result<&str, Div> {
+    let (input, _) = tag(Div::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx1) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx2) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx3) = RIndex::parse_str(input)?;
+
+    let div = Div::make(indx1, indx2, indx3);
+
+    Ok((input, div))
+}
+
+fn load_from_hex_str(input: &str) -> Result<&str, Div> {
+    let (input, _) = tag(Div::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx1) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx2) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx3) = RIndex::parse_hex_str(input)?;
+
+    let div = Div::make(indx1, indx2, indx3);
+
+    Ok((input, div))
+}
+
+fn load_from_bytes(input: &[u8]) -> Result<&[u8], Div> {
+    let (input, _) = tag([Div::ID])(input)?;
+    let (input, indx1) = RIndex::parse_bytes(input)?;
+    let (input, indx2) = RIndex::parse_bytes(input)?;
+    let (input, indx3) = RIndex::parse_bytes(input)?;
+
+    let div = Div::make(indx1, indx2, indx3);
+
+    Ok((input, div))
+}
+
+impl ParseString for Div {
+    type Output = Self;
+
+    /// Tries to create an [`Div`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Div, RIndex};
+    /// use lvm_parser::*;
+    ///
+    /// let input = "DIV $10 $20 $30";
+    /// let (_, div) = Div::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, div.index1().into());
+    /// assert_eq!(20u8, div.index2().into());
+    ///assert_eq!(30u8, div.index3().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, load_from_str)(input)
+    }
+
+    /// Tries to create an [`Div`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Div, RIndex};
+    /// use lvm_parser::*;
+    ///
+    /// let input = "DIV $0A $14 $1E";
+    /// let (_, div) = Div::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, div.index1().into());
+    /// assert_eq!(20u8, div.index2().into());
+    ///assert_eq!(30u8, div.index3().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, load_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for Div {
+    type Output = Self;
+
+    /// Tries to create an [`Div`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Div, RIndex};
+    /// use lvm_parser::*;
+    ///
+    /// let input = [11u8, 10u8, 20u8, 30u8, 0u8].as_slice();
+    /// let (_, div) = Div::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, div.index1().into());
+    /// assert_eq!(20u8, div.index2().into());
+    ///assert_eq!(30u8, div.index3().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, load_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let input = "DIV $10 $20 $30";
+
+        let res = Div::parse_str(input);
+        assert!(res.is_ok());
+
+        let div = res.unwrap().1;
+
+        assert_eq!(10u8, div.index1().into());
+        assert_eq!(20u8, div.index2().into());
+        assert_eq!(30u8, div.index3().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let input = "DIV $0A $14 $1E";
+
+        let res = Div::parse_hex_str(input);
+        assert!(res.is_ok());
+
+        let div = res.unwrap().1;
+
+        assert_eq!(10u8, div.index1().into());
+        assert_eq!(20u8, div.index2().into());
+        assert_eq!(30u8, div.index3().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [11u8, 10u8, 20u8, 30u8, 0u8].as_slice();
+
+        let res = Div::parse_bytes(input);
+        assert!(res.is_ok());
+
+        let (rst, div) = res.unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(10u8, div.index1().into());
+        assert_eq!(20u8, div.index2().into());
+        assert_eq!(30u8, div.index3().into());
+    }
+}
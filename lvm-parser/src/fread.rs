@@ -0,0 +1,129 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{FRead, RIndex};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "fread";
+
+fn fread_from_str(input: &str) -> Result<&str, FRead> {
+    let (input, _) = tag(FRead::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, handle) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, buffer) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, len) = RIndex::parse_str(input)?;
+
+    Ok((input, FRead::make(handle, buffer, len)))
+}
+
+fn fread_from_hex_str(input: &str) -> Result<&str, FRead> {
+    let (input, _) = tag(FRead::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, handle) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, buffer) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, len) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, FRead::make(handle, buffer, len)))
+}
+
+fn fread_from_bytes(input: &[u8]) -> Result<&[u8], FRead> {
+    let (input, _) = tag([FRead::ID])(input)?;
+    let (input, handle) = RIndex::parse_bytes(input)?;
+    let (input, buffer) = RIndex::parse_bytes(input)?;
+    let (input, len) = RIndex::parse_bytes(input)?;
+
+    Ok((input, FRead::make(handle, buffer, len)))
+}
+
+impl ParseString for FRead {
+    type Output = Self;
+
+    /// Tries to create an [`FRead`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::FRead;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "FREAD $1 $2 $10";
+    /// let (_, fread) = FRead::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, fread.len().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, fread_from_str)(input)
+    }
+
+    /// Tries to create an [`FRead`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::FRead;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "FREAD $01 $02 $0A";
+    /// let (_, fread) = FRead::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, fread.len().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, fread_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for FRead {
+    type Output = Self;
+
+    /// Tries to create an [`FRead`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::FRead;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [8u8, 1u8, 2u8, 10u8].as_slice();
+    /// let (_, fread) = FRead::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, fread.len().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, fread_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, fread) = FRead::parse_str("FREAD $1 $2 $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(1u8, fread.handle().into());
+        assert_eq!(2u8, fread.buffer().into());
+        assert_eq!(10u8, fread.len().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, fread) = FRead::parse_hex_str("FREAD $01 $02 $0A").unwrap();
+        assert_eq!(10u8, fread.len().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [8u8, 1u8, 2u8, 10u8, 9u8].as_slice();
+        let (rst, fread) = FRead::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(1u8, fread.handle().into());
+        assert_eq!(10u8, fread.len().into());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{FWrite, RIndex};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "fwrite";
+
+fn fwrite_from_str(input: &str) -> Result<&str, FWrite> {
+    let (input, _) = tag(FWrite::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, handle) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, buffer) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, len) = RIndex::parse_str(input)?;
+
+    Ok((input, FWrite::make(handle, buffer, len)))
+}
+
+fn fwrite_from_hex_str(input: &str) -> Result<&str, FWrite> {
+    let (input, _) = tag(FWrite::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, handle) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, buffer) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, len) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, FWrite::make(handle, buffer, len)))
+}
+
+fn fwrite_from_bytes(input: &[u8]) -> Result<&[u8], FWrite> {
+    let (input, _) = tag([FWrite::ID])(input)?;
+    let (input, handle) = RIndex::parse_bytes(input)?;
+    let (input, buffer) = RIndex::parse_bytes(input)?;
+    let (input, len) = RIndex::parse_bytes(input)?;
+
+    Ok((input, FWrite::make(handle, buffer, len)))
+}
+
+impl ParseString for FWrite {
+    type Output = Self;
+
+    /// Tries to create an [`FWrite`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::FWrite;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "FWRITE $1 $2 $10";
+    /// let (_, fwrite) = FWrite::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, fwrite.len().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, fwrite_from_str)(input)
+    }
+
+    /// Tries to create an [`FWrite`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::FWrite;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "FWRITE $01 $02 $0A";
+    /// let (_, fwrite) = FWrite::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, fwrite.len().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, fwrite_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for FWrite {
+    type Output = Self;
+
+    /// Tries to create an [`FWrite`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::FWrite;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [9u8, 1u8, 2u8, 10u8].as_slice();
+    /// let (_, fwrite) = FWrite::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, fwrite.len().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, fwrite_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, fwrite) = FWrite::parse_str("FWRITE $1 $2 $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(1u8, fwrite.handle().into());
+        assert_eq!(2u8, fwrite.buffer().into());
+        assert_eq!(10u8, fwrite.len().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, fwrite) = FWrite::parse_hex_str("FWRITE $01 $02 $0A").unwrap();
+        assert_eq!(10u8, fwrite.len().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [9u8, 1u8, 2u8, 10u8, 9u8].as_slice();
+        let (rst, fwrite) = FWrite::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(1u8, fwrite.handle().into());
+        assert_eq!(10u8, fwrite.len().into());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::Halt;
+use nom::{bytes::complete::tag, error::context};
+
+const CONTEXT: &str = "halt";
+
+fn halt_from_str(input: &str) -> Result<&str, Halt> {
+    let (input, _) = tag(Halt::PREFIX)(input)?;
+    Ok((input, Halt::make()))
+}
+
+fn halt_from_bytes(input: &[u8]) -> Result<&[u8], Halt> {
+    let (input, _) = tag([Halt::ID, 0u8, 0u8, 0u8])(input)?;
+    Ok((input, Halt::make()))
+}
+
+impl ParseString for Halt {
+    type Output = Self;
+
+    /// Tries to create an [`Halt`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Halt;
+    /// use lvm_parser::*;
+    ///
+    /// let (_, halt) = Halt::parse_str("HALT").unwrap();
+    /// assert_eq!(Halt::make(), halt);
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, halt_from_str)(input)
+    }
+
+    /// Tries to create an [`Halt`] instance by parsing a hex string
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, halt_from_str)(input)
+    }
+}
+
+impl ParseBytes for Halt {
+    type Output = Self;
+
+    /// Tries to create an [`Halt`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Halt;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [14u8, 0u8, 0u8, 0u8].as_slice();
+    /// let (_, halt) = Halt::parse_bytes(input).unwrap();
+    /// assert_eq!(Halt::make(), halt);
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, halt_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, _) = Halt::parse_str("HALT ABC").unwrap();
+        assert_eq!(" ABC", rst);
+        assert!(Halt::parse_hex_str("HALT").is_ok());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [14u8, 0u8, 0u8, 0u8, 9u8].as_slice();
+        let (rst, _) = Halt::parse_bytes(input).unwrap();
+        assert_eq!(1, rst.len());
+
+        assert!(Halt::parse_bytes([14u8, 1u8, 0u8, 0u8].as_slice()).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{ParseBytes, ParseString, Result};
 
-use lvm_core::{Add, Instruction, Load};
+use lvm_core::{
+    Add, Close, Div, FRead, FWrite, Halt, Instruction, Iret, Ivec, Load, Open, Prtc, Prti, Read,
+    RecvMsg, SendMsg, Spawn, Syscall,
+};
 use nom::{branch::alt, combinator::map, error::context};
 
 const CONTEXT: &str = "instruction";
@@ -11,16 +14,52 @@ impl ParseString for Instruction {
     fn parse_str(input: &str) -> Result<&str, Self::Output> {
         let load = map(Load::parse_str, Instruction::LoadI);
         let add = map(Add::parse_str, Instruction::AddI);
-
-        let f = alt((load, add));
+        let syscall = map(Syscall::parse_str, Instruction::SyscallI);
+        let prti = map(Prti::parse_str, Instruction::PrtiI);
+        let prtc = map(Prtc::parse_str, Instruction::PrtcI);
+        let read = map(Read::parse_str, Instruction::ReadI);
+        let open = map(Open::parse_str, Instruction::OpenI);
+        let fread = map(FRead::parse_str, Instruction::FReadI);
+        let fwrite = map(FWrite::parse_str, Instruction::FWriteI);
+        let close = map(Close::parse_str, Instruction::CloseI);
+        let div = map(Div::parse_str, Instruction::DivI);
+        let ivec = map(Ivec::parse_str, Instruction::IvecI);
+        let iret = map(Iret::parse_str, Instruction::IretI);
+        let halt = map(Halt::parse_str, Instruction::HaltI);
+        let spawn = map(Spawn::parse_str, Instruction::SpawnI);
+        let send = map(SendMsg::parse_str, Instruction::SendI);
+        let recv = map(RecvMsg::parse_str, Instruction::RecvI);
+
+        let f = alt((
+            load, add, syscall, prti, prtc, read, open, fread, fwrite, close, div, ivec, iret,
+            halt, spawn, send, recv,
+        ));
         context(CONTEXT, f)(input)
     }
 
     fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
         let load = map(Load::parse_hex_str, Instruction::LoadI);
         let add = map(Add::parse_hex_str, Instruction::AddI);
-
-        let f = alt((load, add));
+        let syscall = map(Syscall::parse_hex_str, Instruction::SyscallI);
+        let prti = map(Prti::parse_hex_str, Instruction::PrtiI);
+        let prtc = map(Prtc::parse_hex_str, Instruction::PrtcI);
+        let read = map(Read::parse_hex_str, Instruction::ReadI);
+        let open = map(Open::parse_hex_str, Instruction::OpenI);
+        let fread = map(FRead::parse_hex_str, Instruction::FReadI);
+        let fwrite = map(FWrite::parse_hex_str, Instruction::FWriteI);
+        let close = map(Close::parse_hex_str, Instruction::CloseI);
+        let div = map(Div::parse_hex_str, Instruction::DivI);
+        let ivec = map(Ivec::parse_hex_str, Instruction::IvecI);
+        let iret = map(Iret::parse_hex_str, Instruction::IretI);
+        let halt = map(Halt::parse_hex_str, Instruction::HaltI);
+        let spawn = map(Spawn::parse_hex_str, Instruction::SpawnI);
+        let send = map(SendMsg::parse_hex_str, Instruction::SendI);
+        let recv = map(RecvMsg::parse_hex_str, Instruction::RecvI);
+
+        let f = alt((
+            load, add, syscall, prti, prtc, read, open, fread, fwrite, close, div, ivec, iret,
+            halt, spawn, send, recv,
+        ));
         context(CONTEXT, f)(input)
     }
 }
@@ -31,8 +70,26 @@ impl ParseBytes for Instruction {
     fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
         let load = map(Load::parse_bytes, Instruction::LoadI);
         let add = map(Add::parse_bytes, Instruction::AddI);
-
-        let f = alt((load, add));
+        let syscall = map(Syscall::parse_bytes, Instruction::SyscallI);
+        let prti = map(Prti::parse_bytes, Instruction::PrtiI);
+        let prtc = map(Prtc::parse_bytes, Instruction::PrtcI);
+        let read = map(Read::parse_bytes, Instruction::ReadI);
+        let open = map(Open::parse_bytes, Instruction::OpenI);
+        let fread = map(FRead::parse_bytes, Instruction::FReadI);
+        let fwrite = map(FWrite::parse_bytes, Instruction::FWriteI);
+        let close = map(Close::parse_bytes, Instruction::CloseI);
+        let div = map(Div::parse_bytes, Instruction::DivI);
+        let ivec = map(Ivec::parse_bytes, Instruction::IvecI);
+        let iret = map(Iret::parse_bytes, Instruction::IretI);
+        let halt = map(Halt::parse_bytes, Instruction::HaltI);
+        let spawn = map(Spawn::parse_bytes, Instruction::SpawnI);
+        let send = map(SendMsg::parse_bytes, Instruction::SendI);
+        let recv = map(RecvMsg::parse_bytes, Instruction::RecvI);
+
+        let f = alt((
+            load, add, syscall, prti, prtc, read, open, fread, fwrite, close, div, ivec, iret,
+            halt, spawn, send, recv,
+        ));
         context(CONTEXT, f)(input)
     }
 }
@@ -55,7 +112,7 @@ mod tests {
                 assert_eq!(10u8, load.index().into());
                 assert_eq!(500u16, load.operand().into());
             }
-            _ => assert!(false),
+            _ => panic!("expected a load instruction"),
         }
     }
 
@@ -73,7 +130,66 @@ mod tests {
                 assert_eq!(10u8, load.index().into());
                 assert_eq!(500u16, load.operand().into());
             }
-            _ => assert!(false),
+            _ => panic!("expected a load instruction"),
+        }
+    }
+
+    #[test]
+    fn parse_syscall() {
+        let (_, instruction) = Instruction::parse_str("SYSCALL #7").unwrap();
+        assert_eq!("SYSCALL #7", instruction.to_string());
+
+        let input = [3u8, 0u8, 7u8, 0u8].as_slice();
+        let (_, instruction) = Instruction::parse_bytes(input).unwrap();
+        assert_eq!("SYSCALL #7", instruction.to_string());
+    }
+
+    #[test]
+    fn parse_console() {
+        for input in ["PRTI $1", "PRTC $2", "READ $3"] {
+            let (_, instruction) = Instruction::parse_str(input).unwrap();
+            assert_eq!(input, instruction.to_string());
+
+            let bytes: [u8; 4] = instruction.into();
+            let (_, instruction) = Instruction::parse_bytes(&bytes).unwrap();
+            assert_eq!(input, instruction.to_string());
+        }
+    }
+
+    #[test]
+    fn parse_files() {
+        for input in [
+            "OPEN $1 $2 $3",
+            "FREAD $1 $2 $3",
+            "FWRITE $1 $2 $3",
+            "CLOSE $1",
+        ] {
+            let (_, instruction) = Instruction::parse_str(input).unwrap();
+            assert_eq!(input, instruction.to_string());
+
+            let bytes: [u8; 4] = instruction.into();
+            let (_, instruction) = Instruction::parse_bytes(&bytes).unwrap();
+            assert_eq!(input, instruction.to_string());
+        }
+    }
+
+    #[test]
+    fn parse_control() {
+        for input in [
+            "DIV $1 $2 $3",
+            "IVEC #1 #20",
+            "IRET",
+            "HALT",
+            "SPAWN $1 $2",
+            "SEND $1 $2",
+            "RECV $1 $2",
+        ] {
+            let (_, instruction) = Instruction::parse_str(input).unwrap();
+            assert_eq!(input, instruction.to_string());
+
+            let bytes: [u8; 4] = instruction.into();
+            let (_, instruction) = Instruction::parse_bytes(&bytes).unwrap();
+            assert_eq!(input, instruction.to_string());
         }
     }
 
@@ -91,7 +207,7 @@ mod tests {
                 assert_eq!(10u8, load.index().into());
                 assert_eq!(((50u16 << 8) + 1u16), load.operand().into());
             }
-            _ => assert!(false),
+            _ => panic!("expected a load instruction"),
         }
     }
 }
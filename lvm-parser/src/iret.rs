@@ -0,0 +1,81 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::Iret;
+use nom::{bytes::complete::tag, error::context};
+
+const CONTEXT: &str = "iret";
+
+fn iret_from_str(input: &str) -> Result<&str, Iret> {
+    let (input, _) = tag(Iret::PREFIX)(input)?;
+    Ok((input, Iret::make()))
+}
+
+fn iret_from_bytes(input: &[u8]) -> Result<&[u8], Iret> {
+    let (input, _) = tag([Iret::ID, 0u8, 0u8, 0u8])(input)?;
+    Ok((input, Iret::make()))
+}
+
+impl ParseString for Iret {
+    type Output = Self;
+
+    /// Tries to create an [`Iret`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Iret;
+    /// use lvm_parser::*;
+    ///
+    /// let (_, iret) = Iret::parse_str("IRET").unwrap();
+    /// assert_eq!(Iret::make(), iret);
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, iret_from_str)(input)
+    }
+
+    /// Tries to create an [`Iret`] instance by parsing a hex string
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, iret_from_str)(input)
+    }
+}
+
+impl ParseBytes for Iret {
+    type Output = Self;
+
+    /// Tries to create an [`Iret`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Iret;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [13u8, 0u8, 0u8, 0u8].as_slice();
+    /// let (_, iret) = Iret::parse_bytes(input).unwrap();
+    /// assert_eq!(Iret::make(), iret);
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, iret_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, _) = Iret::parse_str("IRET ABC").unwrap();
+        assert_eq!(" ABC", rst);
+        assert!(Iret::parse_hex_str("IRET").is_ok());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [13u8, 0u8, 0u8, 0u8, 9u8].as_slice();
+        let (rst, _) = Iret::parse_bytes(input).unwrap();
+        assert_eq!(1, rst.len());
+
+        assert!(Iret::parse_bytes([13u8, 1u8, 0u8, 0u8].as_slice()).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{Ivec, Operand16, Operand8};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "ivec";
+
+fn ivec_from_str(input: &str) -> Result<&str, Ivec> {
+    let (input, _) = tag(Ivec::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, vector) = Operand8::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, address) = Operand16::parse_str(input)?;
+
+    let ivec = Ivec::make(vector, address);
+
+    Ok((input, ivec))
+}
+
+fn ivec_from_hex_str(input: &str) -> Result<&str, Ivec> {
+    let (input, _) = tag(Ivec::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, vector) = Operand8::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, address) = Operand16::parse_hex_str(input)?;
+
+    let ivec = Ivec::make(vector, address);
+
+    Ok((input, ivec))
+}
+
+fn ivec_from_bytes(input: &[u8]) -> Result<&[u8], Ivec> {
+    let (input, _) = tag([Ivec::ID])(input)?;
+    let (input, vector) = Operand8::parse_bytes(input)?;
+    let (input, address) = Operand16::parse_bytes(input)?;
+
+    let ivec = Ivec::make(vector, address);
+
+    Ok((input, ivec))
+}
+
+impl ParseString for Ivec {
+    type Output = Self;
+
+    /// Tries to create an [`Ivec`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Ivec, Operand16, Operand8};
+    /// use lvm_parser::*;
+    ///
+    /// let input = "IVEC #10 #500";
+    /// let (_, ivec) = Ivec::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, ivec.vector().into());
+    /// assert_eq!(500u16, ivec.address().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, ivec_from_str)(input)
+    }
+
+    /// Tries to create an [`Ivec`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Ivec, Operand16, Operand8};
+    /// use lvm_parser::*;
+    ///
+    /// let input = "IVEC #0A #01F4";
+    /// let (_, ivec) = Ivec::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, ivec.vector().into());
+    /// assert_eq!(500u16, ivec.address().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, ivec_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for Ivec {
+    type Output = Self;
+
+    /// Tries to create an [`Ivec`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::{Ivec, Operand16, Operand8};
+    /// use lvm_parser::*;
+    ///
+    /// let input = [12u8, 10u8, 50u8, 1u8, 0u8].as_slice();
+    /// let (_, ivec) = Ivec::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, ivec.vector().into());
+    /// assert_eq!(((50u16 << 8) + 1u16), ivec.address().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, ivec_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let input = "IVEC #10 #500";
+
+        let res = Ivec::parse_str(input);
+        assert!(res.is_ok());
+
+        let ivec = res.unwrap().1;
+
+        assert_eq!(10u8, ivec.vector().into());
+        assert_eq!(500u16, ivec.address().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let input = "IVEC #0A #01F4";
+
+        let res = Ivec::parse_hex_str(input);
+        assert!(res.is_ok());
+
+        let ivec = res.unwrap().1;
+
+        assert_eq!(10u8, ivec.vector().into());
+        assert_eq!(500u16, ivec.address().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [12u8, 10u8, 50u8, 1u8, 0u8].as_slice();
+
+        let res = Ivec::parse_bytes(input);
+        assert!(res.is_ok());
+
+        let (rst, ivec) = res.unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(10u8, ivec.vector().into());
+        assert_eq!(((50u16 << 8) + 1u16), ivec.address().into());
+    }
+}
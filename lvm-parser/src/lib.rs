@@ -1,18 +1,45 @@
 mod add;
+mod close;
+mod div;
+mod fread;
+mod fwrite;
+mod halt;
 mod instruction;
+mod iret;
+mod ivec;
+mod listing;
 mod load;
+mod open;
 mod operand16;
 mod operand8;
 mod program;
+mod prtc;
+mod prti;
+mod read;
+mod recv;
 mod rindex;
+mod send;
+mod source;
+mod spawn;
+mod syscall;
 
+// The modules only implement the parsing traits, their re-exports are empty for now.
+#[allow(unused_imports)]
 pub use add::*;
+#[allow(unused_imports)]
 pub use instruction::*;
+pub use listing::*;
+#[allow(unused_imports)]
 pub use load::*;
+#[allow(unused_imports)]
 pub use operand16::*;
+#[allow(unused_imports)]
 pub use operand8::*;
+#[allow(unused_imports)]
 pub use program::*;
+#[allow(unused_imports)]
 pub use rindex::*;
+pub use source::*;
 
 use nom::{error::VerboseError, IResult};
 
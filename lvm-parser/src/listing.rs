@@ -0,0 +1,165 @@
+use crate::{from_str, Grammar, Location, ParseSource, ParseString, SourceError};
+
+use lvm_core::{Instruction, Program};
+
+/// An element of a source.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Item {
+    /// An instruction.
+    Instruction(Instruction),
+    /// A label, without the `:`.
+    Label(String),
+    /// A comment, without the `;`.
+    Comment(String),
+}
+
+/// A source as it is written: the instructions, with their mnemonics in any case,
+/// the labels and the comments. Only the listings accept them, a [`Program`] source
+/// has uppercase mnemonics and no labels or comments.
+///
+/// The instructions do not refer to the labels, they only name the code.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_parser::*;
+///
+/// let (listing, locations) = Listing::from_located_source("start:\n  load $1 #2 ; two").unwrap();
+/// assert_eq!(Item::Label("start".to_string()), listing.items()[0]);
+/// assert_eq!(Item::Comment(" two".to_string()), listing.items()[2]);
+/// assert_eq!(Location::make(2, 14), locations[2]);
+/// assert_eq!(1, listing.program().len());
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Listing {
+    items: Vec<Item>,
+}
+
+impl Listing {
+    /// Creates a [`Listing`] instance.
+    pub fn make(items: Vec<Item>) -> Self {
+        Self { items }
+    }
+
+    /// Returns the items.
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Returns the program of the instructions.
+    pub fn program(&self) -> Program {
+        let instructions = self.items.iter().filter_map(|item| match item {
+            Item::Instruction(i) => Some(*i),
+            _ => None,
+        });
+        Program::make(instructions.collect())
+    }
+}
+
+impl From<Program> for Listing {
+    fn from(program: Program) -> Self {
+        Self::make(program.into_iter().map(Item::Instruction).collect())
+    }
+}
+
+impl ParseSource for Listing {
+    /// Tries to create a [`Listing`] instance from a complete source.
+    fn from_source(input: &str) -> Result<Self, SourceError> {
+        Self::from_located_source(input).map(|(listing, _)| listing)
+    }
+
+    /// Tries to create a [`Listing`] instance from a complete source,
+    /// with the location of every item.
+    fn from_located_source(input: &str) -> Result<(Self, Vec<Location>), SourceError> {
+        from_str(input, Instruction::parse_str, Grammar::Listing)
+            .map(|(items, locations)| (Self::make(items), locations))
+    }
+
+    /// Tries to create a [`Listing`] instance from a complete hex source.
+    fn from_hex_source(input: &str) -> Result<Self, SourceError> {
+        Self::from_located_hex_source(input).map(|(listing, _)| listing)
+    }
+
+    /// Tries to create a [`Listing`] instance from a complete hex source,
+    /// with the location of every item.
+    fn from_located_hex_source(input: &str) -> Result<(Self, Vec<Location>), SourceError> {
+        from_str(input, Instruction::parse_hex_str, Grammar::Listing)
+            .map(|(items, locations)| (Self::make(items), locations))
+    }
+
+    /// Tries to create a [`Listing`] instance from a complete slice of bytes,
+    /// which has no labels or comments.
+    fn from_binary(input: &[u8]) -> Result<Self, SourceError> {
+        Program::from_binary(input).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_source() {
+        let input = "; add\nstart: LOAD $1 #2;two\n\n  _loop_1:\nADD $1 $1 $2 ;\nHALT";
+        let listing = Listing::from_source(input).unwrap();
+
+        let items: Vec<String> = listing
+            .items()
+            .iter()
+            .map(|item| match item {
+                Item::Instruction(i) => i.to_string(),
+                Item::Label(name) => format!("{}:", name),
+                Item::Comment(text) => format!(";{}", text),
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                "; add",
+                "start:",
+                "LOAD $1 #2",
+                ";two",
+                "_loop_1:",
+                "ADD $1 $1 $2",
+                ";",
+                "HALT"
+            ],
+            items
+        );
+        assert_eq!(3, listing.program().len());
+    }
+
+    #[test]
+    fn from_hex_source() {
+        let input = "main:\nLOAD $0A #01F4 ; 500";
+        let listing = Listing::from_hex_source(input).unwrap();
+
+        assert_eq!(3, listing.items().len());
+        assert_eq!(1, listing.program().len());
+    }
+
+    #[test]
+    fn from_source_any_case() {
+        let input = "; sum\nstart:\n  load $1 #2 ; two\n  Add $1 $1 $2;\n";
+        let (listing, locations) = Listing::from_located_source(input).unwrap();
+
+        assert_eq!(
+            Program::from_source("LOAD $1 #2\nADD $1 $1 $2").unwrap(),
+            listing.program()
+        );
+        assert_eq!(Location::make(3, 3), locations[2]);
+        assert_eq!(Location::make(4, 3), locations[4]);
+
+        // The errors point at the same place whatever the case.
+        let lower = Listing::from_source("LOAD $1 #2\n  load $1 2").unwrap_err();
+        let upper = Listing::from_source("LOAD $1 #2\n  LOAD $1 2").unwrap_err();
+        assert_eq!(upper.location(), lower.location());
+        assert_eq!(Some(2), lower.location().map(|l| l.line()));
+    }
+
+    #[test]
+    fn from_source_failed() {
+        assert!(Listing::from_source("start:LOAD $1 #2").is_err());
+        assert!(Listing::from_source("1start: LOAD $1 #2").is_err());
+        assert!(Listing::from_source("LOAD $1 #2 LOAD").is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{Open, RIndex};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "open";
+
+fn open_from_str(input: &str) -> Result<&str, Open> {
+    let (input, _) = tag(Open::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, path) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, mode) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, handle) = RIndex::parse_str(input)?;
+
+    Ok((input, Open::make(path, mode, handle)))
+}
+
+fn open_from_hex_str(input: &str) -> Result<&str, Open> {
+    let (input, _) = tag(Open::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, path) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, mode) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, handle) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, Open::make(path, mode, handle)))
+}
+
+fn open_from_bytes(input: &[u8]) -> Result<&[u8], Open> {
+    let (input, _) = tag([Open::ID])(input)?;
+    let (input, path) = RIndex::parse_bytes(input)?;
+    let (input, mode) = RIndex::parse_bytes(input)?;
+    let (input, handle) = RIndex::parse_bytes(input)?;
+
+    Ok((input, Open::make(path, mode, handle)))
+}
+
+impl ParseString for Open {
+    type Output = Self;
+
+    /// Tries to create an [`Open`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Open;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "OPEN $1 $2 $10";
+    /// let (_, open) = Open::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, open.handle().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, open_from_str)(input)
+    }
+
+    /// Tries to create an [`Open`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Open;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "OPEN $01 $02 $0A";
+    /// let (_, open) = Open::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, open.handle().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, open_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for Open {
+    type Output = Self;
+
+    /// Tries to create an [`Open`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Open;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [7u8, 1u8, 2u8, 10u8].as_slice();
+    /// let (_, open) = Open::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, open.handle().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, open_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, open) = Open::parse_str("OPEN $1 $2 $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(1u8, open.path().into());
+        assert_eq!(2u8, open.mode().into());
+        assert_eq!(10u8, open.handle().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, open) = Open::parse_hex_str("OPEN $01 $02 $0A").unwrap();
+        assert_eq!(10u8, open.handle().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [7u8, 1u8, 2u8, 10u8, 9u8].as_slice();
+        let (rst, open) = Open::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(1u8, open.path().into());
+        assert_eq!(10u8, open.handle().into());
+    }
+}
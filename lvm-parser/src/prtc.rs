@@ -0,0 +1,123 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{Prtc, RIndex};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "prtc";
+
+fn prtc_from_str(input: &str) -> Result<&str, Prtc> {
+    let (input, _) = tag(Prtc::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx) = RIndex::parse_str(input)?;
+
+    Ok((input, Prtc::make(indx)))
+}
+
+fn prtc_from_hex_str(input: &str) -> Result<&str, Prtc> {
+    let (input, _) = tag(Prtc::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, Prtc::make(indx)))
+}
+
+fn prtc_from_bytes(input: &[u8]) -> Result<&[u8], Prtc> {
+    let (input, _) = tag([Prtc::ID])(input)?;
+    let (input, indx) = RIndex::parse_bytes(input)?;
+    let (input, _) = tag([0u8, 0u8])(input)?;
+
+    Ok((input, Prtc::make(indx)))
+}
+
+impl ParseString for Prtc {
+    type Output = Self;
+
+    /// Tries to create an [`Prtc`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Prtc;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "PRTC $10";
+    /// let (_, prtc) = Prtc::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, prtc.index().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, prtc_from_str)(input)
+    }
+
+    /// Tries to create an [`Prtc`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Prtc;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "PRTC $0A";
+    /// let (_, prtc) = Prtc::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, prtc.index().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, prtc_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for Prtc {
+    type Output = Self;
+
+    /// Tries to create an [`Prtc`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Prtc;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [5u8, 10u8, 0u8, 0u8].as_slice();
+    /// let (_, prtc) = Prtc::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, prtc.index().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, prtc_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, prtc) = Prtc::parse_str("PRTC $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(10u8, prtc.index().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, prtc) = Prtc::parse_hex_str("PRTC $0A").unwrap();
+        assert_eq!(10u8, prtc.index().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [5u8, 10u8, 0u8, 0u8, 9u8].as_slice();
+        let (rst, prtc) = Prtc::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(10u8, prtc.index().into());
+    }
+
+    #[test]
+    fn parse_bytes_failed() {
+        let input = [5u8, 10u8, 0u8, 1u8].as_slice();
+        assert!(Prtc::parse_bytes(input).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{Prti, RIndex};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "prti";
+
+fn prti_from_str(input: &str) -> Result<&str, Prti> {
+    let (input, _) = tag(Prti::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx) = RIndex::parse_str(input)?;
+
+    Ok((input, Prti::make(indx)))
+}
+
+fn prti_from_hex_str(input: &str) -> Result<&str, Prti> {
+    let (input, _) = tag(Prti::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, Prti::make(indx)))
+}
+
+fn prti_from_bytes(input: &[u8]) -> Result<&[u8], Prti> {
+    let (input, _) = tag([Prti::ID])(input)?;
+    let (input, indx) = RIndex::parse_bytes(input)?;
+    let (input, _) = tag([0u8, 0u8])(input)?;
+
+    Ok((input, Prti::make(indx)))
+}
+
+impl ParseString for Prti {
+    type Output = Self;
+
+    /// Tries to create an [`Prti`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Prti;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "PRTI $10";
+    /// let (_, prti) = Prti::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, prti.index().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, prti_from_str)(input)
+    }
+
+    /// Tries to create an [`Prti`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Prti;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "PRTI $0A";
+    /// let (_, prti) = Prti::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, prti.index().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, prti_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for Prti {
+    type Output = Self;
+
+    /// Tries to create an [`Prti`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Prti;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [4u8, 10u8, 0u8, 0u8].as_slice();
+    /// let (_, prti) = Prti::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, prti.index().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, prti_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, prti) = Prti::parse_str("PRTI $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(10u8, prti.index().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, prti) = Prti::parse_hex_str("PRTI $0A").unwrap();
+        assert_eq!(10u8, prti.index().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [4u8, 10u8, 0u8, 0u8, 9u8].as_slice();
+        let (rst, prti) = Prti::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(10u8, prti.index().into());
+    }
+
+    #[test]
+    fn parse_bytes_failed() {
+        let input = [4u8, 10u8, 0u8, 1u8].as_slice();
+        assert!(Prti::parse_bytes(input).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{RIndex, Read};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "read";
+
+fn read_from_str(input: &str) -> Result<&str, Read> {
+    let (input, _) = tag(Read::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx) = RIndex::parse_str(input)?;
+
+    Ok((input, Read::make(indx)))
+}
+
+fn read_from_hex_str(input: &str) -> Result<&str, Read> {
+    let (input, _) = tag(Read::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, indx) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, Read::make(indx)))
+}
+
+fn read_from_bytes(input: &[u8]) -> Result<&[u8], Read> {
+    let (input, _) = tag([Read::ID])(input)?;
+    let (input, indx) = RIndex::parse_bytes(input)?;
+    let (input, _) = tag([0u8, 0u8])(input)?;
+
+    Ok((input, Read::make(indx)))
+}
+
+impl ParseString for Read {
+    type Output = Self;
+
+    /// Tries to create an [`Read`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Read;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "READ $10";
+    /// let (_, read) = Read::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, read.index().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, read_from_str)(input)
+    }
+
+    /// Tries to create an [`Read`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Read;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "READ $0A";
+    /// let (_, read) = Read::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, read.index().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, read_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for Read {
+    type Output = Self;
+
+    /// Tries to create an [`Read`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Read;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [6u8, 10u8, 0u8, 0u8].as_slice();
+    /// let (_, read) = Read::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, read.index().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, read_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, read) = Read::parse_str("READ $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(10u8, read.index().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, read) = Read::parse_hex_str("READ $0A").unwrap();
+        assert_eq!(10u8, read.index().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [6u8, 10u8, 0u8, 0u8, 9u8].as_slice();
+        let (rst, read) = Read::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(10u8, read.index().into());
+    }
+
+    #[test]
+    fn parse_bytes_failed() {
+        let input = [6u8, 10u8, 0u8, 1u8].as_slice();
+        assert!(Read::parse_bytes(input).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{RIndex, RecvMsg};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "recv";
+
+fn recv_from_str(input: &str) -> Result<&str, RecvMsg> {
+    let (input, _) = tag(RecvMsg::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, sender) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, value) = RIndex::parse_str(input)?;
+
+    Ok((input, RecvMsg::make(sender, value)))
+}
+
+fn recv_from_hex_str(input: &str) -> Result<&str, RecvMsg> {
+    let (input, _) = tag(RecvMsg::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, sender) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, value) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, RecvMsg::make(sender, value)))
+}
+
+fn recv_from_bytes(input: &[u8]) -> Result<&[u8], RecvMsg> {
+    let (input, _) = tag([RecvMsg::ID])(input)?;
+    let (input, sender) = RIndex::parse_bytes(input)?;
+    let (input, value) = RIndex::parse_bytes(input)?;
+    let (input, _) = tag([0u8])(input)?;
+
+    Ok((input, RecvMsg::make(sender, value)))
+}
+
+impl ParseString for RecvMsg {
+    type Output = Self;
+
+    /// Tries to create an [`RecvMsg`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::RecvMsg;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "RECV $1 $10";
+    /// let (_, recv) = RecvMsg::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, recv.value().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, recv_from_str)(input)
+    }
+
+    /// Tries to create an [`RecvMsg`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::RecvMsg;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "RECV $01 $0A";
+    /// let (_, recv) = RecvMsg::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, recv.value().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, recv_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for RecvMsg {
+    type Output = Self;
+
+    /// Tries to create an [`RecvMsg`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::RecvMsg;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [17u8, 1u8, 10u8, 0u8].as_slice();
+    /// let (_, recv) = RecvMsg::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, recv.value().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, recv_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, recv) = RecvMsg::parse_str("RECV $1 $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(1u8, recv.sender().into());
+        assert_eq!(10u8, recv.value().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, recv) = RecvMsg::parse_hex_str("RECV $01 $0A").unwrap();
+        assert_eq!(10u8, recv.value().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [17u8, 1u8, 10u8, 0u8, 9u8].as_slice();
+        let (rst, recv) = RecvMsg::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(1u8, recv.sender().into());
+        assert!(RecvMsg::parse_bytes([17u8, 1u8, 10u8, 1u8].as_slice()).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{RIndex, SendMsg};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "send";
+
+fn send_from_str(input: &str) -> Result<&str, SendMsg> {
+    let (input, _) = tag(SendMsg::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, target) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, value) = RIndex::parse_str(input)?;
+
+    Ok((input, SendMsg::make(target, value)))
+}
+
+fn send_from_hex_str(input: &str) -> Result<&str, SendMsg> {
+    let (input, _) = tag(SendMsg::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, target) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, value) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, SendMsg::make(target, value)))
+}
+
+fn send_from_bytes(input: &[u8]) -> Result<&[u8], SendMsg> {
+    let (input, _) = tag([SendMsg::ID])(input)?;
+    let (input, target) = RIndex::parse_bytes(input)?;
+    let (input, value) = RIndex::parse_bytes(input)?;
+    let (input, _) = tag([0u8])(input)?;
+
+    Ok((input, SendMsg::make(target, value)))
+}
+
+impl ParseString for SendMsg {
+    type Output = Self;
+
+    /// Tries to create an [`SendMsg`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::SendMsg;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "SEND $1 $10";
+    /// let (_, send) = SendMsg::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, send.value().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, send_from_str)(input)
+    }
+
+    /// Tries to create an [`SendMsg`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::SendMsg;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "SEND $01 $0A";
+    /// let (_, send) = SendMsg::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, send.value().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, send_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for SendMsg {
+    type Output = Self;
+
+    /// Tries to create an [`SendMsg`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::SendMsg;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [16u8, 1u8, 10u8, 0u8].as_slice();
+    /// let (_, send) = SendMsg::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, send.value().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, send_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, send) = SendMsg::parse_str("SEND $1 $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(1u8, send.target().into());
+        assert_eq!(10u8, send.value().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, send) = SendMsg::parse_hex_str("SEND $01 $0A").unwrap();
+        assert_eq!(10u8, send.value().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [16u8, 1u8, 10u8, 0u8, 9u8].as_slice();
+        let (rst, send) = SendMsg::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(1u8, send.target().into());
+        assert!(SendMsg::parse_bytes([16u8, 1u8, 10u8, 1u8].as_slice()).is_err());
+    }
+}
@@ -0,0 +1,402 @@
+use std::fmt::Display;
+
+use crate::{Item, ParseBytes, ParseString, Result};
+
+use lvm_core::{Instruction, Program};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, alphanumeric1, multispace0, multispace1, not_line_ending},
+    combinator::{eof, map, peek, recognize},
+    error::{context, convert_error, VerboseError},
+    multi::many0_count,
+    sequence::pair,
+    Finish,
+};
+
+const CONTEXT: &str = "source";
+
+/// A position in a source, with the line and the column starting at 1.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Location {
+    line: usize,
+    column: usize,
+}
+
+impl Location {
+    /// Creates a [`Location`] instance.
+    pub fn make(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    /// Returns the location of the byte offset in the source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_parser::Location;
+    ///
+    /// let location = Location::locate("LOAD $1 #1\n  ADD $1 $1 $2", 13);
+    /// assert_eq!(Location::make(2, 3), location);
+    /// ```
+    pub fn locate(input: &str, offset: usize) -> Self {
+        let before = &input[..offset.min(input.len())];
+        let line = before.matches('\n').count() + 1;
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[start..].chars().count() + 1;
+        Self { line, column }
+    }
+
+    /// Returns the line.
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column.
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// The error returned when a complete source cannot be parsed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SourceError {
+    message: String,
+    location: Option<Location>,
+}
+
+impl SourceError {
+    /// Creates a [`SourceError`] instance.
+    pub fn make(message: String) -> Self {
+        Self {
+            message,
+            location: None,
+        }
+    }
+
+    /// Sets the location of the error in the source.
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Returns the description of the error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the location of the error in the source, when it is known.
+    pub fn location(&self) -> Option<Location> {
+        self.location
+    }
+}
+
+impl Display for SourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SourceError {}
+
+/// The file extensions of the programs in the binary format,
+/// read with [`ParseSource::from_binary`].
+pub const BINARY_EXTENSIONS: [&str; 2] = ["lvmb", "bin"];
+
+/// A trait that encapsulates the functions that parse
+/// a complete source, failing if any input is left unparsed.
+pub trait ParseSource: Sized {
+    /// Parses a complete string.
+    fn from_source(input: &str) -> std::result::Result<Self, SourceError>;
+
+    /// Parses a complete string, returning the location of every instruction.
+    fn from_located_source(input: &str) -> std::result::Result<(Self, Vec<Location>), SourceError>;
+
+    /// Parses a complete hex-string.
+    fn from_hex_source(input: &str) -> std::result::Result<Self, SourceError>;
+
+    /// Parses a complete hex-string, returning the location of every instruction.
+    fn from_located_hex_source(
+        input: &str,
+    ) -> std::result::Result<(Self, Vec<Location>), SourceError>;
+
+    /// Parses a complete slice of bytes.
+    fn from_binary(input: &[u8]) -> std::result::Result<Self, SourceError>;
+}
+
+/// Parses a comment, from `;` to the end of the line.
+fn comment(input: &str) -> Result<&str, Item> {
+    let (input, _) = tag(";")(input)?;
+    let (input, text) = not_line_ending(input)?;
+
+    Ok((input, Item::Comment(text.trim_end().to_string())))
+}
+
+/// Parses a label, a name followed by `:`.
+fn label(input: &str) -> Result<&str, Item> {
+    let name_start = alt((alpha1, tag("_")));
+    let name_rest = many0_count(alt((alphanumeric1, tag("_"))));
+    let (input, name) = recognize(pair(name_start, name_rest))(input)?;
+    let (input, _) = tag(":")(input)?;
+
+    Ok((input, Item::Label(name.to_string())))
+}
+
+/// Parses the instruction with its mnemonic in any case.
+fn any_case<F>(input: &str, instruction: F) -> Result<&str, Instruction>
+where
+    F: Fn(&str) -> Result<&str, Instruction>,
+{
+    // The uppercase source has the same length, so its offsets are the offsets of the input.
+    let end = input
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(input.len());
+    let upper = input[..end].to_ascii_uppercase() + &input[end..];
+    let at = |rest: &str| &input[input.len() - rest.len()..];
+
+    match instruction(&upper) {
+        Ok((rest, i)) => Ok((at(rest), i)),
+        Err(e) => Err(e.map(|e| VerboseError {
+            errors: e.errors.into_iter().map(|(r, k)| (at(r), k)).collect(),
+        })),
+    }
+}
+
+/// The grammars of the sources.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Grammar {
+    /// The instructions, with their mnemonics in uppercase.
+    Program,
+    /// The instructions, with their mnemonics in any case, the labels and the comments.
+    Listing,
+}
+
+/// Parses the items, with the remaining input at the start of each.
+fn items<F>(input: &str, instruction: F, grammar: Grammar) -> Result<&str, Vec<(&str, Item)>>
+where
+    F: Fn(&str) -> Result<&str, Instruction>,
+{
+    let mut xs = vec![];
+    let (mut input, _) = multispace0(input)?;
+
+    while !input.is_empty() {
+        let (rest, i) = match grammar {
+            Grammar::Program => map(&instruction, Item::Instruction)(input)?,
+            Grammar::Listing => alt((
+                comment,
+                label,
+                map(|i| any_case(i, &instruction), Item::Instruction),
+            ))(input)?,
+        };
+        let (rest, _) = match grammar {
+            Grammar::Program => alt((multispace1, eof))(rest)?,
+            Grammar::Listing => alt((multispace1, eof, peek(tag(";"))))(rest)?,
+        };
+
+        xs.push((input, i));
+        input = rest;
+    }
+
+    Ok((input, xs))
+}
+
+/// Parses a complete source into its items, with the location of every item.
+pub(crate) fn from_str<F>(
+    input: &str,
+    instruction: F,
+    grammar: Grammar,
+) -> std::result::Result<(Vec<Item>, Vec<Location>), SourceError>
+where
+    F: Fn(&str) -> Result<&str, Instruction>,
+{
+    let locate = |rest: &str| Location::locate(input, input.len() - rest.len());
+
+    context(CONTEXT, |i| items(i, &instruction, grammar))(input)
+        .finish()
+        .map(|(_, xs)| xs.into_iter().map(|(rest, i)| (i, locate(rest))).unzip())
+        .map_err(|e| {
+            let location = e.errors.first().map(|(rest, _)| locate(rest));
+            let error = SourceError::make(convert_error(input, e));
+            match location {
+                Some(location) => error.with_location(location),
+                None => error,
+            }
+        })
+}
+
+/// Keeps the instructions of the items, with their locations.
+fn program(items: Vec<Item>, locations: Vec<Location>) -> (Program, Vec<Location>) {
+    let (instructions, locations) = items
+        .into_iter()
+        .zip(locations)
+        .filter_map(|(item, location)| match item {
+            Item::Instruction(i) => Some((i, location)),
+            _ => None,
+        })
+        .unzip();
+
+    (Program::make(instructions), locations)
+}
+
+impl ParseSource for Program {
+    /// Tries to create a [`Program`] instance from a complete source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Program;
+    /// use lvm_parser::*;
+    ///
+    /// let program = Program::from_source("LOAD $1 #10\nADD $1 $1 $2\n").unwrap();
+    /// assert_eq!(2, program.len());
+    ///
+    /// let res = Program::from_source("LOAD $1 #10\nLOAD $1 10\n");
+    /// assert!(res.is_err());
+    /// ```
+    fn from_source(input: &str) -> std::result::Result<Self, SourceError> {
+        Self::from_located_source(input).map(|(program, _)| program)
+    }
+
+    /// Tries to create a [`Program`] instance from a complete source,
+    /// with the location of every instruction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Program;
+    /// use lvm_parser::*;
+    ///
+    /// let (program, locations) = Program::from_located_source("LOAD $1 #10\n  PRTI $1").unwrap();
+    /// assert_eq!(2, program.len());
+    /// assert_eq!(Location::make(2, 3), locations[1]);
+    /// ```
+    fn from_located_source(input: &str) -> std::result::Result<(Self, Vec<Location>), SourceError> {
+        from_str(input, Instruction::parse_str, Grammar::Program)
+            .map(|(items, locations)| program(items, locations))
+    }
+
+    /// Tries to create a [`Program`] instance from a complete hex source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Program;
+    /// use lvm_parser::*;
+    ///
+    /// let program = Program::from_hex_source("LOAD $0A #01F4\n").unwrap();
+    /// assert_eq!(1, program.len());
+    /// ```
+    fn from_hex_source(input: &str) -> std::result::Result<Self, SourceError> {
+        Self::from_located_hex_source(input).map(|(program, _)| program)
+    }
+
+    /// Tries to create a [`Program`] instance from a complete hex source,
+    /// with the location of every instruction.
+    fn from_located_hex_source(
+        input: &str,
+    ) -> std::result::Result<(Self, Vec<Location>), SourceError> {
+        from_str(input, Instruction::parse_hex_str, Grammar::Program)
+            .map(|(items, locations)| program(items, locations))
+    }
+
+    /// Tries to create a [`Program`] instance from a complete slice of bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Program;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [1u8, 10u8, 1u8, 0xF4u8].as_slice();
+    /// let program = Program::from_binary(input).unwrap();
+    /// assert_eq!(1, program.len());
+    /// ```
+    fn from_binary(input: &[u8]) -> std::result::Result<Self, SourceError> {
+        let (rest, program) = Program::parse_bytes(input)
+            .finish()
+            .map_err(|e| SourceError::make(format!("{:?}", e)))?;
+
+        if rest.is_empty() {
+            Ok(program)
+        } else {
+            let offset = input.len() - rest.len();
+            let message = format!("invalid instruction at offset {}", offset);
+            Err(SourceError::make(message))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_source() {
+        let input = "\n  LOAD $10 #500\n\nADD $10 $20 $30  \n";
+        let program = Program::from_source(input).unwrap();
+
+        assert_eq!(2, program.len());
+    }
+
+    #[test]
+    fn from_source_failed() {
+        let input = "LOAD $10 #500\nADD $10 $20\n";
+        let err = Program::from_source(input).unwrap_err();
+
+        assert!(err.message().contains("line 2"));
+        assert_eq!(Some(2), err.location().map(|l| l.line()));
+    }
+
+    #[test]
+    fn locations() {
+        let input = "\n  LOAD $10 #500\n\nADD $10 $20 $30  \n";
+        let (_, locations) = Program::from_located_source(input).unwrap();
+
+        assert_eq!(vec![Location::make(2, 3), Location::make(4, 1)], locations);
+    }
+
+    #[test]
+    fn from_source_trivia() {
+        // The comments, the labels and the lowercase mnemonics are for the listings only.
+        assert!(Program::from_source("LOAD $1 #2 ; two").is_err());
+        assert!(Program::from_source("start:\nLOAD $1 #2").is_err());
+        assert!(Program::from_source("load $1 #2").is_err());
+        assert!(Program::from_hex_source("Add $01 $01 $02").is_err());
+    }
+
+    #[test]
+    fn from_source_trailing() {
+        let input = "LOAD $10 #500X";
+        assert!(Program::from_source(input).is_err());
+    }
+
+    #[test]
+    fn from_hex_source() {
+        let input = "LOAD $0A #01F4\nADD $0A $14 $1E";
+        let program = Program::from_hex_source(input).unwrap();
+
+        assert_eq!(2, program.len());
+    }
+
+    #[test]
+    fn from_binary() {
+        let input = [1u8, 10u8, 1u8, 0xF4u8, 2u8, 10u8, 20u8, 30u8].as_slice();
+        let program = Program::from_binary(input).unwrap();
+
+        assert_eq!(2, program.len());
+    }
+
+    #[test]
+    fn from_binary_failed() {
+        let input = [1u8, 10u8, 1u8, 0xF4u8, 0xFFu8, 10u8, 20u8, 30u8].as_slice();
+        let err = Program::from_binary(input).unwrap_err();
+
+        assert_eq!("invalid instruction at offset 4", err.message());
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{RIndex, Spawn};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "spawn";
+
+fn spawn_from_str(input: &str) -> Result<&str, Spawn> {
+    let (input, _) = tag(Spawn::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, entry) = RIndex::parse_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, pid) = RIndex::parse_str(input)?;
+
+    Ok((input, Spawn::make(entry, pid)))
+}
+
+fn spawn_from_hex_str(input: &str) -> Result<&str, Spawn> {
+    let (input, _) = tag(Spawn::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, entry) = RIndex::parse_hex_str(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, pid) = RIndex::parse_hex_str(input)?;
+
+    Ok((input, Spawn::make(entry, pid)))
+}
+
+fn spawn_from_bytes(input: &[u8]) -> Result<&[u8], Spawn> {
+    let (input, _) = tag([Spawn::ID])(input)?;
+    let (input, entry) = RIndex::parse_bytes(input)?;
+    let (input, pid) = RIndex::parse_bytes(input)?;
+    let (input, _) = tag([0u8])(input)?;
+
+    Ok((input, Spawn::make(entry, pid)))
+}
+
+impl ParseString for Spawn {
+    type Output = Self;
+
+    /// Tries to create an [`Spawn`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Spawn;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "SPAWN $1 $10";
+    /// let (_, spawn) = Spawn::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, spawn.pid().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, spawn_from_str)(input)
+    }
+
+    /// Tries to create an [`Spawn`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Spawn;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "SPAWN $01 $0A";
+    /// let (_, spawn) = Spawn::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(10u8, spawn.pid().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, spawn_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for Spawn {
+    type Output = Self;
+
+    /// Tries to create an [`Spawn`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Spawn;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [15u8, 1u8, 10u8, 0u8].as_slice();
+    /// let (_, spawn) = Spawn::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(10u8, spawn.pid().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, spawn_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, spawn) = Spawn::parse_str("SPAWN $1 $10 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(1u8, spawn.entry().into());
+        assert_eq!(10u8, spawn.pid().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, spawn) = Spawn::parse_hex_str("SPAWN $01 $0A").unwrap();
+        assert_eq!(10u8, spawn.pid().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [15u8, 1u8, 10u8, 0u8, 9u8].as_slice();
+        let (rst, spawn) = Spawn::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(1u8, spawn.entry().into());
+        assert!(Spawn::parse_bytes([15u8, 1u8, 10u8, 1u8].as_slice()).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{ParseBytes, ParseString, Result};
+
+use lvm_core::{Operand16, Syscall};
+use nom::{bytes::complete::tag, character::complete::multispace1, error::context};
+
+const CONTEXT: &str = "syscall";
+
+fn syscall_from_str(input: &str) -> Result<&str, Syscall> {
+    let (input, _) = tag(Syscall::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, number) = Operand16::parse_str(input)?;
+
+    Ok((input, Syscall::make(number)))
+}
+
+fn syscall_from_hex_str(input: &str) -> Result<&str, Syscall> {
+    let (input, _) = tag(Syscall::PREFIX)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, number) = Operand16::parse_hex_str(input)?;
+
+    Ok((input, Syscall::make(number)))
+}
+
+fn syscall_from_bytes(input: &[u8]) -> Result<&[u8], Syscall> {
+    let (input, _) = tag([Syscall::ID])(input)?;
+    let (input, number) = Operand16::parse_bytes(input)?;
+    let (input, _) = tag([0u8])(input)?;
+
+    Ok((input, Syscall::make(number)))
+}
+
+impl ParseString for Syscall {
+    type Output = Self;
+
+    /// Tries to create an [`Syscall`] instance by parsing a string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Syscall;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "SYSCALL #42";
+    /// let (_, syscall) = Syscall::parse_str(input).unwrap();
+    ///
+    /// assert_eq!(42u16, syscall.number().into());
+    /// ```
+    fn parse_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, syscall_from_str)(input)
+    }
+
+    /// Tries to create an [`Syscall`] instance by parsing a hex string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Syscall;
+    /// use lvm_parser::*;
+    ///
+    /// let input = "SYSCALL #002A";
+    /// let (_, syscall) = Syscall::parse_hex_str(input).unwrap();
+    ///
+    /// assert_eq!(42u16, syscall.number().into());
+    /// ```
+    fn parse_hex_str(input: &str) -> Result<&str, Self::Output> {
+        context(CONTEXT, syscall_from_hex_str)(input)
+    }
+}
+
+impl ParseBytes for Syscall {
+    type Output = Self;
+
+    /// Tries to create an [`Syscall`] instance by parsing a slide of bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvm_core::Syscall;
+    /// use lvm_parser::*;
+    ///
+    /// let input = [3u8, 0u8, 42u8, 0u8].as_slice();
+    /// let (_, syscall) = Syscall::parse_bytes(input).unwrap();
+    ///
+    /// assert_eq!(42u16, syscall.number().into());
+    /// ```
+    fn parse_bytes(input: &[u8]) -> Result<&[u8], Self::Output> {
+        context(CONTEXT, syscall_from_bytes)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_str() {
+        let (rst, syscall) = Syscall::parse_str("SYSCALL #500 ABC").unwrap();
+
+        assert_eq!(" ABC", rst);
+        assert_eq!(500u16, syscall.number().into());
+    }
+
+    #[test]
+    fn parse_hex_str() {
+        let (_, syscall) = Syscall::parse_hex_str("SYSCALL #01F4").unwrap();
+        assert_eq!(500u16, syscall.number().into());
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let input = [3u8, 1u8, 0xF4u8, 0u8, 9u8].as_slice();
+        let (rst, syscall) = Syscall::parse_bytes(input).unwrap();
+
+        assert_eq!(1, rst.len());
+        assert_eq!(500u16, syscall.number().into());
+    }
+
+    #[test]
+    fn parse_bytes_failed() {
+        let input = [3u8, 1u8, 0xF4u8, 1u8].as_slice();
+        assert!(Syscall::parse_bytes(input).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+mod repl;
+
+pub use repl::*;
@@ -1,12 +1,127 @@
-mod repl;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, IsTerminal},
+    path::PathBuf,
+    process::ExitCode,
+};
 
-pub use repl::*;
+use clap::Parser;
+use lvm_repl::*;
 
-fn main() {
-    let mut repl = Repl::builder()
+/// The language VM repl.
+#[derive(Debug, Parser)]
+#[command(name = "lvm-repl", version)]
+struct Args {
+    /// The configuration file, defaults to `lvm-repl/config.toml` in the user's config directory.
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Ignores the default configuration file.
+    #[arg(long, conflicts_with = "config")]
+    no_config: bool,
+
+    /// Runs the commands of the file (`-` for the standard input) in batch mode.
+    /// The batch mode is also used when the standard input is not a terminal.
+    #[arg(short, long)]
+    script: Option<PathBuf>,
+
+    /// The name of the repl.
+    #[arg(long)]
+    name: Option<String>,
+
+    /// The prompt of the repl.
+    #[arg(long)]
+    prompt: Option<String>,
+
+    /// Highlights the input.
+    #[arg(long, overrides_with = "no_color")]
+    color: bool,
+
+    /// Does not highlight the input.
+    #[arg(long, overrides_with = "color")]
+    no_color: bool,
+
+    /// The number base used to display values.
+    #[arg(long, value_enum)]
+    base: Option<Base>,
+
+    /// The number of registers of the virtual machine.
+    #[arg(long)]
+    registers: Option<usize>,
+
+    /// The history file, defaults to `lvm-repl/history.txt` in the user's data directory.
+    #[arg(long)]
+    history: Option<PathBuf>,
+
+    /// Does not load or save the history.
+    #[arg(long, conflicts_with = "history")]
+    no_history: bool,
+}
+
+impl Args {
+    fn config(&self) -> anyhow::Result<ReplConfig> {
+        let mut config = match (&self.config, ReplConfig::default_path()) {
+            (Some(path), _) => ReplConfig::load(path)?,
+            (None, Some(path)) if !self.no_config && path.exists() => ReplConfig::load(&path)?,
+            _ => ReplConfig::default(),
+        };
+
+        config.name = self.name.clone().or(config.name);
+        config.prompt = self.prompt.clone().or(config.prompt);
+        config.color = match (self.color, self.no_color) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => config.color,
+        };
+        config.base = self.base.or(config.base);
+        config.vm.registers = self.registers.or(config.vm.registers);
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    fn input(&self) -> anyhow::Result<Option<Box<dyn BufRead>>> {
+        let input: Box<dyn BufRead> = match &self.script {
+            Some(path) if path.as_os_str() != "-" => Box::new(BufReader::new(File::open(path)?)),
+            Some(_) => Box::new(std::io::stdin().lock()),
+            None if !std::io::stdin().is_terminal() => Box::new(std::io::stdin().lock()),
+            None => return Ok(None),
+        };
+
+        Ok(Some(input))
+    }
+
+    fn history(&self) -> Option<PathBuf> {
+        if self.no_history {
+            None
+        } else {
+            self.history
+                .clone()
+                .or_else(ReplConfig::default_history_path)
+        }
+    }
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+    let config = args.config()?;
+
+    let mut builder = Repl::builder()
         .with_name("Language VM".into())
-        // .with_prompt("*>".into())
-        .build();
+        .with_config(&config);
+
+    match args.input()? {
+        Some(input) => {
+            builder = builder
+                .with_input(input)
+                .with_output(Box::new(std::io::stdout()));
+        }
+        None => {
+            if let Some(history) = args.history() {
+                builder = builder.with_history_file(history);
+            }
+        }
+    }
 
-    repl.run().unwrap();
+    builder.build().run()
 }
@@ -1,8 +1,13 @@
-use lvm_machine::VM;
+use lvm_machine::{VMConfig, VM};
 use rustyline::{config::Configurer, ColorMode};
 
-use crate::Repl;
-use std::io::Write;
+use crate::{commands, Base, CommandRegistry, Repl, ReplCommand, ReplConfig, ReplHelper};
+use std::{
+    collections::VecDeque,
+    io::{BufRead, Write},
+    path::PathBuf,
+    rc::Rc,
+};
 
 pub struct ReplBuilder {
     name: String,
@@ -10,12 +15,19 @@ pub struct ReplBuilder {
     prompt: String,
     out: Box<dyn Write>,
     color_mode: ColorMode,
+    base: Base,
+    vm_config: VMConfig,
+    history: Option<PathBuf>,
+    input: Option<Box<dyn BufRead>>,
+    commands: CommandRegistry,
+    undo_limit: usize,
 }
 
 impl ReplBuilder {
     const DEFAULT_NAME: &str = "lvm repl";
     const DEFAULT_PROMPT: &str = ">";
     const VERSION: &str = "0.1.0";
+    const DEFAULT_UNDO_LIMIT: usize = 100;
 
     pub(crate) fn new() -> Self {
         ReplBuilder {
@@ -24,12 +36,32 @@ impl ReplBuilder {
             version: ReplBuilder::VERSION.into(),
             out: Box::new(std::io::stderr()),
             color_mode: ColorMode::Disabled,
+            base: Base::Dec,
+            vm_config: ReplConfig::default().vm_config(),
+            history: None,
+            input: None,
+            commands: commands::builtin_commands(),
+            undo_limit: ReplBuilder::DEFAULT_UNDO_LIMIT,
         }
     }
 
     pub fn build(self) -> Repl {
-        let mut editor = rustyline::Editor::<()>::new().unwrap();
+        let vm = VM::with_config(self.vm_config);
+        let color = self.color_mode != ColorMode::Disabled;
+        let helper = ReplHelper::new(
+            color,
+            vm.registers().len(),
+            self.base,
+            self.commands.signatures(),
+        );
+
+        let mut editor = rustyline::Editor::<ReplHelper>::new().unwrap();
         editor.set_color_mode(self.color_mode);
+        editor.set_helper(Some(helper));
+        if let Some(history) = &self.history {
+            // A missing history file is expected on the first run.
+            let _ = editor.load_history(history);
+        }
 
         Repl {
             name: self.name,
@@ -37,8 +69,48 @@ impl ReplBuilder {
             prompt: format!("{} ", self.prompt),
             out: self.out,
             editor,
-            vm: VM::new(),
+            color,
+            vm,
+            session: vec![],
+            base: self.base,
+            history: self.history,
+            input: self.input,
+            errors: 0,
+            commands: self.commands,
+            last: None,
+            undo: VecDeque::with_capacity(self.undo_limit),
+            redo: vec![],
+            undo_limit: self.undo_limit,
+        }
+    }
+
+    /// Applies the options which are set in a configuration file.
+    pub fn with_config(mut self, config: &ReplConfig) -> Self {
+        if let Some(name) = &config.name {
+            self.name = name.clone();
+        }
+
+        if let Some(prompt) = &config.prompt {
+            self.prompt = prompt.clone();
+        }
+
+        if let Some(color) = config.color {
+            self.color_mode = if color {
+                ColorMode::Enabled
+            } else {
+                ColorMode::Disabled
+            };
+        }
+
+        if let Some(base) = config.base {
+            self.base = base;
         }
+
+        if config.vm.registers.is_some() || config.vm.journal.is_some() {
+            self.vm_config = config.vm_config();
+        }
+
+        self
     }
 
     pub fn with_name(mut self, n: String) -> Self {
@@ -55,6 +127,45 @@ impl ReplBuilder {
         self.color_mode = ColorMode::Enabled;
         self
     }
+
+    pub fn with_base(mut self, b: Base) -> Self {
+        self.base = b;
+        self
+    }
+
+    pub fn with_vm_config(mut self, c: VMConfig) -> Self {
+        self.vm_config = c;
+        self
+    }
+
+    /// Registers a command, replacing any built-in command with the same name.
+    pub fn with_command(mut self, c: impl ReplCommand + 'static) -> Self {
+        self.commands.register(Rc::new(c));
+        self
+    }
+
+    /// Sets how many executed instructions can be undone.
+    pub fn with_undo_limit(mut self, l: usize) -> Self {
+        self.undo_limit = l;
+        self
+    }
+
+    /// Reads the lines from the input instead of the interactive editor.
+    pub fn with_input(mut self, i: Box<dyn BufRead>) -> Self {
+        self.input = Some(i);
+        self
+    }
+
+    pub fn with_output(mut self, o: Box<dyn Write>) -> Self {
+        self.out = o;
+        self
+    }
+
+    /// Loads the history from the file and saves it back on exit.
+    pub fn with_history_file(mut self, h: PathBuf) -> Self {
+        self.history = Some(h);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -70,4 +181,18 @@ mod tests {
         assert_eq!("Test", repl.name);
         assert_eq!("*> ", repl.prompt);
     }
+
+    #[test]
+    fn build_with_config() {
+        let config =
+            ReplConfig::parse("prompt = \"*>\"\nbase = \"hex\"\n[vm]\nregisters = 4").unwrap();
+        let repl = Repl::builder()
+            .with_name("Test".into())
+            .with_config(&config)
+            .build();
+        assert_eq!("Test", repl.name);
+        assert_eq!("*> ", repl.prompt);
+        assert_eq!(Base::Hex, repl.base);
+        assert_eq!(4, repl.vm.registers().len());
+    }
 }
@@ -0,0 +1,175 @@
+use std::rc::Rc;
+
+use crate::Repl;
+
+/// Tells the repl whether to read the next line or to terminate.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IterationResult {
+    Continue,
+    Break,
+}
+
+/// A command which can be typed at the repl prompt, e.g. `:i`.
+///
+/// # Examples
+///
+/// ```
+/// use lvm_repl::{IterationResult, Repl, ReplCommand};
+///
+/// struct Pc;
+///
+/// impl ReplCommand for Pc {
+///     fn name(&self) -> &str {
+///         ":pc"
+///     }
+///
+///     fn help(&self) -> &str {
+///         "prints the program counter"
+///     }
+///
+///     fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+///         let pc = repl.vm().pc();
+///         writeln!(repl.out(), "{}", pc)?;
+///         Ok(IterationResult::Continue)
+///     }
+/// }
+///
+/// let repl = Repl::builder().with_command(Pc).build();
+/// ```
+pub trait ReplCommand {
+    /// The name which runs the command.
+    fn name(&self) -> &str;
+
+    /// Other names which run the command.
+    fn aliases(&self) -> &[&str] {
+        &[]
+    }
+
+    /// The names of the arguments, shown in the help and the hints.
+    fn arguments(&self) -> &[&str] {
+        &[]
+    }
+
+    /// A one line description of the command.
+    fn help(&self) -> &str;
+
+    /// Runs the command with the rest of the line as argument.
+    fn execute(&self, repl: &mut Repl, argument: &str) -> anyhow::Result<IterationResult>;
+}
+
+/// The commands known by a repl, in the order they are listed by the help.
+#[derive(Default, Clone)]
+pub struct CommandRegistry {
+    commands: Vec<Rc<dyn ReplCommand>>,
+}
+
+impl CommandRegistry {
+    /// Adds a command, which takes precedence over the
+    /// previously registered commands with the same name.
+    pub fn register(&mut self, command: Rc<dyn ReplCommand>) {
+        self.commands.push(command);
+    }
+
+    /// Finds the command run by the given name or alias.
+    pub fn find(&self, name: &str) -> Option<Rc<dyn ReplCommand>> {
+        self.commands
+            .iter()
+            .rev()
+            .find(|c| c.name() == name || c.aliases().contains(&name))
+            .cloned()
+    }
+
+    /// Returns the commands which are not shadowed by a later registration.
+    pub fn iter(&self) -> impl Iterator<Item = &Rc<dyn ReplCommand>> {
+        self.commands.iter().enumerate().filter_map(|(i, c)| {
+            let shadowed = self.commands[i + 1..].iter().any(|d| d.name() == c.name());
+            (!shadowed).then_some(c)
+        })
+    }
+
+    /// Returns every name and alias with the names of the arguments.
+    pub fn signatures(&self) -> Vec<(String, Vec<String>)> {
+        self.iter()
+            .flat_map(|c| {
+                let arguments: Vec<String> = c.arguments().iter().map(|a| a.to_string()).collect();
+                std::iter::once(c.name())
+                    .chain(c.aliases().iter().copied())
+                    .map(move |n| (n.to_string(), arguments.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns the help line of a command.
+    pub fn usage(command: &dyn ReplCommand) -> String {
+        let mut usage = command.name().to_string();
+        for alias in command.aliases() {
+            usage.push_str(", ");
+            usage.push_str(alias);
+        }
+        for argument in command.arguments() {
+            usage.push(' ');
+            usage.push_str(argument);
+        }
+
+        format!("{} - {}", usage, command.help())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fake(&'static str, &'static str);
+
+    impl ReplCommand for Fake {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn aliases(&self) -> &[&str] {
+            &[":f"]
+        }
+
+        fn arguments(&self) -> &[&str] {
+            &["<x>"]
+        }
+
+        fn help(&self) -> &str {
+            self.1
+        }
+
+        fn execute(&self, _repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+            Ok(IterationResult::Continue)
+        }
+    }
+
+    #[test]
+    fn find() {
+        let mut registry = CommandRegistry::default();
+        registry.register(Rc::new(Fake(":fake", "first")));
+        registry.register(Rc::new(Fake(":fake", "second")));
+
+        assert_eq!("second", registry.find(":fake").unwrap().help());
+        assert_eq!("second", registry.find(":f").unwrap().help());
+        assert!(registry.find(":other").is_none());
+        assert_eq!(1, registry.iter().count());
+    }
+
+    #[test]
+    fn usage() {
+        let usage = CommandRegistry::usage(&Fake(":fake", "does nothing"));
+        assert_eq!(":fake, :f <x> - does nothing", usage);
+    }
+
+    #[test]
+    fn signatures() {
+        let mut registry = CommandRegistry::default();
+        registry.register(Rc::new(Fake(":fake", "first")));
+
+        let expected = vec![
+            (":fake".to_string(), vec!["<x>".to_string()]),
+            (":f".to_string(), vec!["<x>".to_string()]),
+        ];
+        assert_eq!(expected, registry.signatures());
+    }
+}
@@ -0,0 +1,598 @@
+//! The commands every repl knows.
+
+use std::rc::Rc;
+
+use anyhow::bail;
+use lvm_core::RIndex;
+
+use crate::{
+    Base, CommandRegistry, IterationResult, Repl, ReplCommand, NEW_VALUE_COLOR, OLD_VALUE_COLOR,
+    REGISTER_COLOR, RESET_COLOR,
+};
+
+/// Terminates the application.
+pub struct Quit;
+
+impl ReplCommand for Quit {
+    fn name(&self) -> &str {
+        ":q"
+    }
+
+    fn aliases(&self) -> &[&str] {
+        &[":quit"]
+    }
+
+    fn help(&self) -> &str {
+        "terminates the application"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        writeln!(repl.out, "Quiting")?;
+        Ok(IterationResult::Break)
+    }
+}
+
+/// Prints the help of the registered commands.
+pub struct Help;
+
+impl ReplCommand for Help {
+    fn name(&self) -> &str {
+        ":h"
+    }
+
+    fn aliases(&self) -> &[&str] {
+        &[":help"]
+    }
+
+    fn help(&self) -> &str {
+        "prints the help"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        let usages: Vec<String> = repl
+            .commands
+            .iter()
+            .map(|c| CommandRegistry::usage(c.as_ref()))
+            .collect();
+
+        writeln!(repl.out, "{} - {} repl", repl.name, repl.version)?;
+        writeln!(repl.out, "Help here")?;
+        for usage in usages {
+            writeln!(repl.out, "  {}", usage)?;
+        }
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Prints the registers in the current base.
+pub struct Info;
+
+impl ReplCommand for Info {
+    fn name(&self) -> &str {
+        ":i"
+    }
+
+    fn help(&self) -> &str {
+        "prints the internal information"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        match repl.base {
+            Base::Dec => writeln!(repl.out, "{}", repl.vm)?,
+            Base::Hex => writeln!(repl.out, "{:X}", repl.vm)?,
+        }
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Prints the registers in hex.
+pub struct InfoHex;
+
+impl ReplCommand for InfoHex {
+    fn name(&self) -> &str {
+        ":ix"
+    }
+
+    fn help(&self) -> &str {
+        "prints the internal information in hex format"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        writeln!(repl.out, "{:X}", repl.vm)?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Switches to hex input and output.
+pub struct Hex;
+
+impl ReplCommand for Hex {
+    fn name(&self) -> &str {
+        ":hex"
+    }
+
+    fn help(&self) -> &str {
+        "switches to hex input and output"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        repl.set_base(Base::Hex)?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Switches to decimal input and output.
+pub struct Dec;
+
+impl ReplCommand for Dec {
+    fn name(&self) -> &str {
+        ":dec"
+    }
+
+    fn help(&self) -> &str {
+        "switches to decimal input and output"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        repl.set_base(Base::Dec)?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Loads a program file and runs it.
+pub struct Load;
+
+impl ReplCommand for Load {
+    fn name(&self) -> &str {
+        ":load"
+    }
+
+    fn arguments(&self) -> &[&str] {
+        &["<file>"]
+    }
+
+    fn help(&self) -> &str {
+        "loads and runs a program"
+    }
+
+    fn execute(&self, repl: &mut Repl, argument: &str) -> anyhow::Result<IterationResult> {
+        repl.load(argument, true)?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Loads a program file without running it.
+pub struct Stage;
+
+impl ReplCommand for Stage {
+    fn name(&self) -> &str {
+        ":stage"
+    }
+
+    fn arguments(&self) -> &[&str] {
+        &["<file>"]
+    }
+
+    fn help(&self) -> &str {
+        "loads a program"
+    }
+
+    fn execute(&self, repl: &mut Repl, argument: &str) -> anyhow::Result<IterationResult> {
+        repl.load(argument, false)?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Executes the next instruction of the loaded program.
+pub struct Step;
+
+impl ReplCommand for Step {
+    fn name(&self) -> &str {
+        ":step"
+    }
+
+    fn help(&self) -> &str {
+        "executes the next program instruction"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        if !repl.step()? {
+            writeln!(repl.out, "Program done")?;
+        }
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Executes the rest of the loaded program.
+pub struct Run;
+
+impl ReplCommand for Run {
+    fn name(&self) -> &str {
+        ":run"
+    }
+
+    fn help(&self) -> &str {
+        "executes the rest of the program"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        repl.run_program()?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Saves the executed instructions to a file.
+pub struct Save;
+
+impl ReplCommand for Save {
+    fn name(&self) -> &str {
+        ":save"
+    }
+
+    fn arguments(&self) -> &[&str] {
+        &["<file>"]
+    }
+
+    fn help(&self) -> &str {
+        "saves the executed instructions"
+    }
+
+    fn execute(&self, repl: &mut Repl, argument: &str) -> anyhow::Result<IterationResult> {
+        repl.write_program(argument)?;
+        writeln!(repl.out, "Saved {} instructions", repl.session.len())?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Prints a register in the current base.
+pub struct Reg;
+
+impl ReplCommand for Reg {
+    fn name(&self) -> &str {
+        ":reg"
+    }
+
+    fn arguments(&self) -> &[&str] {
+        &["$reg"]
+    }
+
+    fn help(&self) -> &str {
+        "prints a register"
+    }
+
+    fn execute(&self, repl: &mut Repl, argument: &str) -> anyhow::Result<IterationResult> {
+        let rindx = repl.parse_register(argument)?;
+        let value = repl.format_value(repl.vm.register(rindx)?);
+        writeln!(repl.out, "{}: {}", repl.format_register(rindx), value)?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Changes the value of a register.
+pub struct Set;
+
+impl ReplCommand for Set {
+    fn name(&self) -> &str {
+        ":set"
+    }
+
+    fn arguments(&self) -> &[&str] {
+        &["$reg", "#value"]
+    }
+
+    fn help(&self) -> &str {
+        "sets the value of a register"
+    }
+
+    fn execute(&self, repl: &mut Repl, argument: &str) -> anyhow::Result<IterationResult> {
+        let (rindx, value) = match argument.split_once(char::is_whitespace) {
+            Some((rindx, value)) => (rindx, value.trim()),
+            None => bail!("Missing value"),
+        };
+
+        let rindx = repl.parse_register(rindx)?;
+        let value = repl.parse_value(value)?;
+        let before = repl.vm.snapshot();
+        repl.vm.set_register(rindx, value)?;
+        // The value set is not recorded, so stepping back would lose it.
+        repl.vm.clear_journal();
+        repl.record_command(format!("{} {}", self.name(), argument.trim()), before);
+        let (rindx, value) = (repl.format_register(rindx), repl.format_value(value));
+        writeln!(repl.out, "{}: {}", rindx, value)?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Clears the registers and rewinds the loaded program.
+pub struct Reset;
+
+impl ReplCommand for Reset {
+    fn name(&self) -> &str {
+        ":reset"
+    }
+
+    fn help(&self) -> &str {
+        "clears the registers and rewinds the program"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        let before = repl.vm.snapshot();
+        repl.vm.reset();
+        repl.record_command(self.name().to_string(), before);
+        writeln!(repl.out, "Reset")?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Prints the registers changed by the last executed instruction.
+pub struct Diff;
+
+impl ReplCommand for Diff {
+    fn name(&self) -> &str {
+        ":diff"
+    }
+
+    fn help(&self) -> &str {
+        "prints the registers changed by the last instruction"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        let (instruction, changes) = match &repl.last {
+            Some(last) => last.clone(),
+            None => {
+                writeln!(repl.out, "Nothing executed")?;
+                return Ok(IterationResult::Continue);
+            }
+        };
+
+        let paint = |color: &str, text: String| match repl.color {
+            true => format!("{}{}{}", color, text, RESET_COLOR),
+            false => text,
+        };
+        let changes: Vec<String> = changes
+            .into_iter()
+            .map(|(i, old, new)| {
+                let register = repl.format_register(RIndex::make(i as u8));
+                let (old, new) = (repl.format_value(old), repl.format_value(new));
+                format!(
+                    "  {}: {} -> {}",
+                    paint(REGISTER_COLOR, register),
+                    paint(OLD_VALUE_COLOR, old),
+                    paint(NEW_VALUE_COLOR, new)
+                )
+            })
+            .collect();
+
+        match repl.base {
+            Base::Dec => writeln!(repl.out, "Changed by {}:", instruction)?,
+            Base::Hex => writeln!(repl.out, "Changed by {:X}:", instruction)?,
+        }
+        if changes.is_empty() {
+            writeln!(repl.out, "  nothing")?;
+        }
+        for change in changes {
+            writeln!(repl.out, "{}", change)?;
+        }
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Writes the state of the machine to an image file.
+pub struct Dump;
+
+impl ReplCommand for Dump {
+    fn name(&self) -> &str {
+        ":dump"
+    }
+
+    fn arguments(&self) -> &[&str] {
+        &["<file>"]
+    }
+
+    fn help(&self) -> &str {
+        "writes the machine state to an image"
+    }
+
+    fn execute(&self, repl: &mut Repl, argument: &str) -> anyhow::Result<IterationResult> {
+        repl.dump(argument)?;
+        writeln!(repl.out, "Dumped the machine state")?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Replaces the machine with the state of an image file.
+pub struct Restore;
+
+impl ReplCommand for Restore {
+    fn name(&self) -> &str {
+        ":restore"
+    }
+
+    fn arguments(&self) -> &[&str] {
+        &["<file>"]
+    }
+
+    fn help(&self) -> &str {
+        "restores the machine state from an image"
+    }
+
+    fn execute(&self, repl: &mut Repl, argument: &str) -> anyhow::Result<IterationResult> {
+        repl.restore(argument)?;
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Reverts the last instruction recorded by the machine.
+pub struct Back;
+
+impl ReplCommand for Back {
+    fn name(&self) -> &str {
+        ":back"
+    }
+
+    fn help(&self) -> &str {
+        "steps back over the last executed instruction"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        match repl.step_back() {
+            Some(instruction) => match repl.base {
+                Base::Dec => writeln!(repl.out, "Reverted: {}", instruction)?,
+                Base::Hex => writeln!(repl.out, "Reverted: {:X}", instruction)?,
+            },
+            None => writeln!(repl.out, "Nothing to step back")?,
+        }
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Steps back to the last write of a register.
+pub struct ReverseContinue;
+
+impl ReplCommand for ReverseContinue {
+    fn name(&self) -> &str {
+        ":rc"
+    }
+
+    fn aliases(&self) -> &[&str] {
+        &[":reverse-continue"]
+    }
+
+    fn arguments(&self) -> &[&str] {
+        &["$reg"]
+    }
+
+    fn help(&self) -> &str {
+        "steps back to the last write of a register"
+    }
+
+    fn execute(&self, repl: &mut Repl, argument: &str) -> anyhow::Result<IterationResult> {
+        let rindx = repl.parse_register(argument)?;
+        match repl.reverse_continue(rindx) {
+            Some(instruction) => match repl.base {
+                Base::Dec => writeln!(repl.out, "Reverted: {}", instruction)?,
+                Base::Hex => writeln!(repl.out, "Reverted: {:X}", instruction)?,
+            },
+            None => {
+                let rindx = repl.format_register(rindx);
+                writeln!(repl.out, "No recorded write of {}", rindx)?;
+            }
+        }
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Goes back to the state before the last executed instruction.
+pub struct Undo;
+
+impl ReplCommand for Undo {
+    fn name(&self) -> &str {
+        ":undo"
+    }
+
+    fn help(&self) -> &str {
+        "undoes the last executed instruction, :set or :reset"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        match repl.undo() {
+            Some(action) => match repl.base {
+                Base::Dec => writeln!(repl.out, "Undone: {}", action)?,
+                Base::Hex => writeln!(repl.out, "Undone: {:X}", action)?,
+            },
+            None => writeln!(repl.out, "Nothing to undo")?,
+        }
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Goes forward to the state after the last undone instruction.
+pub struct Redo;
+
+impl ReplCommand for Redo {
+    fn name(&self) -> &str {
+        ":redo"
+    }
+
+    fn help(&self) -> &str {
+        "redoes the last undone instruction, :set or :reset"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        match repl.redo() {
+            Some(action) => match repl.base {
+                Base::Dec => writeln!(repl.out, "Redone: {}", action)?,
+                Base::Hex => writeln!(repl.out, "Redone: {:X}", action)?,
+            },
+            None => writeln!(repl.out, "Nothing to redo")?,
+        }
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Prints the instructions which can be undone and redone.
+pub struct History;
+
+impl ReplCommand for History {
+    fn name(&self) -> &str {
+        ":history"
+    }
+
+    fn help(&self) -> &str {
+        "prints the instructions which can be undone and redone"
+    }
+
+    fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+        let done = repl.undo.iter().map(|(a, _)| (a, ""));
+        let undone = repl.redo.iter().rev().map(|(a, _)| (a, " (undone)"));
+        let lines: Vec<String> = done
+            .chain(undone)
+            .enumerate()
+            .map(|(n, (action, mark))| match repl.base {
+                Base::Dec => format!("  {}: {}{}", n + 1, action, mark),
+                Base::Hex => format!("  {}: {:X}{}", n + 1, action, mark),
+            })
+            .collect();
+
+        writeln!(repl.out, "History:")?;
+        for line in lines {
+            writeln!(repl.out, "{}", line)?;
+        }
+        Ok(IterationResult::Continue)
+    }
+}
+
+/// Returns a registry with the built-in commands.
+pub fn builtin_commands() -> CommandRegistry {
+    let mut registry = CommandRegistry::default();
+    let commands: [Rc<dyn ReplCommand>; 22] = [
+        Rc::new(Help),
+        Rc::new(Quit),
+        Rc::new(Info),
+        Rc::new(InfoHex),
+        Rc::new(Reg),
+        Rc::new(Set),
+        Rc::new(Reset),
+        Rc::new(Diff),
+        Rc::new(Undo),
+        Rc::new(Redo),
+        Rc::new(History),
+        Rc::new(Back),
+        Rc::new(ReverseContinue),
+        Rc::new(Hex),
+        Rc::new(Dec),
+        Rc::new(Load),
+        Rc::new(Stage),
+        Rc::new(Step),
+        Rc::new(Run),
+        Rc::new(Save),
+        Rc::new(Dump),
+        Rc::new(Restore),
+    ];
+
+    for command in commands {
+        registry.register(command);
+    }
+
+    registry
+}
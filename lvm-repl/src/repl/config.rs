@@ -0,0 +1,148 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use lvm_machine::VMConfig;
+use serde::Deserialize;
+
+/// The number base used to display values.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Base {
+    #[default]
+    Dec,
+    Hex,
+}
+
+/// The configuration of the virtual machine in the configuration file.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VMSection {
+    pub registers: Option<usize>,
+    /// The number of bytes used to record the writes of the executed instructions.
+    pub journal: Option<usize>,
+}
+
+/// The content of the configuration file.
+/// The options which are not set keep the defaults of the [`crate::ReplBuilder`].
+///
+/// ```toml
+/// name = "Language VM"
+/// prompt = "*>"
+/// color = true
+/// base = "hex"
+///
+/// [vm]
+/// registers = 16
+/// journal = 1048576
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplConfig {
+    pub name: Option<String>,
+    pub prompt: Option<String>,
+    pub color: Option<bool>,
+    pub base: Option<Base>,
+    #[serde(default)]
+    pub vm: VMSection,
+}
+
+impl ReplConfig {
+    const APPLICATION: &'static str = "lvm-repl";
+    const CONFIG_FILE: &'static str = "config.toml";
+    const HISTORY_FILE: &'static str = "history.txt";
+
+    /// The default number of bytes of the machine undo log.
+    pub const DEFAULT_JOURNAL: usize = 1 << 20;
+
+    /// Returns the default location of the configuration file.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join(Self::APPLICATION).join(Self::CONFIG_FILE))
+    }
+
+    /// Returns the default location of the history file.
+    pub fn default_history_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|d| d.join(Self::APPLICATION).join(Self::HISTORY_FILE))
+    }
+
+    /// Parses the content of a configuration file.
+    pub fn parse(src: &str) -> anyhow::Result<Self> {
+        let config: Self = toml::from_str(src)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks the values of the options.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(registers) = self.vm.registers {
+            if registers == 0 || registers > VMConfig::MAX_REGISTERS {
+                bail!(
+                    "the number of registers must be between 1 and {}",
+                    VMConfig::MAX_REGISTERS
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads a configuration file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let src = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read {}", path.display()))?;
+        Self::parse(&src).with_context(|| format!("Invalid configuration {}", path.display()))
+    }
+
+    /// Returns the configuration of the virtual machine.
+    pub fn vm_config(&self) -> VMConfig {
+        let journal = self.vm.journal.unwrap_or(Self::DEFAULT_JOURNAL);
+        let config = VMConfig::default().with_journal(journal);
+        match self.vm.registers {
+            Some(registers) => config.with_registers(registers),
+            None => config,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let src = r#"
+            name = "Test"
+            prompt = "*>"
+            color = true
+            base = "hex"
+
+            [vm]
+            registers = 16
+            journal = 1024
+        "#;
+
+        let config = ReplConfig::parse(src).unwrap();
+        assert_eq!(Some("Test".to_string()), config.name);
+        assert_eq!(Some("*>".to_string()), config.prompt);
+        assert_eq!(Some(true), config.color);
+        assert_eq!(Some(Base::Hex), config.base);
+        assert_eq!(16, config.vm_config().registers());
+        assert_eq!(Some(1024), config.vm_config().journal());
+    }
+
+    #[test]
+    fn parse_empty() {
+        let config = ReplConfig::parse("").unwrap();
+        assert_eq!(ReplConfig::default(), config);
+        assert_eq!(
+            VMConfig::default().with_journal(ReplConfig::DEFAULT_JOURNAL),
+            config.vm_config()
+        );
+    }
+
+    #[test]
+    fn parse_failed() {
+        assert!(ReplConfig::parse("colour = true").is_err());
+        assert!(ReplConfig::parse("[vm]\nregisters = 0").is_err());
+        assert!(ReplConfig::parse("base = \"oct\"").is_err());
+    }
+}
@@ -0,0 +1,397 @@
+use std::borrow::Cow;
+
+use lvm_core::{
+    Add, Close, Div, FRead, FWrite, Halt, Instruction, Iret, Ivec, Load, Open, Operand16, Prtc,
+    Prti, RIndex, Read, RecvMsg, SendMsg, Spawn, Syscall,
+};
+use lvm_parser::ParseString;
+use rustyline::{
+    completion::{Completer, FilenameCompleter, Pair},
+    highlight::Highlighter,
+    hint::{Hint, Hinter},
+    validate::{ValidationContext, ValidationResult, Validator},
+    Context,
+};
+use rustyline_derive::Helper;
+
+use crate::Base;
+
+const MNEMONIC_COLOR: &str = "\x1b[1;34m";
+const COMMAND_COLOR: &str = "\x1b[1;35m";
+pub(crate) const REGISTER_COLOR: &str = "\x1b[33m";
+const IMMEDIATE_COLOR: &str = "\x1b[32m";
+const HINT_COLOR: &str = "\x1b[2m";
+pub(crate) const OLD_VALUE_COLOR: &str = "\x1b[31m";
+pub(crate) const NEW_VALUE_COLOR: &str = "\x1b[1;32m";
+pub(crate) const RESET_COLOR: &str = "\x1b[0m";
+
+/// The mnemonics with the names of their arguments.
+const MNEMONICS: [(&str, &[&str]); 17] = [
+    (Load::PREFIX, &["$reg", "#value"]),
+    (Add::PREFIX, &["$lhs", "$rhs", "$dst"]),
+    (Syscall::PREFIX, &["#number"]),
+    (Prti::PREFIX, &["$reg"]),
+    (Prtc::PREFIX, &["$reg"]),
+    (Read::PREFIX, &["$reg"]),
+    (Open::PREFIX, &["$path", "$mode", "$handle"]),
+    (FRead::PREFIX, &["$handle", "$buffer", "$len"]),
+    (FWrite::PREFIX, &["$handle", "$buffer", "$len"]),
+    (Close::PREFIX, &["$handle"]),
+    (Div::PREFIX, &["$lhs", "$rhs", "$dst"]),
+    (Ivec::PREFIX, &["#vector", "#address"]),
+    (Iret::PREFIX, &[]),
+    (Halt::PREFIX, &[]),
+    (Spawn::PREFIX, &["$entry", "$pid"]),
+    (SendMsg::PREFIX, &["$pid", "$value"]),
+    (RecvMsg::PREFIX, &["$sender", "$value"]),
+];
+
+/// A hint describing the arguments still to be typed.
+/// It is only displayed, never inserted in the line.
+pub struct ArgumentsHint(String);
+
+impl Hint for ArgumentsHint {
+    fn display(&self) -> &str {
+        &self.0
+    }
+
+    fn completion(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// The rustyline helper which completes, hints, highlights and validates the input.
+#[derive(Helper)]
+pub struct ReplHelper {
+    color: bool,
+    registers: usize,
+    base: Base,
+    commands: Vec<(String, Vec<String>)>,
+    files: FilenameCompleter,
+}
+
+impl ReplHelper {
+    /// Creates a [`ReplHelper`] instance which knows the
+    /// given commands with the names of their arguments.
+    pub fn new(
+        color: bool,
+        registers: usize,
+        base: Base,
+        commands: Vec<(String, Vec<String>)>,
+    ) -> Self {
+        Self {
+            color,
+            registers,
+            base,
+            commands,
+            files: FilenameCompleter::new(),
+        }
+    }
+
+    /// Returns the number base used to validate the input.
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Sets the number base used to validate the input.
+    pub fn set_base(&mut self, base: Base) {
+        self.base = base;
+    }
+
+    /// Sets the number of registers offered by the completion.
+    pub fn set_registers(&mut self, registers: usize) {
+        self.registers = registers;
+    }
+
+    fn arguments(&self, word: &str) -> Option<Vec<&str>> {
+        let mnemonic = MNEMONICS
+            .iter()
+            .find(|(name, _)| *name == word)
+            .map(|(_, args)| args.to_vec());
+
+        mnemonic.or_else(|| {
+            self.commands
+                .iter()
+                .find(|(name, _)| name == word)
+                .map(|(_, args)| args.iter().map(|a| a.as_str()).collect())
+        })
+    }
+
+    fn takes_file(&self, word: &str) -> bool {
+        self.arguments(word).is_some_and(|args| args == ["<file>"])
+    }
+
+    fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<Pair>) {
+        let start = line[..pos]
+            .char_indices()
+            .rfind(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let word = &line[start..pos];
+
+        let names: Vec<String> = if start == 0 {
+            MNEMONICS
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .chain(self.commands.iter().map(|(name, _)| name.clone()))
+                .collect()
+        } else if word.starts_with(RIndex::PREFIX) {
+            (0..self.registers)
+                .map(|i| RIndex::make(i as u8).to_string())
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let pairs = names
+            .into_iter()
+            .filter(|name| {
+                name.len() >= word.len() && name[..word.len()].eq_ignore_ascii_case(word)
+            })
+            .map(|name| Pair {
+                display: name.clone(),
+                replacement: name,
+            })
+            .collect();
+
+        (start, pairs)
+    }
+
+    fn hint_arguments(&self, line: &str) -> Option<String> {
+        let mut words = line.split_whitespace();
+        let args = self.arguments(words.next()?)?;
+
+        let rest = args.get(words.count()..)?;
+        if rest.is_empty() {
+            None
+        } else if line.ends_with(char::is_whitespace) {
+            Some(rest.join(" "))
+        } else {
+            Some(format!(" {}", rest.join(" ")))
+        }
+    }
+
+    fn parse(line: &str, base: Base) -> lvm_parser::Result<&str, Instruction> {
+        match base {
+            Base::Dec => Instruction::parse_str(line),
+            Base::Hex => Instruction::parse_hex_str(line),
+        }
+    }
+
+    fn paint(&self, word: &str) -> Option<&'static str> {
+        if MNEMONICS.iter().any(|(name, _)| *name == word) {
+            Some(MNEMONIC_COLOR)
+        } else if self.commands.iter().any(|(name, _)| name == word) {
+            Some(COMMAND_COLOR)
+        } else if word.starts_with(RIndex::PREFIX) {
+            Some(REGISTER_COLOR)
+        } else if word.starts_with(Operand16::PREFIX) {
+            Some(IMMEDIATE_COLOR)
+        } else {
+            None
+        }
+    }
+
+    fn validate_line(line: &str, base: Base) -> ValidationResult {
+        let mut words = line.split_whitespace();
+        let expected = match words.next() {
+            Some(word) => match MNEMONICS.iter().find(|(name, _)| *name == word) {
+                Some((_, args)) => args.len(),
+                None => return ValidationResult::Valid(None),
+            },
+            None => return ValidationResult::Valid(None),
+        };
+
+        if words.count() < expected {
+            ValidationResult::Incomplete
+        } else if Self::parse(line.trim(), base).map_or(true, |(rest, _)| !rest.is_empty()) {
+            ValidationResult::Invalid(Some(" - Not an instruction".into()))
+        } else {
+            ValidationResult::Valid(None)
+        }
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        match line.split_whitespace().next() {
+            Some(word) if self.takes_file(word) && line[..pos].contains(char::is_whitespace) => {
+                self.files.complete(line, pos, ctx)
+            }
+            _ => Ok(self.candidates(line, pos)),
+        }
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = ArgumentsHint;
+
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<ArgumentsHint> {
+        if pos < line.len() {
+            return None;
+        }
+
+        self.hint_arguments(line).map(ArgumentsHint)
+    }
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        if !self.color {
+            return Cow::Borrowed(line);
+        }
+
+        let mut highlighted = String::with_capacity(line.len());
+        let mut rest = line;
+        while !rest.is_empty() {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (word, tail) = rest.split_at(end);
+            match self.paint(word) {
+                Some(color) => {
+                    highlighted.push_str(color);
+                    highlighted.push_str(word);
+                    highlighted.push_str(RESET_COLOR);
+                }
+                None => highlighted.push_str(word),
+            }
+
+            let end = tail
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(tail.len());
+            let (spaces, tail) = tail.split_at(end);
+            highlighted.push_str(spaces);
+            rest = tail;
+        }
+
+        Cow::Owned(highlighted)
+    }
+
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        if self.color {
+            Cow::Owned(format!("{}{}{}", HINT_COLOR, hint, RESET_COLOR))
+        } else {
+            Cow::Borrowed(hint)
+        }
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize) -> bool {
+        self.color
+    }
+}
+
+impl Validator for ReplHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        Ok(Self::validate_line(ctx.input(), self.base))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::builtin_commands;
+
+    fn make_helper(color: bool) -> ReplHelper {
+        ReplHelper::new(color, 8, Base::Dec, builtin_commands().signatures())
+    }
+
+    fn replacements(helper: &ReplHelper, line: &str) -> Vec<String> {
+        let (_, pairs) = helper.candidates(line, line.len());
+        pairs.into_iter().map(|p| p.replacement).collect()
+    }
+
+    #[test]
+    fn complete() {
+        let helper = make_helper(false);
+
+        assert_eq!(vec!["LOAD"], replacements(&helper, "lo"));
+        assert_eq!(vec![":load"], replacements(&helper, ":lo"));
+        assert_eq!(
+            vec![":set", ":stage", ":step", ":save"],
+            replacements(&helper, ":s")
+        );
+        assert_eq!(8, replacements(&helper, "ADD $").len());
+        assert_eq!(vec!["$1"], replacements(&helper, "LOAD $1"));
+        assert!(replacements(&helper, "LOAD $1 #").is_empty());
+
+        // A multibyte space separates the words too.
+        assert_eq!(vec!["$1"], replacements(&helper, "LOAD\u{a0}$1"));
+        assert!(replacements(&helper, "LOAD\u{a0}").is_empty());
+    }
+
+    #[test]
+    fn hint() {
+        let helper = make_helper(false);
+        assert_eq!(
+            Some(" $reg #value".to_string()),
+            helper.hint_arguments("LOAD")
+        );
+        assert_eq!(
+            Some("#value".to_string()),
+            helper.hint_arguments("LOAD $1 ")
+        );
+        assert_eq!(
+            Some(" $rhs $dst".to_string()),
+            helper.hint_arguments("ADD $1")
+        );
+        assert_eq!(None, helper.hint_arguments("ADD $1 $2 $3"));
+        assert_eq!(None, helper.hint_arguments(":q"));
+        assert_eq!(None, helper.hint_arguments("FOO"));
+        assert_eq!(Some(" <file>".to_string()), helper.hint_arguments(":load"));
+    }
+
+    #[test]
+    fn highlight() {
+        let helper = make_helper(true);
+        let line = "LOAD  $1 #10";
+        let expected = format!(
+            "{}LOAD{}  {}$1{} {}#10{}",
+            MNEMONIC_COLOR, RESET_COLOR, REGISTER_COLOR, RESET_COLOR, IMMEDIATE_COLOR, RESET_COLOR
+        );
+        assert_eq!(expected, helper.highlight(line, 0));
+
+        let helper = make_helper(false);
+        assert_eq!(line, helper.highlight(line, 0));
+    }
+
+    #[test]
+    fn validate() {
+        let valid = |line| {
+            matches!(
+                ReplHelper::validate_line(line, Base::Dec),
+                ValidationResult::Valid(_)
+            )
+        };
+        let incomplete = |line| {
+            matches!(
+                ReplHelper::validate_line(line, Base::Dec),
+                ValidationResult::Incomplete
+            )
+        };
+        let invalid = |line| {
+            matches!(
+                ReplHelper::validate_line(line, Base::Dec),
+                ValidationResult::Invalid(_)
+            )
+        };
+
+        assert!(valid("LOAD $1 #10"));
+        assert!(valid("LOAD $1\n#10"));
+        assert!(valid(":i"));
+        assert!(valid("FOO"));
+        assert!(incomplete("LOAD $1"));
+        assert!(incomplete("ADD"));
+        assert!(invalid("LOAD $1 10"));
+        assert!(invalid("LOAD $0A #000A"));
+
+        assert!(matches!(
+            ReplHelper::validate_line("LOAD $0A #000A", Base::Hex),
+            ValidationResult::Valid(_)
+        ));
+    }
+}
@@ -1,7 +1,15 @@
 //! A REPL application
 
 mod builder;
+mod command;
+pub mod commands;
+mod config;
+mod helper;
+#[allow(clippy::module_inception)]
 mod repl;
 
 pub use builder::*;
+pub use command::*;
+pub use config::*;
+pub use helper::*;
 pub use repl::*;
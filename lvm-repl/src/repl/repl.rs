@@ -1,25 +1,70 @@
-use std::io::Write;
+use std::{
+    collections::VecDeque,
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
-use anyhow::Error;
-use lvm_core::Instruction;
-use lvm_machine::VM;
-use lvm_parser::ParseString;
+use anyhow::{bail, Context, Error};
+use lvm_core::{Instruction, Operand16, Program, RIndex};
+use lvm_machine::{Image, Snapshot, VM};
+use lvm_parser::{ParseSource, ParseString, BINARY_EXTENSIONS};
 use rustyline::error::ReadlineError;
 
-use crate::ReplBuilder;
+use crate::{Base, CommandRegistry, IterationResult, ReplBuilder, ReplHelper};
+
+/// A register changed by an instruction: the index, the old and the new value.
+pub(crate) type Change = (usize, u16, u16);
+
+/// A change of the state which can be undone: an executed instruction,
+/// or a command like `:set`, with its arguments.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum Action {
+    Instruction(Instruction),
+    Command(String),
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Instruction(instruction) => write!(f, "{}", instruction),
+            Self::Command(command) => write!(f, "{}", command),
+        }
+    }
+}
+
+impl std::fmt::UpperHex for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Instruction(instruction) => write!(f, "{:X}", instruction),
+            Self::Command(command) => write!(f, "{}", command),
+        }
+    }
+}
 
 pub struct Repl {
     pub(crate) name: String,
     pub(crate) version: String,
     pub(crate) prompt: String,
     pub(crate) out: Box<dyn Write>,
-    pub(crate) editor: rustyline::Editor<()>,
+    pub(crate) editor: rustyline::Editor<ReplHelper>,
+    /// Highlights the output of the commands, like the input.
+    pub(crate) color: bool,
     pub(crate) vm: VM,
-}
-
-enum IterationResult {
-    Continue,
-    Break,
+    /// The executed instructions, with the program counter they ran at, saved by `:save`.
+    pub(crate) session: Vec<(usize, Instruction)>,
+    pub(crate) base: Base,
+    pub(crate) history: Option<PathBuf>,
+    pub(crate) input: Option<Box<dyn BufRead>>,
+    pub(crate) errors: usize,
+    pub(crate) commands: CommandRegistry,
+    /// The last executed instruction with the registers it changed.
+    pub(crate) last: Option<(Instruction, Vec<Change>)>,
+    /// The executed actions with the state before their execution, oldest first.
+    pub(crate) undo: VecDeque<(Action, Snapshot)>,
+    /// The undone actions with the state after their execution, latest undone last.
+    pub(crate) redo: Vec<(Action, Snapshot)>,
+    pub(crate) undo_limit: usize,
 }
 
 impl Repl {
@@ -27,59 +72,395 @@ impl Repl {
         ReplBuilder::new()
     }
 
-    fn parse_instruction(input: &str) -> anyhow::Result<Instruction> {
-        Instruction::parse_str(input)
-            .map(|(_, i)| i)
+    /// Returns the virtual machine.
+    pub fn vm(&self) -> &VM {
+        &self.vm
+    }
+
+    /// Returns the virtual machine for changes.
+    pub fn vm_mut(&mut self) -> &mut VM {
+        &mut self.vm
+    }
+
+    /// Returns the output the commands write to.
+    pub fn out(&mut self) -> &mut dyn Write {
+        &mut self.out
+    }
+
+    /// Returns the number base used for input and output.
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    fn parse_instruction(input: &str, base: Base) -> anyhow::Result<Instruction> {
+        let res = match base {
+            Base::Dec => Instruction::parse_str(input),
+            Base::Hex => Instruction::parse_hex_str(input),
+        };
+
+        res.map(|(_, i)| i)
             .map_err(|_e| Error::msg("Not an instruction"))
     }
 
+    /// Parses a register index, e.g. `$3`, in the current base.
+    pub(crate) fn parse_register(&self, input: &str) -> anyhow::Result<RIndex> {
+        let res = match self.base {
+            Base::Dec => RIndex::parse_str(input),
+            Base::Hex => RIndex::parse_hex_str(input),
+        };
+
+        match res {
+            Ok(("", rindx)) => Ok(rindx),
+            _ => bail!("Not a register: {}", input),
+        }
+    }
+
+    /// Parses a value, e.g. `#42`, in the current base.
+    pub(crate) fn parse_value(&self, input: &str) -> anyhow::Result<u16> {
+        let res = match self.base {
+            Base::Dec => Operand16::parse_str(input),
+            Base::Hex => Operand16::parse_hex_str(input),
+        };
+
+        match res {
+            Ok(("", value)) => Ok(value.into()),
+            _ => bail!("Not a value: {}", input),
+        }
+    }
+
+    /// Formats a register index in the current base.
+    pub(crate) fn format_register(&self, rindx: RIndex) -> String {
+        match self.base {
+            Base::Dec => rindx.to_string(),
+            Base::Hex => format!("{}{:X}", RIndex::PREFIX, rindx),
+        }
+    }
+
+    /// Formats a register value in the current base.
+    pub(crate) fn format_value(&self, value: u16) -> String {
+        match self.base {
+            Base::Dec => value.to_string(),
+            Base::Hex => format!("0x{:X}", value),
+        }
+    }
+
+    pub(crate) fn set_base(&mut self, base: Base) -> anyhow::Result<()> {
+        self.base = base;
+        if let Some(helper) = self.editor.helper_mut() {
+            helper.set_base(base);
+        }
+
+        match base {
+            Base::Dec => writeln!(&mut self.out, "Decimal mode")?,
+            Base::Hex => writeln!(&mut self.out, "Hex mode")?,
+        }
+        Ok(())
+    }
+
+    fn echo(&mut self, instruction: &Instruction) -> anyhow::Result<()> {
+        match self.base {
+            Base::Dec => writeln!(&mut self.out, "Executing: {}", instruction)?,
+            Base::Hex => writeln!(&mut self.out, "Executing: {:X}", instruction)?,
+        }
+        Ok(())
+    }
+
+    fn is_json(path: &Path) -> bool {
+        path.extension().is_some_and(|e| e == "json")
+    }
+
+    fn is_binary(path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| BINARY_EXTENSIONS.contains(&e))
+    }
+
+    fn read_program(path: &str) -> anyhow::Result<Program> {
+        if path.is_empty() {
+            bail!("Missing file name");
+        }
+
+        let path = Path::new(path);
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Cannot read {}", path.display()))?;
+
+        let program = if Self::is_binary(path) {
+            Program::from_binary(&bytes)?
+        } else {
+            Program::from_source(std::str::from_utf8(&bytes)?)?
+        };
+
+        Ok(program)
+    }
+
+    pub(crate) fn write_program(&self, path: &str) -> anyhow::Result<()> {
+        if path.is_empty() {
+            bail!("Missing file name");
+        }
+
+        let path = Path::new(path);
+        let program = Program::make(self.session.iter().map(|(_, i)| *i).collect());
+        let bytes: Vec<u8> = if Self::is_binary(path) {
+            program.into()
+        } else {
+            format!("{}\n", program).into_bytes()
+        };
+
+        std::fs::write(path, bytes).with_context(|| format!("Cannot write {}", path.display()))
+    }
+
+    fn execute(&mut self, instruction: Instruction) -> anyhow::Result<()> {
+        self.echo(&instruction)?;
+        let before = self.vm.snapshot();
+        let pc = self.vm.pc();
+        self.vm.execute(instruction)?;
+        self.record(instruction, before);
+        self.session.push((pc, instruction));
+        Ok(())
+    }
+
+    /// Remembers the registers changed by an executed instruction
+    /// and the state to go back to when the instruction is undone.
+    fn record(&mut self, instruction: Instruction, before: Snapshot) {
+        let changes = before
+            .registers()
+            .iter()
+            .zip(self.vm.registers())
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(i, (old, new))| (i, *old, *new))
+            .collect();
+        self.last = Some((instruction, changes));
+        self.push_undo(Action::Instruction(instruction), before);
+    }
+
+    /// Remembers the state before a command changed it, so the command can be undone.
+    pub(crate) fn record_command(&mut self, command: String, before: Snapshot) {
+        self.last = None;
+        self.push_undo(Action::Command(command), before);
+    }
+
+    fn push_undo(&mut self, action: Action, before: Snapshot) {
+        self.redo.clear();
+        if self.undo_limit > 0 {
+            if self.undo.len() == self.undo_limit {
+                self.undo.pop_front();
+            }
+            self.undo.push_back((action, before));
+        }
+    }
+
+    /// Forgets the undo and redo history, e.g. after the state was changed by a command.
+    pub(crate) fn forget(&mut self) {
+        self.last = None;
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Reverts the last instruction recorded by the machine.
+    pub(crate) fn step_back(&mut self) -> Option<Instruction> {
+        let recorded = self.recorded();
+        let instruction = self.vm.step_back()?;
+        self.unwind(&recorded);
+        Some(instruction)
+    }
+
+    /// Reverts the recorded instructions up to the last write of the register.
+    pub(crate) fn reverse_continue(&mut self, watch: RIndex) -> Option<Instruction> {
+        let recorded = self.recorded();
+        let instruction = self.vm.reverse_continue(watch);
+        self.unwind(&recorded);
+        instruction
+    }
+
+    /// Returns the instructions recorded by the machine, with their program counter.
+    fn recorded(&self) -> Vec<(usize, Instruction)> {
+        self.vm.journal().map_or(vec![], |journal| {
+            journal
+                .iter()
+                .map(|entry| (entry.pc(), entry.instruction()))
+                .collect()
+        })
+    }
+
+    /// Removes from the session the instructions the machine reverted. A faulting
+    /// instruction is recorded by the machine when it wrote something, but it is
+    /// not in the session.
+    fn unwind(&mut self, recorded: &[(usize, Instruction)]) {
+        let kept = self.vm.journal().map_or(0, |journal| journal.len());
+        for entry in recorded[kept..].iter().rev() {
+            if self.session.last() == Some(entry) {
+                self.session.pop();
+            }
+        }
+        self.forget();
+    }
+
+    /// Restores the state before the last executed action.
+    pub(crate) fn undo(&mut self) -> Option<Action> {
+        let (action, before) = self.undo.pop_back()?;
+        self.redo.push((action.clone(), self.vm.snapshot()));
+        self.vm.restore(&before);
+        if let Action::Instruction(_) = action {
+            self.session.pop();
+        }
+        self.last = None;
+        Some(action)
+    }
+
+    /// Restores the state after the last undone action.
+    pub(crate) fn redo(&mut self) -> Option<Action> {
+        let (action, after) = self.redo.pop()?;
+        let pc = self.vm.pc();
+        self.undo.push_back((action.clone(), self.vm.snapshot()));
+        self.vm.restore(&after);
+        if let Action::Instruction(instruction) = action {
+            self.session.push((pc, instruction));
+        }
+        self.last = None;
+        Some(action)
+    }
+
+    pub(crate) fn step(&mut self) -> anyhow::Result<bool> {
+        let before = self.vm.snapshot();
+        match self.vm.step()? {
+            Some(instruction) => {
+                let pc = before.pc();
+                self.echo(&instruction)?;
+                self.record(instruction, before);
+                self.session.push((pc, instruction));
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    pub(crate) fn load(&mut self, path: &str, run: bool) -> anyhow::Result<()> {
+        let program = Repl::read_program(path)?;
+        writeln!(&mut self.out, "Loaded {} instructions", program.len())?;
+        self.vm.load_program(program);
+        self.forget();
+
+        if run {
+            self.run_program()?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn run_program(&mut self) -> anyhow::Result<()> {
+        while self.step()? {}
+        Ok(())
+    }
+
+    /// Writes the state of the machine to an image file,
+    /// as JSON for `.json` files and as bytes otherwise.
+    pub(crate) fn dump(&self, path: &str) -> anyhow::Result<()> {
+        if path.is_empty() {
+            bail!("Missing file name");
+        }
+
+        let path = Path::new(path);
+        let image = self.vm.image();
+        let bytes = if Self::is_json(path) {
+            image.to_json().into_bytes()
+        } else {
+            image.to_bytes()
+        };
+
+        std::fs::write(path, bytes).with_context(|| format!("Cannot write {}", path.display()))
+    }
+
+    /// Replaces the machine with the state of an image file.
+    pub(crate) fn restore(&mut self, path: &str) -> anyhow::Result<()> {
+        if path.is_empty() {
+            bail!("Missing file name");
+        }
+
+        let path = Path::new(path);
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Cannot read {}", path.display()))?;
+        let image = if Self::is_json(path) {
+            Image::from_json(std::str::from_utf8(&bytes)?)?
+        } else {
+            Image::from_bytes(&bytes)?
+        };
+
+        self.vm = VM::from_image(&image, *self.vm.config());
+        if let Some(helper) = self.editor.helper_mut() {
+            helper.set_registers(self.vm.registers().len());
+        }
+        self.forget();
+
+        writeln!(
+            &mut self.out,
+            "Restored {} registers and {} instructions",
+            image.registers().len(),
+            image.program().len()
+        )?;
+        Ok(())
+    }
+
+    fn report(&mut self, res: anyhow::Result<()>) -> anyhow::Result<IterationResult> {
+        if let Err(e) = res {
+            self.errors += 1;
+            writeln!(&mut self.out, "Error: {:#}", e)?;
+        }
+
+        Ok(IterationResult::Continue)
+    }
+
+    /// Reads the next line from the editor or, in batch mode, from the input
+    /// in which case the line is echoed after the prompt to build a transcript.
+    fn read_line(&mut self) -> Result<String, ReadlineError> {
+        let input = match &mut self.input {
+            Some(input) => input,
+            None => return self.editor.readline(&self.prompt),
+        };
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Err(ReadlineError::Eof);
+        }
+
+        let line = line.trim_end_matches(['\n', '\r']).to_string();
+        writeln!(&mut self.out, "{}{}", self.prompt, line)?;
+        Ok(line)
+    }
+
     fn iterate(&mut self) -> anyhow::Result<IterationResult> {
-        let readline = self.editor.readline(&self.prompt);
+        let readline = self.read_line();
         match readline {
-            Ok(line) => match line.as_str() {
-                ":q" => {
-                    writeln!(&mut self.out, "Quiting")?;
-                    Ok(IterationResult::Break)
-                }
-                ":h" => {
-                    self.editor.add_history_entry(line);
-                    writeln!(&mut self.out, "{} - {} repl", self.name, self.version)?;
-                    writeln!(&mut self.out, "Help here")?;
-                    writeln!(&mut self.out, "  :h - prints the help")?;
-                    writeln!(&mut self.out, "  :q - terminates the application")?;
-                    writeln!(&mut self.out, "  :i - prints the internal information")?;
-                    Ok(IterationResult::Continue)
-                }
-                ":i" => {
-                    self.editor.add_history_entry(line);
-                    writeln!(&mut self.out, "{}", self.vm)?;
-                    Ok(IterationResult::Continue)
-                }
-                ":ix" => {
-                    self.editor.add_history_entry(line);
-                    writeln!(&mut self.out, "{:X}", self.vm)?;
-                    Ok(IterationResult::Continue)
-                }
-                line => {
-                    match Repl::parse_instruction(line) {
-                        Ok(Instruction::LoadI(load)) => {
-                            self.editor.add_history_entry(line);
-                            writeln!(&mut self.out, "Executing: {}", &load)?;
-                            self.vm.run_load(load)
-                        }
-                        Ok(Instruction::AddI(add)) => {
-                            self.editor.add_history_entry(line);
-                            writeln!(&mut self.out, "Executing: {}", &add)?;
-                            self.vm.run_add(add)
+            Ok(line) if line.trim().is_empty() => Ok(IterationResult::Continue),
+            Ok(line) => {
+                let (name, argument) = match line.trim().split_once(char::is_whitespace) {
+                    Some((name, argument)) => (name, argument.trim()),
+                    None => (line.trim(), ""),
+                };
+
+                let res = match self.commands.find(name) {
+                    Some(command) => command.execute(self, argument),
+                    None => match Repl::parse_instruction(&line, self.base) {
+                        Ok(instruction) => {
+                            self.execute(instruction).map(|_| IterationResult::Continue)
                         }
                         _ => {
-                            self.editor.add_history_entry(line);
+                            self.errors += 1;
                             writeln!(&mut self.out, "Unknown: {}", line)?;
+                            Ok(IterationResult::Continue)
                         }
+                    },
+                };
+
+                match res {
+                    Ok(IterationResult::Break) => Ok(IterationResult::Break),
+                    res => {
+                        self.editor.add_history_entry(line.as_str());
+                        self.report(res.map(|_| ()))
                     }
-                    Ok(IterationResult::Continue)
                 }
-            },
+            }
+            Err(ReadlineError::Eof) if self.input.is_some() => Ok(IterationResult::Break),
             Err(ReadlineError::Interrupted) => {
                 writeln!(&mut self.out, "CTRL-C")?;
                 Ok(IterationResult::Break)
@@ -91,7 +472,9 @@ impl Repl {
         }
     }
 
-    pub fn run(&mut self) -> anyhow::Result<()> {
+    /// Runs the repl until it is terminated.
+    /// In batch mode the exit code reports whether any command failed.
+    pub fn run(&mut self) -> anyhow::Result<ExitCode> {
         writeln!(
             &mut self.out,
             "Welcome to `{} - {}` repl!",
@@ -100,6 +483,377 @@ impl Repl {
 
         while let IterationResult::Continue = self.iterate()? {}
 
+        self.out.flush()?;
+        self.save_history()?;
+
+        if self.input.is_some() && self.errors > 0 {
+            Ok(ExitCode::FAILURE)
+        } else {
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+
+    fn save_history(&mut self) -> anyhow::Result<()> {
+        if let Some(history) = &self.history {
+            if let Some(dir) = history.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            self.editor.save_history(history)?;
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::{Load, Operand16, RIndex};
+    use lvm_machine::{VMConfig, VMError};
+
+    use super::*;
+    use crate::ReplCommand;
+
+    fn make_repl() -> Repl {
+        let mut repl = Repl::builder().build();
+        repl.out = Box::new(std::io::sink());
+        repl
+    }
+
+    fn temp_file(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("lvm-repl-{}-{}", std::process::id(), name));
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn save_and_load() {
+        for name in ["session.lvm", "session.lvmb"] {
+            let path = temp_file(name);
+
+            let mut repl = make_repl();
+            repl.execute(Repl::parse_instruction("LOAD $1 #10", Base::Dec).unwrap())
+                .unwrap();
+            repl.execute(Repl::parse_instruction("ADD $1 $1 $2", Base::Dec).unwrap())
+                .unwrap();
+            repl.write_program(&path).unwrap();
+
+            let mut repl = make_repl();
+            repl.load(&path, false).unwrap();
+            assert_eq!(0, repl.vm.registers()[2]);
+            assert_eq!(2, repl.vm.program().len());
+
+            repl.run_program().unwrap();
+            assert_eq!(20, repl.vm.registers()[2]);
+            assert_eq!(2, repl.session.len());
+
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn parse_in_base() {
+        let dec = Repl::parse_instruction("LOAD $10 #10", Base::Dec).unwrap();
+        let hex = Repl::parse_instruction("LOAD $0A #000A", Base::Hex).unwrap();
+        assert_eq!(dec, hex);
+
+        assert!(Repl::parse_instruction("LOAD $0A #000A", Base::Dec).is_err());
+    }
+
+    #[test]
+    fn set_base() {
+        let mut repl = make_repl();
+        repl.set_base(Base::Hex).unwrap();
+
+        assert_eq!(Base::Hex, repl.base);
+        assert_eq!(Base::Hex, repl.editor.helper().unwrap().base());
+    }
+
+    #[test]
+    fn batch() {
+        let input = "LOAD $1 #10\n\nADD $1 $1 $2\nFOO\n:q\nLOAD $3 #1\n";
+        let mut repl = Repl::builder()
+            .with_input(Box::new(input.as_bytes()))
+            .with_output(Box::new(std::io::sink()))
+            .build();
+
+        assert_eq!(ExitCode::FAILURE, repl.run().unwrap());
+        assert_eq!(20, repl.vm.registers()[2]);
+        assert_eq!(0, repl.vm.registers()[3]);
+        assert_eq!(1, repl.errors);
+    }
+
+    #[test]
+    fn custom_command() {
+        struct Double;
+
+        impl ReplCommand for Double {
+            fn name(&self) -> &str {
+                ":double"
+            }
+
+            fn help(&self) -> &str {
+                "doubles the registers"
+            }
+
+            fn execute(&self, repl: &mut Repl, _argument: &str) -> anyhow::Result<IterationResult> {
+                let values: Vec<u16> = repl.vm().registers().to_vec();
+                for (i, value) in values.into_iter().enumerate() {
+                    let load = Load::make(RIndex::make(i as u8), Operand16::make(value * 2));
+                    repl.vm_mut().run_load(load)?;
+                }
+                Ok(IterationResult::Continue)
+            }
+        }
+
+        let input = "LOAD $1 #10\n:double\n:h\n";
+        let mut repl = Repl::builder()
+            .with_command(Double)
+            .with_input(Box::new(input.as_bytes()))
+            .with_output(Box::new(std::io::sink()))
+            .build();
+
+        assert_eq!(ExitCode::SUCCESS, repl.run().unwrap());
+        assert_eq!(20, repl.vm.registers()[1]);
+    }
+
+    #[test]
+    fn registers() {
+        let input = "LOAD $1 #10\n:set $2 #5\n:reg $2\n:set $9 #1\n:set $2\n:diff\n:reset\n";
+        let mut repl = Repl::builder()
+            .with_input(Box::new(input.as_bytes()))
+            .with_output(Box::new(std::io::sink()))
+            .build();
+
+        assert_eq!(ExitCode::FAILURE, repl.run().unwrap());
+        assert_eq!(2, repl.errors);
+        assert_eq!(&[0; 8], repl.vm.registers());
+        assert!(repl.last.is_none());
+    }
+
+    #[test]
+    fn diff() {
+        for (color, expected) in [
+            (false, "Changed by ADD $1 $1 $2:\n  $2: 0 -> 20\n"),
+            (
+                true,
+                "Changed by ADD $1 $1 $2:\n  \x1b[33m$2\x1b[0m: \x1b[31m0\x1b[0m -> \x1b[1;32m20\x1b[0m\n",
+            ),
+        ] {
+            let path = temp_file(&format!("diff-{}.out", color));
+            let mut repl = make_repl();
+            repl.color = color;
+            repl.execute(Repl::parse_instruction("LOAD $1 #10", Base::Dec).unwrap())
+                .unwrap();
+            repl.execute(Repl::parse_instruction("ADD $1 $1 $2", Base::Dec).unwrap())
+                .unwrap();
+
+            repl.out = Box::new(std::fs::File::create(&path).unwrap());
+            let diff = repl.commands.find(":diff").unwrap();
+            diff.execute(&mut repl, "").unwrap();
+            drop(repl);
+
+            assert_eq!(expected, std::fs::read_to_string(&path).unwrap());
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn last() {
+        let mut repl = make_repl();
+        repl.execute(Repl::parse_instruction("LOAD $1 #10", Base::Dec).unwrap())
+            .unwrap();
+        let instruction = Repl::parse_instruction("ADD $1 $1 $2", Base::Dec).unwrap();
+        repl.execute(instruction).unwrap();
+
+        repl.vm.set_register(RIndex::make(3), 1).unwrap();
+
+        let (last, changes) = repl.last.clone().unwrap();
+        assert_eq!(instruction, last);
+        assert_eq!(vec![(2, 0, 20)], changes);
+    }
+
+    #[test]
+    fn parse_operands() {
+        let mut repl = make_repl();
+        assert_eq!(RIndex::make(10), repl.parse_register("$10").unwrap());
+        assert_eq!(42, repl.parse_value("#42").unwrap());
+        assert!(repl.parse_register("$1x").is_err());
+        assert!(repl.parse_value("42").is_err());
+
+        repl.set_base(Base::Hex).unwrap();
+        assert_eq!(RIndex::make(10), repl.parse_register("$0A").unwrap());
+        assert_eq!(42, repl.parse_value("#002A").unwrap());
+        assert_eq!("0x2A", repl.format_value(42));
+        assert_eq!("$0A", repl.format_register(RIndex::make(10)));
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut repl = make_repl();
+        repl.execute(Repl::parse_instruction("LOAD $1 #10", Base::Dec).unwrap())
+            .unwrap();
+        let add = Repl::parse_instruction("ADD $1 $1 $2", Base::Dec).unwrap();
+        repl.execute(add).unwrap();
+
+        assert_eq!(Some(Action::Instruction(add)), repl.undo());
+        assert_eq!(0, repl.vm.registers()[2]);
+        assert_eq!(1, repl.session.len());
+
+        assert_eq!(Some(Action::Instruction(add)), repl.redo());
+        assert_eq!(20, repl.vm.registers()[2]);
+        assert_eq!(2, repl.session.len());
+        assert_eq!(None, repl.redo());
+
+        repl.undo();
+        repl.undo();
+        assert_eq!(None, repl.undo());
+        assert_eq!(&[0; 8], repl.vm.registers());
+
+        repl.execute(add).unwrap();
+        assert!(repl.redo.is_empty());
+    }
+
+    #[test]
+    fn undo_commands() {
+        let mut repl = make_repl();
+        repl.execute(Repl::parse_instruction("LOAD $1 #10", Base::Dec).unwrap())
+            .unwrap();
+        for (name, argument) in [(":set", "$2 #5"), (":reset", "")] {
+            let command = repl.commands.find(name).unwrap();
+            command.execute(&mut repl, argument).unwrap();
+        }
+        assert_eq!(&[0; 8], repl.vm.registers());
+
+        assert_eq!(Some(Action::Command(":reset".to_string())), repl.undo());
+        assert_eq!(&[0, 10, 5, 0, 0, 0, 0, 0], repl.vm.registers());
+        assert_eq!(Some(Action::Command(":set $2 #5".to_string())), repl.undo());
+        assert_eq!(&[0, 10, 0, 0, 0, 0, 0, 0], repl.vm.registers());
+        assert_eq!(1, repl.session.len());
+
+        assert!(repl.redo().is_some());
+        assert_eq!(5, repl.vm.registers()[2]);
+    }
+
+    #[test]
+    fn step_back_commands() {
+        let mut repl = make_repl();
+        repl.execute(Repl::parse_instruction("LOAD $1 #1", Base::Dec).unwrap())
+            .unwrap();
+        let set = repl.commands.find(":set").unwrap();
+        set.execute(&mut repl, "$1 #9").unwrap();
+
+        // The instructions before the command are not stepped back.
+        assert_eq!(None, repl.step_back());
+        assert_eq!(9, repl.vm.registers()[1]);
+
+        repl.execute(Repl::parse_instruction("LOAD $2 #2", Base::Dec).unwrap())
+            .unwrap();
+        let reset = repl.commands.find(":reset").unwrap();
+        reset.execute(&mut repl, "").unwrap();
+        assert_eq!(None, repl.step_back());
+        assert_eq!(&[0; 8], repl.vm.registers());
+    }
+
+    #[test]
+    fn undo_limit() {
+        let mut repl = Repl::builder()
+            .with_undo_limit(2)
+            .with_output(Box::new(std::io::sink()))
+            .build();
+        for value in 1..=3 {
+            let load = Load::make(RIndex::make(1), Operand16::make(value));
+            repl.execute(Instruction::LoadI(load)).unwrap();
+        }
+
+        repl.undo();
+        repl.undo();
+        assert_eq!(None, repl.undo());
+        assert_eq!(1, repl.vm.registers()[1]);
+    }
+
+    #[test]
+    fn dump_and_restore() {
+        for name in ["state.lvmi", "state.json"] {
+            let path = temp_file(name);
+
+            let mut repl = make_repl();
+            repl.execute(Repl::parse_instruction("LOAD $1 #10", Base::Dec).unwrap())
+                .unwrap();
+            repl.dump(&path).unwrap();
+
+            let mut repl = Repl::builder()
+                .with_vm_config(VMConfig::default().with_registers(2))
+                .with_output(Box::new(std::io::sink()))
+                .build();
+            repl.restore(&path).unwrap();
+            assert_eq!(8, repl.vm.registers().len());
+            assert_eq!(10, repl.vm.registers()[1]);
+
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn restore_invalid() {
+        let path = temp_file("invalid.lvmi");
+        std::fs::write(&path, b"LVMI\x09").unwrap();
+
+        let mut repl = make_repl();
+        let err = repl.restore(&path).unwrap_err();
+        assert_eq!("unsupported image version 9", err.to_string());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn step_back() {
+        let mut repl = make_repl();
+        for line in ["LOAD $1 #10", "LOAD $2 #20", "ADD $1 $2 $1", "LOAD $3 #30"] {
+            repl.execute(Repl::parse_instruction(line, Base::Dec).unwrap())
+                .unwrap();
+        }
+
+        let load = Repl::parse_instruction("LOAD $3 #30", Base::Dec).unwrap();
+        assert_eq!(Some(load), repl.step_back());
+        assert_eq!(3, repl.session.len());
+        assert!(repl.undo.is_empty());
+
+        let add = Repl::parse_instruction("ADD $1 $2 $1", Base::Dec).unwrap();
+        assert_eq!(Some(add), repl.reverse_continue(RIndex::make(1)));
+        assert_eq!(2, repl.session.len());
+        assert_eq!(10, repl.vm.registers()[1]);
+
+        assert_eq!(None, repl.reverse_continue(RIndex::make(7)));
+        assert!(repl.session.is_empty());
+        assert_eq!(None, repl.step_back());
+    }
+
+    #[test]
+    fn step_back_fault() {
+        let mut repl = make_repl();
+        repl.vm.register_syscall(1, |ctx| {
+            ctx.set_register(RIndex::make(2), 7)?;
+            Err(VMError::SyscallFailed(1))
+        });
+        for line in ["LOAD $1 #10", "SYSCALL #1"] {
+            let _ = repl.execute(Repl::parse_instruction(line, Base::Dec).unwrap());
+        }
+        assert_eq!(1, repl.session.len());
+
+        // The failed syscall is reverted without the load before it.
+        let syscall = Repl::parse_instruction("SYSCALL #1", Base::Dec).unwrap();
+        assert_eq!(Some(syscall), repl.step_back());
+        assert_eq!(1, repl.session.len());
+        assert_eq!(&[0, 10, 0, 0, 0, 0, 0, 0], repl.vm.registers());
+
+        let _ = repl.execute(syscall);
+        assert_eq!(None, repl.reverse_continue(RIndex::make(7)));
+        assert!(repl.session.is_empty());
+    }
+
+    #[test]
+    fn load_missing() {
+        let mut repl = make_repl();
+        assert!(repl.load("", true).is_err());
+        assert!(repl.load(&temp_file("missing.lvm"), true).is_err());
+    }
+}
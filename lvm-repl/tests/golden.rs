@@ -0,0 +1,97 @@
+//! Runs the REPL in batch mode over the `tests/golden/*.repl` scripts
+//! and compares the transcripts with the `tests/golden/*.out` files.
+//! Setting `UPDATE_GOLDEN=1` rewrites the `.out` files instead.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+}
+
+fn golden(name: &str, success: bool) {
+    golden_in(name, success, &golden_dir());
+}
+
+/// Runs the script with the working directory, for the scripts writing files.
+fn golden_in(name: &str, success: bool, cwd: &Path) {
+    let dir = golden_dir();
+    let script = dir.join(format!("{}.repl", name));
+    let expected = dir.join(format!("{}.out", name));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_lvm-repl"))
+        .current_dir(cwd)
+        .arg("--no-config")
+        .arg("--no-history")
+        .arg("--script")
+        .arg(&script)
+        .output()
+        .unwrap();
+    let transcript = String::from_utf8(output.stdout).unwrap();
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&expected, &transcript).unwrap();
+    }
+
+    assert_eq!(success, output.status.success());
+    assert_eq!(std::fs::read_to_string(expected).unwrap(), transcript);
+}
+
+#[test]
+fn basic() {
+    golden("basic", true);
+}
+
+#[test]
+fn help() {
+    golden("help", false);
+}
+
+#[test]
+fn modes() {
+    golden("modes", true);
+}
+
+#[test]
+fn program() {
+    golden("program", true);
+}
+
+#[test]
+fn errors() {
+    golden("errors", false);
+}
+
+#[test]
+fn registers() {
+    golden("registers", true);
+}
+
+#[test]
+fn undo() {
+    golden("undo", true);
+}
+
+#[test]
+fn image() {
+    let cwd = std::env::temp_dir().join(format!("lvm-repl-golden-{}", std::process::id()));
+    std::fs::create_dir_all(&cwd).unwrap();
+    std::fs::copy(golden_dir().join("sum.lvm"), cwd.join("sum.lvm")).unwrap();
+
+    golden_in("image", false, &cwd);
+    std::fs::remove_dir_all(cwd).unwrap();
+}
+
+#[test]
+fn reverse() {
+    golden("reverse", true);
+}
+
+#[test]
+fn console() {
+    golden("console", false);
+}
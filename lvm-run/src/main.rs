@@ -0,0 +1,88 @@
+mod runner;
+
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+use lvm_machine::VM;
+use runner::*;
+
+/// Runs a program on the language VM.
+#[derive(Debug, Parser)]
+#[command(name = "lvm-run", version)]
+struct Args {
+    /// The program file.
+    file: PathBuf,
+
+    /// The encoding of the program file.
+    #[arg(short, long, value_enum, default_value_t = Format::Auto)]
+    format: Format,
+
+    /// Dumps the registers when the program is done.
+    #[arg(short, long, value_enum)]
+    dump: Option<Dump>,
+
+    /// Stops with an error after executing this many instructions.
+    #[arg(short, long)]
+    limit: Option<usize>,
+
+    /// Checks the program with the verifier before running it.
+    #[arg(long)]
+    verify: bool,
+
+    /// Prints every executed instruction.
+    #[arg(short, long)]
+    trace: bool,
+
+    /// The register whose value (modulo 256) becomes the exit code.
+    #[arg(short, long, value_name = "REGISTER")]
+    exit_register: Option<usize>,
+}
+
+fn execute(args: &Args) -> anyhow::Result<u8> {
+    let program = load_program(&args.file, args.format)?;
+
+    let mut vm = VM::new();
+    if args.verify {
+        if let Err(violations) = vm.load_verified(program) {
+            for violation in &violations {
+                eprintln!("{}", violation);
+            }
+            anyhow::bail!("the program has {} violation(s)", violations.len());
+        }
+    } else {
+        vm.load_program(program);
+    }
+
+    let options = RunOptions {
+        limit: args.limit,
+        trace: args.trace,
+    };
+    let res = run(&mut vm, &options, &mut std::io::stderr());
+
+    match args.dump {
+        Some(Dump::Dec) => eprint!("{}", vm),
+        Some(Dump::Hex) => eprint!("{:X}", vm),
+        None => (),
+    }
+    res?;
+
+    match args.exit_register {
+        Some(r) => match vm.registers().get(r) {
+            Some(value) => Ok(*value as u8),
+            None => anyhow::bail!("invalid exit register ${}", r),
+        },
+        None => Ok(0),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match execute(&args) {
+        Ok(code) => ExitCode::from(code),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
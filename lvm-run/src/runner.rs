@@ -0,0 +1,147 @@
+use std::{io::Write, path::Path};
+
+use anyhow::{bail, Context};
+use lvm_core::Program;
+use lvm_machine::VM;
+use lvm_parser::{ParseSource, BINARY_EXTENSIONS};
+
+/// The encoding of a program file.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, clap::ValueEnum)]
+pub enum Format {
+    /// Chooses based on the file extension.
+    Auto,
+    /// Assembly text with decimal operands.
+    Text,
+    /// Assembly text with hex operands.
+    Hex,
+    /// Encoded instructions.
+    Binary,
+}
+
+/// The number base used for the register dump.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, clap::ValueEnum)]
+pub enum Dump {
+    Dec,
+    Hex,
+}
+
+/// The options that control how a program is run.
+#[derive(Debug, Default)]
+pub struct RunOptions {
+    pub limit: Option<usize>,
+    pub trace: bool,
+}
+
+impl Format {
+    fn resolve(self, path: &Path) -> Self {
+        match self {
+            Self::Auto => match path.extension().and_then(|e| e.to_str()) {
+                Some(ext) if BINARY_EXTENSIONS.contains(&ext) => Self::Binary,
+                _ => Self::Text,
+            },
+            format => format,
+        }
+    }
+}
+
+/// Reads and parses a program file.
+pub fn load_program(path: &Path, format: Format) -> anyhow::Result<Program> {
+    let bytes = std::fs::read(path).with_context(|| format!("cannot read {}", path.display()))?;
+
+    let program = match format.resolve(path) {
+        Format::Binary => Program::from_binary(&bytes)?,
+        format => {
+            let text = String::from_utf8(bytes)
+                .with_context(|| format!("{} is not a text file", path.display()))?;
+            match format {
+                Format::Hex => Program::from_hex_source(&text)?,
+                _ => Program::from_source(&text)?,
+            }
+        }
+    };
+
+    Ok(program)
+}
+
+/// Runs the loaded program until it is done, writing the trace to `out`.
+pub fn run(vm: &mut VM, options: &RunOptions, out: &mut dyn Write) -> anyhow::Result<usize> {
+    let mut count = 0;
+
+    while !vm.is_done() {
+        if options.limit.is_some_and(|limit| count >= limit) {
+            bail!("instruction limit of {} reached", count);
+        }
+
+        let pc = vm.pc();
+        if let Some(instruction) = vm.step().with_context(|| format!("fault at {:04}", pc))? {
+            if options.trace {
+                writeln!(out, "{:04}: {}", pc, instruction)?;
+            }
+        }
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use lvm_core::Program;
+    use lvm_parser::ParseSource;
+
+    use super::*;
+
+    fn make_vm(source: &str) -> VM {
+        let mut vm = VM::new();
+        vm.load_program(Program::from_source(source).unwrap());
+        vm
+    }
+
+    #[test]
+    fn resolve_format() {
+        assert_eq!(Format::Binary, Format::Auto.resolve(Path::new("a.lvmb")));
+        assert_eq!(Format::Text, Format::Auto.resolve(Path::new("a.lvm")));
+        assert_eq!(Format::Hex, Format::Hex.resolve(Path::new("a.lvmb")));
+    }
+
+    #[test]
+    fn run_program() {
+        let mut vm = make_vm("LOAD $1 #2\nLOAD $2 #3\nADD $1 $2 $0");
+        let mut out = vec![];
+        let options = RunOptions {
+            trace: true,
+            ..Default::default()
+        };
+
+        let count = run(&mut vm, &options, &mut out).unwrap();
+
+        assert_eq!(3, count);
+        assert_eq!(5, vm.registers()[0]);
+        assert_eq!(
+            "0000: LOAD $1 #2\n0001: LOAD $2 #3\n0002: ADD $1 $2 $0\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn run_limit() {
+        let mut vm = make_vm("LOAD $1 #2\nLOAD $2 #3\nADD $1 $2 $0");
+        let options = RunOptions {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let res = run(&mut vm, &options, &mut std::io::sink());
+        assert!(res.is_err());
+        assert_eq!(2, vm.pc());
+    }
+
+    #[test]
+    fn run_fault() {
+        let mut vm = make_vm("LOAD $1 #2\nLOAD $9 #3");
+
+        let err = run(&mut vm, &RunOptions::default(), &mut std::io::sink()).unwrap_err();
+        assert_eq!("fault at 0001", err.to_string());
+    }
+}